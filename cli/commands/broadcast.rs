@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    helpers::{api_client, wait_for_broadcast},
+    Aleo,
+    CurrentNetwork,
+};
+use aleo_rust::{ProgramManager, Transaction};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+
+/// Broadcasts a transaction built in a dry run after verifying it
+#[derive(Debug, Parser)]
+pub struct Broadcast {
+    /// JSON file holding the transaction to broadcast
+    transaction: PathBuf,
+    /// Aleo Network peer to broadcast the transaction to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
+    /// Wait for the transaction to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+}
+
+impl Broadcast {
+    pub fn parse(self) -> Result<String> {
+        // Read the transaction from the file
+        let transaction = std::fs::read_to_string(&self.transaction)
+            .map_err(|error| anyhow!("Could not read a transaction from {}: {error}", self.transaction.display()))?;
+        let transaction = serde_json::from_str::<Transaction<CurrentNetwork>>(&transaction)
+            .map_err(|error| anyhow!("{} does not hold a valid transaction: {error}", self.transaction.display()))?;
+        let transaction_id = transaction.id();
+
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
+
+        // Verify the transaction against the programs it references before broadcasting it
+        println!("{}", format!("Verifying transaction {transaction_id}...").bright_blue());
+        ProgramManager::<CurrentNetwork>::verify_transaction::<Aleo>(&transaction, &api_client)?;
+
+        // Broadcast the transaction
+        let result = api_client.transaction_broadcast(transaction);
+
+        // Wait for the transaction to be confirmed on chain if requested
+        let result = match result {
+            Ok(response) if self.wait => wait_for_broadcast(&api_client, &response).map(|_| response),
+            result => result,
+        };
+
+        // Inform the user of the result of the broadcast
+        if result.is_err() {
+            println!("{}", format!("Broadcast of transaction {transaction_id} failed with error:").red().bold());
+        } else {
+            println!("{}", format!("Transaction {transaction_id} broadcast successfully!").bright_green().bold());
+            println!("Transaction ID:");
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_config_errors() {
+        // Assert that the broadcast fails if the transaction file does not exist
+        let broadcast_missing_file = Broadcast::try_parse_from(["aleo", "missing-transaction.json"]);
+        assert!(broadcast_missing_file.unwrap().parse().is_err());
+
+        // Assert that the broadcast fails if the file does not hold a transaction
+        let directory = std::env::temp_dir().join("aleo_broadcast_test");
+        std::fs::create_dir_all(&directory).unwrap();
+        let file = directory.join("transaction.json");
+        std::fs::write(&file, "{\"type\": \"execute\"}").unwrap();
+        let broadcast_invalid_file = Broadcast::try_parse_from(["aleo", file.to_str().unwrap()]);
+        assert!(broadcast_invalid_file.unwrap().parse().is_err());
+        std::fs::remove_dir_all(directory).unwrap();
+
+        // Assert that the transaction file is required
+        assert_eq!(
+            Broadcast::try_parse_from(["aleo"]).unwrap_err().kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
+    }
+}
@@ -14,13 +14,26 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Aleo, CurrentNetwork};
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
+use crate::{
+    helpers::{
+        api_client,
+        output_transaction,
+        record_finder,
+        wait_for_confirmation,
+        CoinSelectionArg,
+        WAIT_POLL_INTERVAL_SECS,
+        WAIT_TIMEOUT_SECS,
+    },
+    Aleo,
+    CurrentNetwork,
+};
+use aleo_rust::{ApiError, Encryptor, ProgramManager};
 use snarkvm::prelude::{Ciphertext, Plaintext, PrivateKey, ProgramID, Record};
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
+use std::time::Duration;
 
 /// Deploys an Aleo program
 #[derive(Debug, Parser)]
@@ -34,9 +47,9 @@ pub struct Deploy {
     /// Directory containing the program files
     #[clap(short, long)]
     directory: Option<std::path::PathBuf>,
-    /// Aleo Network peer to broadcast the deployment to
-    #[clap(short, long)]
-    endpoint: Option<String>,
+    /// Aleo Network peer to broadcast the deployment to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
     /// Deployment fee in credits
     #[clap(short, long)]
     fee: Option<f64>,
@@ -52,6 +65,26 @@ pub struct Deploy {
     /// Password to decrypt the private key
     #[clap(short, long, conflicts_with = "private_key", requires = "ciphertext")]
     password: Option<String>,
+    /// Strategy for choosing the records that pay for the transaction
+    #[clap(long, value_enum, default_value_t = CoinSelectionArg::LargestFirst)]
+    coin_selection: CoinSelectionArg,
+    /// File to cache the account's records in, so later record searches only scan new blocks
+    #[clap(long)]
+    record_store: Option<std::path::PathBuf>,
+    /// Deploy the imports of the program that are not deployed on chain first, in dependency
+    /// order, waiting for each to be confirmed. Imports are loaded from the imports directory of
+    /// the program and paid for with records found on the network
+    #[clap(long, conflicts_with_all = &["record", "dry_run"])]
+    with_imports: bool,
+    /// Wait for the deployment to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+    /// Build the transaction without broadcasting it, printing its ID and fee
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+    /// File to write the transaction built in a dry run to as JSON
+    #[clap(long, requires = "dry_run")]
+    output: Option<std::path::PathBuf>,
 }
 
 impl Deploy {
@@ -86,21 +119,16 @@ impl Deploy {
             0u64
         };
 
-        // Setup the API client to use configured peer or default to https://vm.aleo.org/api/testnet3
-        let api_client = self
-            .endpoint
-            .map_or_else(
-                || {
-                    println!("Using default peer: https://vm.aleo.org/api/testnet3");
-                    Ok(AleoAPIClient::<CurrentNetwork>::testnet3())
-                },
-                |peer| AleoAPIClient::<CurrentNetwork>::new(&peer, "testnet3"),
-            )
-            .map_err(|e| anyhow!("{:?}", e))?;
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
 
         // Verify program is not already deployed
         println!("Verifying {} is not already deployed on the aleo network..", program_string.bright_blue());
-        ensure!(api_client.get_program(self.program_id).is_err(), "Program is already deployed");
+        match api_client.get_program(self.program_id) {
+            Ok(_) => bail!("Program is already deployed"),
+            Err(error) if ApiError::is_not_found(&error) => (),
+            Err(error) => bail!("Could not check if the program is already deployed: {error}"),
+        }
         println!("{} was not found on the Aleo Network, continuing deployment..", program_string.bright_blue());
 
         // Assume the local directory is the program directory if none is specified
@@ -140,25 +168,58 @@ impl Deploy {
             return Ok("".to_string());
         }
 
-        // Find a fee record to pay the fee if necessary
-        let fee_record = if self.record.is_none() {
-            println!("Searching for a record to spend the deployment fee from, this may take a while..");
-            let private_key = if let Some(private_key) = self.private_key {
-                private_key
+        // Deploy the program along with its imports missing on chain if requested, finding a record to
+        // pay for each deployment and waiting for each import to be confirmed before the next
+        let result = if self.with_imports {
+            println!("Attempting to deploy program {} along with its missing imports", program_string.bright_blue());
+            let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?;
+            let timeout = Duration::from_secs(WAIT_TIMEOUT_SECS);
+            let poll_interval = Duration::from_secs(WAIT_POLL_INTERVAL_SECS);
+            program_manager.deploy_program_with_imports(
+                self.program_id,
+                fee_microcredits,
+                &record_finder,
+                self.password.as_deref(),
+                timeout,
+                poll_interval,
+            )
+        } else {
+            // Find a fee record to pay the fee if necessary
+            let fee_record = if self.record.is_none() {
+                println!("Searching for a record to spend the deployment fee from, this may take a while..");
+                let private_key = if let Some(private_key) = self.private_key {
+                    private_key
+                } else {
+                    let ciphertext = self.ciphertext.as_ref().unwrap();
+                    Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
+                };
+                let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?;
+                record_finder.find_one_record(&private_key, fee_microcredits)?
             } else {
-                let ciphertext = self.ciphertext.as_ref().unwrap();
-                Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
+                self.record.unwrap()
             };
-            let record_finder = RecordFinder::new(api_client);
-            record_finder.find_one_record(&private_key, fee_microcredits)?
-        } else {
-            self.record.unwrap()
+
+            // Build the deployment without broadcasting it if this is a dry run
+            if self.dry_run {
+                let transaction = program_manager.build_deployment(
+                    self.program_id,
+                    fee_microcredits,
+                    fee_record,
+                    self.password.as_deref(),
+                )?;
+                return output_transaction(&transaction, self.output.as_ref());
+            }
+
+            // Deploy the program
+            println!("Attempting to deploy program: {}", program_string.bright_blue());
+            program_manager.deploy_program(self.program_id, fee_microcredits, fee_record, self.password.as_deref())
         };
 
-        // Deploy the program
-        println!("Attempting to deploy program: {}", program_string.bright_blue());
-        let result =
-            program_manager.deploy_program(self.program_id, fee_microcredits, fee_record, self.password.as_deref());
+        // Wait for the deployment to be confirmed on chain if requested
+        let result = match result {
+            Ok(response) if self.wait => wait_for_confirmation(&program_manager, &response).map(|_| response),
+            result => result,
+        };
 
         // Inform the user of the result of the program deployment
         if result.is_err() {
@@ -235,5 +296,16 @@ mod tests {
         ]);
 
         assert!(deploy_bad_peer.unwrap().parse().is_err());
+
+        // Assert the wait flag is parsed
+        let deploy_wait = Deploy::try_parse_from(["aleo", "hello.aleo", "-f", "0.5", "--wait"]);
+        assert!(deploy_wait.unwrap().wait);
+
+        // Assert imports cannot be deployed along with the program in a dry run or with a given record
+        let deploy_with_imports = Deploy::try_parse_from(["aleo", "hello.aleo", "-f", "0.5", "--with-imports"]);
+        assert!(deploy_with_imports.unwrap().with_imports);
+        let deploy_with_imports_dry_run =
+            Deploy::try_parse_from(["aleo", "hello.aleo", "-f", "0.5", "--with-imports", "--dry-run"]);
+        assert_eq!(deploy_with_imports_dry_run.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
     }
 }
@@ -14,11 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Aleo, CurrentNetwork};
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
-use snarkvm::prelude::{Ciphertext, Identifier, Plaintext, PrivateKey, ProgramID, Record, Value};
+use crate::{
+    helpers::{api_client, output_transaction, record_finder, wait_for_confirmation, CoinSelectionArg},
+    Aleo,
+    CurrentNetwork,
+};
+use aleo_rust::{Encryptor, InputValidator, KeyCache, ProgramManager};
+use snarkvm::prelude::{Address, Ciphertext, Identifier, Plaintext, PrivateKey, ProgramID, Record, Value};
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 
@@ -35,9 +39,9 @@ pub struct Execute {
     /// program but will NOT execute the program
     #[clap(long)]
     estimate_fee: bool,
-    /// Aleo Network peer to broadcast the transaction to
-    #[clap(short, long)]
-    endpoint: Option<String>,
+    /// Aleo Network peer to broadcast the transaction to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
     /// Execution fee in credits
     #[clap(long)]
     fee: Option<f64>,
@@ -53,6 +57,25 @@ pub struct Execute {
     /// Password to decrypt the private key
     #[clap(short, long, conflicts_with = "private_key", requires = "ciphertext")]
     password: Option<String>,
+    /// Strategy for choosing the records that pay for the transaction
+    #[clap(long, value_enum, default_value_t = CoinSelectionArg::LargestFirst)]
+    coin_selection: CoinSelectionArg,
+    /// File to cache the account's records in, so later record searches only scan new blocks
+    #[clap(long)]
+    record_store: Option<std::path::PathBuf>,
+    /// Directory to cache the proving and verifying keys of the program in, so later executions
+    /// skip synthesizing them
+    #[clap(long)]
+    key_cache: Option<std::path::PathBuf>,
+    /// Wait for the execution to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+    /// Build the transaction without broadcasting it, printing its ID and fee
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+    /// File to write the transaction built in a dry run to as JSON
+    #[clap(long, requires = "dry_run")]
+    output: Option<std::path::PathBuf>,
 }
 
 impl Execute {
@@ -95,18 +118,8 @@ impl Execute {
             0u64
         };
 
-        // Setup the API client to use configured peer or default to https://vm.aleo.org/api/testnet3
-        let api_client = self
-            .endpoint
-            .clone()
-            .map_or_else(
-                || {
-                    println!("Using default peer: https://vm.aleo.org/api/testnet3");
-                    Ok(AleoAPIClient::<CurrentNetwork>::testnet3())
-                },
-                |peer| AleoAPIClient::<CurrentNetwork>::new(&peer, "testnet3"),
-            )
-            .map_err(|e| anyhow!("{:?}", e))?;
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
 
         // Create the program manager and find the program
         println!("Attempting to find program: {}", program_string.bright_blue());
@@ -116,8 +129,25 @@ impl Execute {
             Some(api_client.clone()),
             None,
         )?;
+        if let Some(path) = &self.key_cache {
+            program_manager = program_manager.with_key_cache(KeyCache::open(path)?);
+        }
         let program = program_manager.find_program(&self.program_id)?;
 
+        // Check the inputs against the function signature before any proving work
+        let private_key = if let Some(private_key) = self.private_key {
+            private_key
+        } else {
+            let ciphertext = self.ciphertext.as_ref().unwrap();
+            Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
+        };
+        let imports = api_client.get_program_imports_from_source(&program)?;
+        let inputs = self.inputs.iter().map(|input| input.to_string()).collect::<Vec<_>>();
+        InputValidator::new(&program)
+            .with_imports(imports.values())
+            .with_caller(Address::try_from(&private_key)?)
+            .validate(self.function, &inputs)?;
+
         if self.estimate_fee {
             let (total, (storage, finalize)) =
                 program_manager.estimate_execution_fee::<Aleo>(&program, self.function, self.inputs.iter())?;
@@ -143,22 +173,31 @@ impl Execute {
         // Find a fee record to pay the fee if necessary
         let fee_record = if self.record.is_none() {
             println!("Searching for a record to spend the execution fee from, this may take a while..");
-            let private_key = if let Some(private_key) = self.private_key {
-                private_key
-            } else {
-                let ciphertext = self.ciphertext.as_ref().unwrap();
-                Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
-            };
-            let record_finder = RecordFinder::new(api_client);
+            let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?;
             record_finder.find_one_record(&private_key, fee_microcredits)?
         } else {
             self.record.unwrap()
         };
 
+        // Build the execution without broadcasting it if this is a dry run
+        if self.dry_run {
+            let transaction = program_manager.build_execution_with_imports(
+                &program,
+                imports.values(),
+                self.function,
+                self.inputs.iter(),
+                fee_microcredits,
+                fee_record,
+                self.password.as_deref(),
+            )?;
+            return output_transaction(&transaction, self.output.as_ref());
+        }
+
         // Execute the program function
         println!("Executing '{}:{}'", program_string.bright_blue(), function_string.bright_blue());
-        let result = program_manager.execute_program(
-            self.program_id,
+        let result = program_manager.execute_program_with_imports(
+            &program,
+            imports.values(),
             self.function,
             self.inputs.iter(),
             fee_microcredits,
@@ -166,6 +205,12 @@ impl Execute {
             self.password.as_deref(),
         );
 
+        // Wait for the execution to be confirmed on chain if requested
+        let result = match result {
+            Ok(response) if self.wait => wait_for_confirmation(&program_manager, &response).map(|_| response),
+            result => result,
+        };
+
         // Inform the user of the result of the program execution
         if result.is_err() {
             println!(
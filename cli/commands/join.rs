@@ -0,0 +1,168 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    helpers::{api_client, output_transaction, record_finder, wait_for_confirmation, CoinSelectionArg},
+    CurrentNetwork,
+};
+use aleo_rust::{Ciphertext, Credits, Encryptor, Plaintext, PrivateKey, ProgramManager, Record};
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use colored::*;
+use std::time::Duration;
+
+/// Number of seconds the records being joined are kept from being chosen to pay the fee
+const JOINED_RECORDS_RESERVATION_SECS: u64 = 60;
+
+/// Join two credits records into a single record holding the credits of both
+#[derive(Debug, Parser)]
+pub struct Join {
+    /// First record to join
+    #[clap(long)]
+    first_record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+    /// Second record to join
+    #[clap(long)]
+    second_record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+    /// Transaction fee in credits
+    #[clap(short, long)]
+    fee: f64,
+    /// Record to spend the fee from
+    #[clap(long)]
+    fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+    /// Private key used to generate the join
+    #[clap(short='k', long, conflicts_with_all = &["ciphertext", "password"])]
+    private_key: Option<PrivateKey<CurrentNetwork>>,
+    /// Aleo Network peer to broadcast the transaction to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
+    /// Private key ciphertext used to generate the join (requires password to decrypt)
+    #[clap(short, long, conflicts_with = "private_key", requires = "password")]
+    ciphertext: Option<Ciphertext<CurrentNetwork>>,
+    /// Password to decrypt the private key
+    #[clap(short = 'p', long, conflicts_with = "private_key", requires = "ciphertext")]
+    password: Option<String>,
+    /// Strategy for choosing the record that pays the fee
+    #[clap(long, value_enum, default_value_t = CoinSelectionArg::LargestFirst)]
+    coin_selection: CoinSelectionArg,
+    /// File to cache the account's records in, so later record searches only scan new blocks
+    #[clap(long)]
+    record_store: Option<std::path::PathBuf>,
+    /// Wait for the join to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+    /// Build the transaction without broadcasting it, printing its ID and fee
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+    /// File to write the transaction built in a dry run to as JSON
+    #[clap(long, requires = "dry_run")]
+    output: Option<std::path::PathBuf>,
+}
+
+impl Join {
+    pub fn parse(self) -> Result<String> {
+        // Check for config errors
+        ensure!(self.fee > 0f64, "fee must be greater than zero to join records");
+        ensure!(self.first_record != self.second_record, "The records to join must be different records");
+        ensure!(
+            !(self.private_key.is_none() && self.ciphertext.is_none()),
+            "Private key or private key ciphertext required"
+        );
+
+        // Convert the fee to microcredits
+        let fee_microcredits = (self.fee * 1000000.0) as u64;
+
+        println!(
+            "{}",
+            format!(
+                "Attempting to join records of {} and {} microcredits with a fee of {} credits...",
+                self.first_record.microcredits()?,
+                self.second_record.microcredits()?,
+                self.fee
+            )
+            .bright_blue()
+        );
+
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
+
+        // Create the program manager
+        let program_manager = ProgramManager::<CurrentNetwork>::new(
+            self.private_key,
+            self.ciphertext.clone(),
+            Some(api_client.clone()),
+            None,
+        )?;
+
+        // Find a fee record other than the records being joined if one is not provided
+        let fee_record = if let Some(fee_record) = self.fee_record {
+            ensure!(
+                fee_record.microcredits()? > fee_microcredits,
+                "Fee record must have more microcredits than the fee"
+            );
+            fee_record
+        } else {
+            let private_key = if let Some(private_key) = self.private_key {
+                private_key
+            } else {
+                let ciphertext = self.ciphertext.as_ref().unwrap();
+                Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
+            };
+            let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?
+                .with_reservations(Duration::from_secs(JOINED_RECORDS_RESERVATION_SECS));
+            if let Some(reservations) = record_finder.reservations() {
+                reservations.reserve(&[self.first_record.clone(), self.second_record.clone()])?;
+            }
+            record_finder.find_one_record(&private_key, fee_microcredits)?
+        };
+
+        // Build the join without broadcasting it if this is a dry run
+        if self.dry_run {
+            let transaction = program_manager.build_join(
+                self.first_record,
+                self.second_record,
+                fee_microcredits,
+                fee_record,
+                self.password.as_deref(),
+            )?;
+            return output_transaction(&transaction, self.output.as_ref());
+        }
+
+        // Execute the join
+        let join = program_manager.join_records(
+            self.first_record,
+            self.second_record,
+            fee_microcredits,
+            fee_record,
+            self.password.as_deref(),
+        );
+
+        // Wait for the join to be confirmed on chain if requested
+        let join = match join {
+            Ok(response) if self.wait => wait_for_confirmation(&program_manager, &response).map(|_| response),
+            join => join,
+        };
+
+        // Inform the user of the result of the join
+        if join.is_err() {
+            println!("{}", "Join failed with error:".to_string().red().bold());
+        } else {
+            println!("{}", "Join successful!".to_string().bright_green().bold());
+            println!("Transaction ID:");
+        }
+        join
+    }
+}
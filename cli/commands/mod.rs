@@ -17,6 +17,9 @@
 mod account;
 pub use account::*;
 
+mod broadcast;
+pub use broadcast::*;
+
 mod build;
 pub use build::*;
 
@@ -29,15 +32,24 @@ pub use deploy::*;
 mod execute;
 pub use execute::*;
 
+mod join;
+pub use join::*;
+
 mod new;
 pub use new::*;
 
 // mod node;
 // pub use node::*;
 
+mod program;
+pub use program::*;
+
 mod run;
 pub use run::*;
 
+mod split;
+pub use split::*;
+
 mod transfer;
 pub use transfer::*;
 
@@ -62,6 +74,8 @@ pub struct CLI {
 pub enum Command {
     #[clap(subcommand)]
     Account(Account),
+    #[clap(name = "broadcast")]
+    Broadcast(Broadcast),
     #[clap(name = "build")]
     Build(Build),
     #[clap(name = "clean")]
@@ -70,12 +84,18 @@ pub enum Command {
     Deploy(Deploy),
     #[clap(name = "execute")]
     Execute(Execute),
+    #[clap(name = "join")]
+    Join(Box<Join>),
     #[clap(name = "new")]
     New(New),
+    #[clap(subcommand)]
+    Program(ProgramCommand),
     #[clap(name = "run")]
     Run(Run),
+    #[clap(name = "split")]
+    Split(Split),
     #[clap(name = "transfer")]
-    Transfer(Transfer),
+    Transfer(Box<Transfer>),
     #[clap(name = "update")]
     Update(Update),
 }
@@ -85,13 +105,17 @@ impl Command {
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Account(command) => command.parse(),
+            Self::Broadcast(command) => command.parse(),
             Self::Build(command) => command.parse(),
             Self::Clean(command) => command.parse(),
             Self::Deploy(command) => command.parse(),
             Self::Execute(command) => command.parse(),
+            Self::Join(command) => command.parse(),
             Self::New(command) => command.parse(),
             // Self::Node(command) => command.parse(),
+            Self::Program(command) => command.parse(),
             Self::Run(command) => command.parse(),
+            Self::Split(command) => command.parse(),
             Self::Transfer(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{helpers::api_client, CurrentNetwork};
+use aleo_rust::ProgramInterface;
+use snarkvm::prelude::{Program, ProgramID};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::{path::PathBuf, str::FromStr};
+
+/// Commands to describe Aleo programs
+#[derive(Debug, Parser)]
+pub enum ProgramCommand {
+    /// Print the interface of a program as JSON: its functions, finalize signatures, closures,
+    /// mappings, structs, records and imports
+    Inspect {
+        /// The program identifier of a program deployed on the network
+        #[clap(required_unless_present = "path", conflicts_with = "path")]
+        program_id: Option<ProgramID<CurrentNetwork>>,
+        /// Path of a local program file, or of a package directory containing a main.aleo file
+        #[clap(long)]
+        path: Option<PathBuf>,
+        /// Aleo Network peer to fetch the program from. Several peers can be separated by commas to fail over across them
+        #[clap(short, long, value_delimiter = ',', conflicts_with = "path")]
+        endpoint: Vec<String>,
+        /// File to write the interface to instead of printing it
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+impl ProgramCommand {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Inspect { program_id, path, endpoint, output } => {
+                let program = match (program_id, path) {
+                    (_, Some(path)) => {
+                        let file = if path.is_dir() { path.join("main.aleo") } else { path };
+                        let source = std::fs::read_to_string(&file)
+                            .map_err(|e| anyhow!("Could not read the program at {}: {e}", file.display()))?;
+                        Program::<CurrentNetwork>::from_str(&source)?
+                    }
+                    (Some(program_id), None) => api_client(&endpoint)?.get_program(program_id)?,
+                    (None, None) => unreachable!("clap requires a program ID or a path"),
+                };
+
+                let json = ProgramInterface::new(&program).to_json()?;
+                match output {
+                    Some(output) => {
+                        std::fs::write(&output, json)?;
+                        Ok(format!(
+                            "Interface of {} written to {}",
+                            program.id().to_string().bright_blue(),
+                            output.display().to_string().bright_blue()
+                        ))
+                    }
+                    None => Ok(json),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_PROGRAM: &str = "program hello.aleo;
+
+function hello:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+";
+
+    #[test]
+    fn test_program_inspect() {
+        let directory = std::env::temp_dir().join("aleo_program_inspect");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("main.aleo"), HELLO_PROGRAM).unwrap();
+
+        // Assert a local package is described
+        let inspect = ProgramCommand::try_parse_from(["aleo", "inspect", "--path", directory.to_str().unwrap()]);
+        let interface = ProgramInterface::from_json(&inspect.unwrap().parse().unwrap()).unwrap();
+        assert_eq!(interface.program, "hello.aleo");
+        assert_eq!(interface.function("hello").unwrap().inputs.len(), 2);
+
+        // Assert the interface is written to a file if requested
+        let output = directory.join("interface.json");
+        let inspect = ProgramCommand::try_parse_from([
+            "aleo",
+            "inspect",
+            "--path",
+            directory.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ]);
+        inspect.unwrap().parse().unwrap();
+        let written = ProgramInterface::from_json(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(written, interface);
+
+        // Assert a program ID or a path is required, but not both
+        let inspect_missing = ProgramCommand::try_parse_from(["aleo", "inspect"]);
+        assert_eq!(inspect_missing.unwrap_err().kind(), clap::error::ErrorKind::MissingRequiredArgument);
+        let inspect_conflicting =
+            ProgramCommand::try_parse_from(["aleo", "inspect", "hello.aleo", "--path", directory.to_str().unwrap()]);
+        assert_eq!(inspect_conflicting.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    helpers::{api_client, output_transaction, record_finder, wait_for_confirmation, CoinSelectionArg},
+    CurrentNetwork,
+};
+use aleo_rust::{Ciphertext, Credits, Encryptor, Plaintext, PrivateKey, ProgramManager, Record};
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use colored::*;
+
+/// Split a credits record into two records, one holding the specified amount
+#[derive(Debug, Parser)]
+pub struct Split {
+    /// Number of credits to split off the record
+    #[clap(short, long)]
+    amount: f64,
+    /// Record to split, a record holding more than the amount is searched for if not specified
+    #[clap(short, long)]
+    record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+    /// Private key used to generate the split
+    #[clap(short='k', long, conflicts_with_all = &["ciphertext", "password"])]
+    private_key: Option<PrivateKey<CurrentNetwork>>,
+    /// Aleo Network peer to broadcast the transaction to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
+    /// Private key ciphertext used to generate the split (requires password to decrypt)
+    #[clap(short, long, conflicts_with = "private_key", requires = "password")]
+    ciphertext: Option<Ciphertext<CurrentNetwork>>,
+    /// Password to decrypt the private key
+    #[clap(short = 'p', long, conflicts_with = "private_key", requires = "ciphertext")]
+    password: Option<String>,
+    /// Strategy for choosing the record to split
+    #[clap(long, value_enum, default_value_t = CoinSelectionArg::LargestFirst)]
+    coin_selection: CoinSelectionArg,
+    /// File to cache the account's records in, so later record searches only scan new blocks
+    #[clap(long)]
+    record_store: Option<std::path::PathBuf>,
+    /// Wait for the split to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+    /// Build the transaction without broadcasting it, printing its ID and fee
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+    /// File to write the transaction built in a dry run to as JSON
+    #[clap(long, requires = "dry_run")]
+    output: Option<std::path::PathBuf>,
+}
+
+impl Split {
+    pub fn parse(self) -> Result<String> {
+        // Check for config errors
+        ensure!(self.amount > 0f64, "Split amount must be greater than 0 credits");
+        ensure!(
+            !(self.private_key.is_none() && self.ciphertext.is_none()),
+            "Private key or private key ciphertext required"
+        );
+
+        // Convert the split amount to microcredits
+        let amount_microcredits = (self.amount * 1000000.0) as u64;
+
+        println!("{}", format!("Attempting to split {} credits off a record...", self.amount).bright_blue());
+
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
+
+        // Create the program manager
+        let program_manager = ProgramManager::<CurrentNetwork>::new(
+            self.private_key,
+            self.ciphertext.clone(),
+            Some(api_client.clone()),
+            None,
+        )?;
+
+        // Find a record holding more than the amount if one is not provided
+        let record = if let Some(record) = self.record {
+            ensure!(
+                record.microcredits()? > amount_microcredits,
+                "Record to split must have more microcredits than the split amount specified"
+            );
+            record
+        } else {
+            let private_key = if let Some(private_key) = self.private_key {
+                private_key
+            } else {
+                let ciphertext = self.ciphertext.as_ref().unwrap();
+                Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
+            };
+            let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?;
+            record_finder.find_one_record(&private_key, amount_microcredits.saturating_add(1))?
+        };
+
+        // Build the split without broadcasting it if this is a dry run
+        if self.dry_run {
+            let transaction = program_manager.build_split(amount_microcredits, record, self.password.as_deref())?;
+            return output_transaction(&transaction, self.output.as_ref());
+        }
+
+        // Execute the split
+        let split = program_manager.split_record(amount_microcredits, record, self.password.as_deref());
+
+        // Wait for the split to be confirmed on chain if requested
+        let split = match split {
+            Ok(response) if self.wait => wait_for_confirmation(&program_manager, &response).map(|_| response),
+            split => split,
+        };
+
+        // Inform the user of the result of the split
+        if split.is_err() {
+            println!("{}", "Split failed with error:".to_string().red().bold());
+        } else {
+            println!("{}", "Split successful!".to_string().bright_green().bold());
+            println!("Transaction ID:");
+        }
+        split
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::TestRng;
+
+    #[test]
+    fn test_split_config_errors() {
+        let private_key = PrivateKey::<CurrentNetwork>::new(&mut TestRng::default()).unwrap();
+
+        // Assert that the split fails without a private key or private key ciphertext
+        let split_missing_key_material = Split::try_parse_from(["aleo", "-a", "1.0"]);
+        assert!(split_missing_key_material.unwrap().parse().is_err());
+
+        // Assert that the split fails if a ciphertext is provided without a password
+        let ciphertext = Encryptor::encrypt_private_key_with_secret(&private_key, "password").unwrap();
+        let split_no_password = Split::try_parse_from(["aleo", "-a", "1.0", "--ciphertext", &ciphertext.to_string()]);
+        assert_eq!(split_no_password.unwrap_err().kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        // Assert that the split fails if a zero amount is specified
+        let split_zero_amount =
+            Split::try_parse_from(["aleo", "-a", "0.0", "-k", &private_key.to_string(), "-e", "http://localhost:3030"]);
+        assert!(split_zero_amount.unwrap().parse().is_err());
+
+        // Assert that an output file requires a dry run and that a dry run cannot be waited on
+        let split_output_without_dry_run =
+            Split::try_parse_from(["aleo", "-a", "1.0", "-k", &private_key.to_string(), "--output", "tx.json"]);
+        assert_eq!(split_output_without_dry_run.unwrap_err().kind(), clap::error::ErrorKind::MissingRequiredArgument);
+        let split_dry_run_wait =
+            Split::try_parse_from(["aleo", "-a", "1.0", "-k", &private_key.to_string(), "--dry-run", "--wait"]);
+        assert_eq!(split_dry_run_wait.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+}
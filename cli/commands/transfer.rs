@@ -14,10 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{helpers::TransferTypeArg, CurrentNetwork};
+use crate::{
+    helpers::{
+        api_client,
+        output_transaction,
+        record_finder,
+        wait_for_confirmation,
+        CoinSelectionArg,
+        TransferTypeArg,
+    },
+    CurrentNetwork,
+};
 use aleo_rust::{
     Address,
-    AleoAPIClient,
     Ciphertext,
     Credits,
     Encryptor,
@@ -25,11 +34,10 @@ use aleo_rust::{
     PrivateKey,
     ProgramManager,
     Record,
-    RecordFinder,
     TransferType,
 };
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{ensure, Result};
 use clap::Parser;
 use colored::*;
 
@@ -57,15 +65,30 @@ pub struct Transfer {
     /// Record to spend the fee from
     #[clap(long)]
     fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
-    /// Aleo Network peer to broadcast the transaction to
-    #[clap(short, long)]
-    endpoint: Option<String>,
+    /// Aleo Network peer to broadcast the transaction to. Several peers can be separated by commas to fail over across them
+    #[clap(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
     /// Private key ciphertext used to generate the transfer (requires password to decrypt)
     #[clap(short, long, conflicts_with = "private_key", requires = "password")]
     ciphertext: Option<Ciphertext<CurrentNetwork>>,
     /// Password to decrypt the private key
     #[clap(short = 'p', long, conflicts_with = "private_key", requires = "ciphertext")]
     password: Option<String>,
+    /// Strategy for choosing the records that pay for the transaction
+    #[clap(long, value_enum, default_value_t = CoinSelectionArg::LargestFirst)]
+    coin_selection: CoinSelectionArg,
+    /// File to cache the account's records in, so later record searches only scan new blocks
+    #[clap(long)]
+    record_store: Option<std::path::PathBuf>,
+    /// Wait for the transfer to be confirmed on chain before exiting
+    #[clap(long)]
+    wait: bool,
+    /// Build the transaction without broadcasting it, printing its ID and fee
+    #[clap(long, conflicts_with = "wait")]
+    dry_run: bool,
+    /// File to write the transaction built in a dry run to as JSON
+    #[clap(long, requires = "dry_run")]
+    output: Option<std::path::PathBuf>,
 }
 
 impl Transfer {
@@ -95,17 +118,8 @@ impl Transfer {
             .bright_blue()
         );
 
-        // Setup the API client to use configured peer or default to https://vm.aleo.org/api/testnet3
-        let api_client = self
-            .endpoint
-            .map_or_else(
-                || {
-                    println!("Using default peer: {}", "https://vm.aleo.org/api/testnet3".bright_blue().bold());
-                    Ok(AleoAPIClient::<CurrentNetwork>::testnet3())
-                },
-                |peer| AleoAPIClient::<CurrentNetwork>::new(&peer, "testnet3"),
-            )
-            .map_err(|e| anyhow!("{:?}", e))?;
+        // Setup the API client to use the configured peers or default to https://vm.aleo.org/api/testnet3
+        let api_client = api_client(&self.endpoint)?;
 
         // Create the program manager
         let program_manager = ProgramManager::<CurrentNetwork>::new(
@@ -122,7 +136,7 @@ impl Transfer {
             let ciphertext = self.ciphertext.as_ref().unwrap();
             Encryptor::decrypt_private_key_with_secret(ciphertext, self.password.as_ref().unwrap())?
         };
-        let record_finder = RecordFinder::new(api_client);
+        let record_finder = record_finder(api_client, self.record_store.as_ref(), self.coin_selection.clone())?;
 
         let (amount_record, fee_record) = if self.fee_record.is_none() {
             match transfer_type {
@@ -185,6 +199,20 @@ impl Transfer {
             }
         };
 
+        // Build the transfer without broadcasting it if this is a dry run
+        if self.dry_run {
+            let transaction = program_manager.build_transfer(
+                amount_microcredits,
+                fee_microcredits,
+                self.recipient,
+                transfer_type,
+                self.password.as_deref(),
+                amount_record,
+                fee_record,
+            )?;
+            return output_transaction(&transaction, self.output.as_ref());
+        }
+
         // Execute the transfer
         let transfer = program_manager.transfer(
             amount_microcredits,
@@ -196,6 +224,12 @@ impl Transfer {
             fee_record,
         );
 
+        // Wait for the transfer to be confirmed on chain if requested
+        let transfer = match transfer {
+            Ok(response) if self.wait => wait_for_confirmation(&program_manager, &response).map(|_| response),
+            transfer => transfer,
+        };
+
         // Inform the user of the result of the transfer
         if transfer.is_err() {
             println!("{}", "Transfer failed with error:".to_string().red().bold());
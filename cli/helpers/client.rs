@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{helpers::CoinSelectionArg, CurrentNetwork};
+use aleo_rust::{
+    AleoAPIClient,
+    CoinSelection,
+    RecordFinder,
+    RecordStore,
+    DEFAULT_CONNECT_TIMEOUT,
+    DEFAULT_READ_TIMEOUT,
+};
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Peer used when no endpoint is specified
+pub const DEFAULT_PEER: &str = "https://vm.aleo.org/api";
+
+/// Create an API client for the specified endpoints, or the default peer if none are specified.
+/// Requests fail over across the endpoints in the order given and failed queries are retried
+/// with the default retry policy.
+pub fn api_client(endpoints: &[String]) -> Result<AleoAPIClient<CurrentNetwork>> {
+    let builder = AleoAPIClient::<CurrentNetwork>::builder()
+        .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+        .read_timeout(DEFAULT_READ_TIMEOUT);
+    if endpoints.is_empty() {
+        println!("Using default peer: {}", format!("{DEFAULT_PEER}/testnet3").bright_blue().bold());
+        builder.peer(DEFAULT_PEER).build()
+    } else {
+        builder.peers(endpoints).build()
+    }
+}
+
+/// Create a record finder searching the network through the API client and choosing records with
+/// the given coin selection strategy. If a record store path is specified, records are cached in it
+/// so later searches only scan new blocks.
+pub fn record_finder(
+    api_client: AleoAPIClient<CurrentNetwork>,
+    record_store: Option<&PathBuf>,
+    coin_selection: CoinSelectionArg,
+) -> Result<RecordFinder<CurrentNetwork>> {
+    let record_finder = RecordFinder::new(api_client).with_coin_selector(CoinSelection::from(coin_selection));
+    match record_store {
+        Some(path) => Ok(record_finder.with_record_store(RecordStore::open(path)?)),
+        None => Ok(record_finder),
+    }
+}
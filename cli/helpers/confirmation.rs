@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::CurrentNetwork;
+use aleo_rust::{NetworkClient, ProgramManager, TransactionStatus};
+use snarkvm::prelude::Network;
+
+use anyhow::{anyhow, bail, Result};
+use std::{str::FromStr, time::Duration};
+
+/// Number of seconds to wait for a transaction to be included in a block
+pub const WAIT_TIMEOUT_SECS: u64 = 300;
+
+/// Number of seconds between checks for the transaction while waiting
+pub const WAIT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Wait for a broadcast transaction to be included in a block, failing if it is rejected or not
+/// found on chain before the timeout elapses
+pub fn wait_for_confirmation<C: NetworkClient<CurrentNetwork>>(
+    program_manager: &ProgramManager<CurrentNetwork, C>,
+    broadcast_response: &str,
+) -> Result<()> {
+    let transaction_id = broadcast_transaction_id(broadcast_response)?;
+    let timeout = Duration::from_secs(WAIT_TIMEOUT_SECS);
+    let poll_interval = Duration::from_secs(WAIT_POLL_INTERVAL_SECS);
+    ensure_confirmed(transaction_id, program_manager.wait_for_transaction(transaction_id, timeout, poll_interval)?)
+}
+
+/// Wait for a transaction broadcast directly through a network client to be included in a block,
+/// for when no program manager is at hand
+pub fn wait_for_broadcast<C: NetworkClient<CurrentNetwork>>(api_client: &C, broadcast_response: &str) -> Result<()> {
+    let transaction_id = broadcast_transaction_id(broadcast_response)?;
+    let timeout = Duration::from_secs(WAIT_TIMEOUT_SECS);
+    let poll_interval = Duration::from_secs(WAIT_POLL_INTERVAL_SECS);
+    println!("⏳ Waiting for transaction {transaction_id} to be included in a block..");
+    ensure_confirmed(transaction_id, api_client.wait_for_transaction(transaction_id, timeout, poll_interval)?)
+}
+
+/// Read the transaction ID the node responds to a broadcast with as a JSON string
+fn broadcast_transaction_id(broadcast_response: &str) -> Result<<CurrentNetwork as Network>::TransactionID> {
+    let transaction_id = broadcast_response.trim().trim_matches('"');
+    <CurrentNetwork as Network>::TransactionID::from_str(transaction_id)
+        .map_err(|_| anyhow!("Could not read a transaction ID from the broadcast response {broadcast_response}"))
+}
+
+/// Fail unless the transaction was confirmed
+fn ensure_confirmed(
+    transaction_id: <CurrentNetwork as Network>::TransactionID,
+    status: TransactionStatus<CurrentNetwork>,
+) -> Result<()> {
+    match status {
+        TransactionStatus::Confirmed { .. } => Ok(()),
+        TransactionStatus::Rejected { block_height, .. } => {
+            bail!("Transaction {transaction_id} was rejected in block {block_height}")
+        }
+        TransactionStatus::TimedOut => {
+            bail!("Transaction {transaction_id} was not confirmed within {WAIT_TIMEOUT_SECS} seconds")
+        }
+    }
+}
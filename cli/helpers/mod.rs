@@ -17,6 +17,15 @@
 // pub mod ledger;
 // pub use ledger::*;
 
+pub mod client;
+pub use client::*;
+
+pub mod confirmation;
+pub use confirmation::*;
+
+pub mod output;
+pub use output::*;
+
 pub mod serialize;
 pub use serialize::*;
 
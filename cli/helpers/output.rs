@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::CurrentNetwork;
+use aleo_rust::BuiltTransaction;
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Describe a transaction built in a dry run and write it to the output file as JSON if one is
+/// specified, so it can be reviewed or broadcast later. Returns the ID of the transaction.
+pub fn output_transaction(transaction: &BuiltTransaction<CurrentNetwork>, output: Option<&PathBuf>) -> Result<String> {
+    let fee = transaction.fee();
+    let credits = |microcredits: u64| format!("{}", microcredits as f64 / 1_000_000.0).bright_blue();
+    println!("{}", "Dry run, the transaction was built but not broadcast".bright_green().bold());
+    println!("Storage cost: {} credits", credits(fee.storage_cost));
+    println!("Namespace cost: {} credits", credits(fee.namespace_cost));
+    println!("Finalize cost: {} credits", credits(fee.finalize_cost));
+    println!("Priority fee: {} credits", credits(fee.priority_fee));
+    println!("Total fee: {} credits", credits(fee.total_fee));
+
+    if let Some(output) = output {
+        std::fs::write(output, serde_json::to_string_pretty(transaction.transaction())?)?;
+        println!("Transaction written to {}", output.display().to_string().bright_blue());
+    }
+    println!("Transaction ID:");
+    Ok(transaction.id().to_string())
+}
@@ -20,7 +20,7 @@ use snarkvm::{
     prelude::{Address, PrivateKey, ViewKey},
 };
 
-use aleo_rust::TransferType;
+use aleo_rust::{CoinSelection, TransferType};
 use serde::{Deserialize, Serialize};
 
 /// Serialization model for writing Aleo key material to disk
@@ -59,3 +59,27 @@ impl From<TransferTypeArg> for TransferType {
         }
     }
 }
+
+/// Strategy for choosing the records that pay for a transaction. Only strategies paying for each
+/// amount with a single record are offered, as the commands spend one record per amount.
+#[derive(Debug, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoinSelectionArg {
+    /// Pay with the largest records
+    LargestFirst,
+    /// Pay with the smallest record that covers each amount
+    SmallestSufficient,
+    /// Pay with records that leave no dust sized change
+    AvoidDust,
+}
+
+impl From<CoinSelectionArg> for CoinSelection {
+    fn from(arg: CoinSelectionArg) -> Self {
+        match arg {
+            CoinSelectionArg::LargestFirst => CoinSelection::LargestFirst,
+            CoinSelectionArg::SmallestSufficient => CoinSelection::SmallestSufficient,
+            CoinSelectionArg::AvoidDust => {
+                CoinSelection::AvoidDust { dust_threshold: CoinSelection::DEFAULT_DUST_THRESHOLD }
+            }
+        }
+    }
+}
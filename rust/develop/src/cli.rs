@@ -58,9 +58,26 @@ pub enum Command {
         /// and execute transactions to. The peer must be running the testnet3 api
         /// <https://developer.aleo.org/testnet/getting_started/overview/> in order
         /// for the development server to successfully send transactions to the Aleo
-        /// Network.
-        #[clap(short, long)]
-        peer: Option<String>,
+        /// Network. Several peers can be separated by commas, in which case the
+        /// server fails over to the next peer when one is unreachable.
+        #[clap(short, long, value_delimiter = ',')]
+        peer: Vec<String>,
+        /// File to cache the records of the accounts used with the server in. Records
+        /// are always cached in memory while the server runs, if a file is specified
+        /// they are also kept across restarts.
+        #[clap(long)]
+        record_store: Option<PathBuf>,
+        /// Directory to cache the proving and verifying keys of the program functions
+        /// executed with the server in, so each function only synthesizes its keys
+        /// once across requests and restarts.
+        #[clap(long)]
+        key_cache: Option<PathBuf>,
+        /// Number of seconds the records chosen to pay for a request are reserved for
+        /// while its transaction settles, so concurrent requests never spend the
+        /// same record. Records are released early once the transaction is confirmed
+        /// or rejected, or if it could not be created.
+        #[clap(long, default_value_t = 300)]
+        reservation_ttl: u64,
         /// Start the server with debug logging enabled [default: false]
         #[clap(short, long)]
         debug: bool,
@@ -68,10 +85,19 @@ pub enum Command {
 }
 
 impl Command {
-    pub fn parse(self) -> Result<Rest<Testnet3>> {
+    pub fn parse(self) -> Result<Rest<Testnet3, AleoV0>> {
         match self {
-            Command::Start { server_address, key_ciphertext: key, peer, debug } => {
-                Rest::initialize(server_address, key, peer, debug)
+            Command::Start {
+                server_address,
+                key_ciphertext: key,
+                peer,
+                record_store,
+                key_cache,
+                reservation_ttl,
+                debug,
+            } => {
+                let reservation_ttl = Duration::from_secs(reservation_ttl);
+                Rest::initialize(server_address, key, peer, record_store, key_cache, reservation_ttl, debug)
             }
         }
     }
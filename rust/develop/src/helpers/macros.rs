@@ -14,9 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-/// Spawn a blocking tokio task and await its result (used for proof computation)
+/// Spawn a blocking tokio task and await its result (used for proof computation). Prefix the task
+/// with `result:` to also receive a failure to join the task as an error instead of returning early,
+/// for instance to release the records reserved for a transaction.
 #[macro_export]
 macro_rules! spawn_blocking {
+    (result: $($tt:tt)*) => {
+        (tokio::task::spawn_blocking(move || $($tt)*.or_reject())).await.or_reject().and_then(|result| result)
+    };
     ($($tt:tt)*) => {
         (tokio::task::spawn_blocking(move || $($tt)*.or_reject())).await.or_reject()?
     };
@@ -33,10 +33,14 @@
 //!
 //! The Aleo Development Server provides REST endpoints that allow developers to send the necessary
 //! data needed to create program deployments and executions to the Aleo network. Currently there
-//! are three endpoints:
+//! are seven endpoints:
 //! - `/deploy` - Create a program deployment
 //! - `/execute` - Create a program execution
 //! - `/transfer` - Create a transfer of Aleo credits
+//! - `/split` - Split a credits record into two records
+//! - `/join` - Join two credits records into one
+//! - `/broadcast` - Verify and broadcast a previously built transaction
+//! - `/prove` - Prove an execution authorized by another machine
 //!
 //! ## Installation & Configuration
 //! The development server can be installed with:
@@ -78,7 +82,7 @@
 //! * `program_id` The program ID of the program to be executed (e.g. hello.aleo)
 //! * `program_function` The function to execute within the program (e.g. hello)
 //! * `fee` Optional fee to be paid for the transfer, specify 0 for no fee
-//! * `inputs` Array of inputs to be passed to the program
+//! * `inputs` Array of inputs to be passed to the program. Inputs are checked against the types in the function signature before the execution is built, and the request fails listing every input that does not match
 //! * `private_key` Optional private key of the user who is executing the program
 //! * `password`: If the development server is started with an encrypted private key, the password will decrypt the private key
 //! * `fee_record`: Optional record in text format to be used for the fee. If not provided, the server will search the network for a suitable record to pay the fee
@@ -94,6 +98,34 @@
 //! * `fee_record` Optional record in text format to be used for the fee. If not provided, the server will search the network for a suitable record to pay the fee
 //! @returns {string | Error} The transaction ID of the execution transaction if successful
 //!
+//! `\split`
+//! * `amount` The amount of microcredits to split off the record
+//! * `record` Optional record in text format to split. If not provided, the server will search the network for a record holding more than the amount
+//! * `private_key` Optional private key of the user who owns the record
+//! * `password`: If the development server is started with an encrypted private key, the password will decrypt the private key
+//! * `coin_selection` Optional strategy for choosing the record to split when it is not provided
+//! * `returns`: The transaction ID of the split transaction if successful
+//!
+//! `\join`
+//! * `first_record` The first record to join in text format
+//! * `second_record` The second record to join in text format
+//! * `fee` Required fee to be paid for the join
+//! * `fee_record` Optional record in text format to be used for the fee. If not provided, the server will search the network for a suitable record other than the records being joined
+//! * `private_key` Optional private key of the user who owns the records
+//! * `password`: If the development server is started with an encrypted private key, the password will decrypt the private key
+//! * `coin_selection` Optional strategy for choosing the record that pays the fee when it is not provided
+//! * `returns`: The transaction ID of the join transaction if successful
+//!
+//! `\broadcast`
+//! * `transaction` A transaction in JSON format, such as one written by the `--dry-run` option of the Aleo CLI
+//! * `peer_url` Optional peer to verify the transaction against and broadcast it to
+//! * `returns`: The transaction ID of the broadcast transaction if it is valid
+//!
+//! `\prove`
+//! * `authorization` An execution authorization in JSON format created with `ProgramManager::authorize_execution`
+//! * `peer_url` Optional peer to fetch the programs and the state of the spent records from
+//! * `returns`: The proven transaction in JSON format, which can be sent to `\broadcast`
+//!
 //! #### Curl Examples
 //! Example curl requests for the above endpoints:
 //! ```bash
@@ -126,6 +158,14 @@
 //!     "private_key": "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH"
 //! }' \
 //! http://0.0.0.0:4040/testnet3/transfer
+//!
+//! ## Split a record
+//! curl -X POST -H "Content-Type: application/json" \
+//! -d '{
+//!     "amount": 1000,
+//!     "private_key": "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH"
+//! }' \
+//! http://0.0.0.0:4040/testnet3/split
 //! ```
 //!
 //! This API of this server is currently under active development and is expected to change in the
@@ -149,7 +189,24 @@ use requests::*;
 mod routes;
 pub use routes::*;
 
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder, TransferType};
+use aleo_rust::{
+    Aleo,
+    AleoAPIClient,
+    AleoV0,
+    CoinSelection,
+    Encryptor,
+    ExecutionAuthorization,
+    InputValidator,
+    KeyCache,
+    ProgramManager,
+    RecordFinder,
+    RecordStore,
+    RetryPolicy,
+    Transaction,
+    TransferType,
+    DEFAULT_CONNECT_TIMEOUT,
+    DEFAULT_READ_TIMEOUT,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -163,40 +220,63 @@ use tracing_subscriber::fmt;
 use anyhow::{anyhow, Result};
 use colored::*;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{marker::PhantomData, net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 use warp::{reject, reply, Filter, Rejection, Reply};
 
-/// Server object for the Aleo Development Server
+/// Server object for the Aleo Development Server, proving and verifying with the circuit
+/// environment `A` of the network
 #[derive(Clone)]
-pub struct Rest<N: Network> {
+pub struct Rest<N: Network, A: Aleo<Network = N>> {
     api_client: AleoAPIClient<N>,
     /// Private key ciphertext for the account being used with the server
     private_key_ciphertext: Option<Ciphertext<N>>,
     /// Record finder for finding records
     record_finder: RecordFinder<N>,
+    /// Key cache for the keys of the program functions executed with the server
+    key_cache: Option<KeyCache>,
     /// Socket address for the server
     socket_address: SocketAddr,
     /// Debug mode flag
     debug: bool,
+    /// Circuit environment of the network
+    circuit: PhantomData<A>,
 }
 
-impl<N: Network> Rest<N> {
+impl<N: Network, A: Aleo<Network = N>> Rest<N, A> {
     /// Initializes a new instance of the server.
     pub fn initialize(
         socket_address: Option<SocketAddr>,
         private_key_ciphertext: Option<Ciphertext<N>>,
-        peer_url: Option<String>,
+        peer_urls: Vec<String>,
+        record_store_path: Option<PathBuf>,
+        key_cache_path: Option<PathBuf>,
+        reservation_ttl: Duration,
         debug: bool,
     ) -> Result<Self> {
         // If no socket address was specified, use the default of 0.0.0.0:4040
         let socket_address = socket_address.unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 4040)));
 
         // If no peer url was specified, use the default of https://vm.aleo.org/api
-        let peer = peer_url.unwrap_or("https://vm.aleo.org/api".to_string());
+        let peers = if peer_urls.is_empty() { vec!["https://vm.aleo.org/api".to_string()] } else { peer_urls };
+
+        // Initialize an API client configured for the specified network, failing over across the
+        // peers and retrying failed queries
+        let api_client = AleoAPIClient::builder()
+            .peers(&peers)
+            .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+            .read_timeout(DEFAULT_READ_TIMEOUT)
+            .retry_policy(RetryPolicy::default())
+            .build()?;
 
-        // Initialize an API client configured for the specified network.
-        let api_client = AleoAPIClient::new(&peer, "testnet3")?;
-        let record_finder = RecordFinder::new(api_client.clone());
+        // Cache records between requests so each search only scans new blocks, and reserve the
+        // records chosen for a request so concurrent requests do not spend the same record
+        let record_store = record_store_path.map_or_else(|| Ok(RecordStore::new()), RecordStore::open)?;
+        let record_finder =
+            RecordFinder::new(api_client.clone()).with_record_store(record_store).with_reservations(reservation_ttl);
+
+        // Keep the keys synthesized for program functions so each function only pays for key
+        // synthesis on its first execution
+        let key_cache = key_cache_path.map(KeyCache::open).transpose()?;
 
         let key_warning = if private_key_ciphertext.is_some() {
             format!("{}", "Using configured private key ciphertext for main development account, authentication will be required for all requests\n".bright_blue())
@@ -205,7 +285,15 @@ impl<N: Network> Rest<N> {
         };
 
         // Initialize the server.
-        let server = Self { api_client, private_key_ciphertext, record_finder, socket_address, debug };
+        let server = Self {
+            api_client,
+            private_key_ciphertext,
+            record_finder,
+            key_cache,
+            socket_address,
+            debug,
+            circuit: PhantomData,
+        };
 
         // Print an initialization message and return the server
         println!("{}", "\nStarting Aleo development server...".bright_blue());
@@ -214,14 +302,14 @@ impl<N: Network> Rest<N> {
             "Listening on ".bright_blue(),
             socket_address.to_string().bright_green().bold(),
             " with remote peer ".bright_blue(),
-            peer
+            peers.join(", ")
         );
         println!("{}", key_warning);
         Ok(server)
     }
 }
 
-impl<N: Network> Rest<N> {
+impl<N: Network, A: Aleo<Network = N>> Rest<N, A> {
     /// Initializes the development server
     pub async fn start(&mut self) {
         let cors = warp::cors().allow_any_origin().allow_methods(vec!["GET", "POST", "OPTIONS"]).allow_headers(vec![
@@ -26,6 +26,7 @@ pub(crate) struct DeployRequest<N: Network> {
     pub fee: u64,
     pub fee_record: Option<Record<N, Plaintext<N>>>,
     pub peer_url: Option<String>,
+    pub coin_selection: Option<String>,
 }
 
 /// Request to execute a program
@@ -40,6 +41,7 @@ pub(crate) struct ExecuteRequest<N: Network> {
     pub fee: u64,
     pub fee_record: Option<Record<N, Plaintext<N>>>,
     pub peer_url: Option<String>,
+    pub coin_selection: Option<String>,
 }
 
 /// Request to make transfer of Aleo credits
@@ -55,4 +57,43 @@ pub(crate) struct TransferRequest<N: Network> {
     pub fee_record: Option<Record<N, Plaintext<N>>>,
     pub amount_record: Option<Record<N, Plaintext<N>>>,
     pub peer_url: Option<String>,
+    pub coin_selection: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub(crate) struct SplitRequest<N: Network> {
+    pub amount: u64,
+    pub record: Option<Record<N, Plaintext<N>>>,
+    pub private_key: Option<PrivateKey<N>>,
+    pub password: Option<String>,
+    pub peer_url: Option<String>,
+    pub coin_selection: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub(crate) struct JoinRequest<N: Network> {
+    pub first_record: Record<N, Plaintext<N>>,
+    pub second_record: Record<N, Plaintext<N>>,
+    pub fee: u64,
+    pub fee_record: Option<Record<N, Plaintext<N>>>,
+    pub private_key: Option<PrivateKey<N>>,
+    pub password: Option<String>,
+    pub peer_url: Option<String>,
+    pub coin_selection: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub(crate) struct BroadcastRequest<N: Network> {
+    pub transaction: Transaction<N>,
+    pub peer_url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub(crate) struct ProveRequest<N: Network> {
+    pub authorization: ExecutionAuthorization<N>,
+    pub peer_url: Option<String>,
 }
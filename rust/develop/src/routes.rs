@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> Rest<N> {
+impl<N: Network, A: Aleo<Network = N>> Rest<N, A> {
     /// Initializes the routes for the development server REST API
     pub fn routes(&self) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
         // POST /deploy
@@ -37,6 +37,7 @@ impl<N: Network> Rest<N> {
             .and(with(self.record_finder.clone()))
             .and(with(self.private_key_ciphertext.clone()))
             .and(with(self.api_client.clone()))
+            .and(with(self.key_cache.clone()))
             .and_then(Self::execute_program);
 
         // POST /transfer
@@ -49,14 +50,50 @@ impl<N: Network> Rest<N> {
             .and(with(self.api_client.clone()))
             .and_then(Self::transfer);
 
+        // POST /split
+        let split = warp::post()
+            .and(warp::path!("testnet3" / "split"))
+            .and(warp::body::content_length_limit(16 * 1024 * 1024))
+            .and(warp::body::json())
+            .and(with(self.record_finder.clone()))
+            .and(with(self.private_key_ciphertext.clone()))
+            .and(with(self.api_client.clone()))
+            .and_then(Self::split);
+
+        // POST /join
+        let join = warp::post()
+            .and(warp::path!("testnet3" / "join"))
+            .and(warp::body::content_length_limit(16 * 1024 * 1024))
+            .and(warp::body::json())
+            .and(with(self.record_finder.clone()))
+            .and(with(self.private_key_ciphertext.clone()))
+            .and(with(self.api_client.clone()))
+            .and_then(Self::join);
+
+        // POST /broadcast
+        let broadcast = warp::post()
+            .and(warp::path!("testnet3" / "broadcast"))
+            .and(warp::body::content_length_limit(16 * 1024 * 1024))
+            .and(warp::body::json())
+            .and(with(self.api_client.clone()))
+            .and_then(Self::broadcast);
+
+        // POST /prove
+        let prove = warp::post()
+            .and(warp::path!("testnet3" / "prove"))
+            .and(warp::body::content_length_limit(16 * 1024 * 1024))
+            .and(warp::body::json())
+            .and(with(self.api_client.clone()))
+            .and_then(Self::prove);
+
         // GET /health
         let health = warp::get().and(warp::path!("health")).map(reply::reply);
 
-        deploy.or(execute).or(transfer).or(health)
+        deploy.or(execute).or(transfer).or(split).or(join).or(broadcast).or(prove).or(health)
     }
 }
 
-impl<N: Network> Rest<N> {
+impl<N: Network, A: Aleo<Network = N>> Rest<N, A> {
     // Get the private key if one is specified in the request or decrypt the local request
     fn get_private_key(
         private_key_ciphertext: Option<Ciphertext<N>>,
@@ -79,15 +116,86 @@ impl<N: Network> Rest<N> {
         }
     }
 
-    // If a separate peer url is provided in the request, use that instead of the one in the config
+    // Check the inputs of a program function against the signature of the function on chain,
+    // returning the program and its imports so they are not fetched again for the execution
+    fn validate_inputs(
+        api_client: &AleoAPIClient<N>,
+        program_id: ProgramID<N>,
+        function: Identifier<N>,
+        inputs: &[String],
+        private_key: &PrivateKey<N>,
+    ) -> Result<(Program<N>, Vec<Program<N>>)> {
+        let program = api_client.get_program(program_id)?;
+        let imports = api_client.get_program_imports_from_source(&program)?.into_values().collect::<Vec<_>>();
+        InputValidator::new(&program)
+            .with_imports(imports.iter())
+            .with_caller(Address::try_from(private_key)?)
+            .validate(function, inputs)?;
+        Ok((program, imports))
+    }
+
+    // If a separate peer url is provided in the request, use that instead of the ones in the config,
+    // with the same timeouts and retry policy
     fn get_api_client(api_client: AleoAPIClient<N>, peer_url: &Option<String>) -> Result<AleoAPIClient<N>, Rejection> {
         if let Some(peer_url) = peer_url {
-            AleoAPIClient::new(peer_url, "testnet3").or_reject()
+            AleoAPIClient::builder()
+                .peer(peer_url)
+                .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                .read_timeout(DEFAULT_READ_TIMEOUT)
+                .retry_policy(RetryPolicy::default())
+                .build()
+                .or_reject()
         } else {
             Ok(api_client)
         }
     }
 
+    // If a coin selection strategy is provided in the request, use it to choose the records paying for the request
+    fn get_record_finder(
+        record_finder: RecordFinder<N>,
+        coin_selection: &Option<String>,
+    ) -> Result<RecordFinder<N>, Rejection> {
+        if let Some(coin_selection) = coin_selection {
+            let coin_selection = coin_selection.parse::<CoinSelection>().or_reject()?;
+            if !coin_selection.selects_single_records() {
+                return Err(reject::custom(RestError::Request(
+                    "Invalid coin selection strategy, it must pay for each amount with a single record".to_string(),
+                )));
+            }
+            Ok(record_finder.with_coin_selector(coin_selection))
+        } else {
+            Ok(record_finder)
+        }
+    }
+
+    // Release the records reserved for a request once its transaction has settled, or right away if
+    // the transaction could not be created
+    fn release_reserved_records(
+        record_finder: RecordFinder<N>,
+        reserved_records: Vec<Record<N, Plaintext<N>>>,
+        transaction_id: &Result<String, Rejection>,
+    ) {
+        if reserved_records.is_empty() {
+            return;
+        }
+        // The node responds to a broadcast with the transaction ID as a JSON string
+        let transaction_id = transaction_id
+            .as_ref()
+            .ok()
+            .and_then(|transaction_id| N::TransactionID::from_str(transaction_id.trim().trim_matches('"')).ok());
+        tokio::task::spawn_blocking(move || {
+            let released = match transaction_id {
+                Some(transaction_id) => {
+                    record_finder.release_records_when_settled(transaction_id, &reserved_records).map(|_| ())
+                }
+                None => record_finder.release_records(&reserved_records),
+            };
+            if let Err(error) = released {
+                warn!("Failed to release reserved records: {error}");
+            }
+        });
+    }
+
     // Deploy a program to the network specified
     async fn deploy_program(
         request: DeployRequest<N>,
@@ -103,22 +211,32 @@ impl<N: Network> Rest<N> {
         }
         // Get API client and private key and create a program manager
         let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let record_finder = Self::get_record_finder(record_finder, &request.coin_selection)?;
         let private_key = Self::get_private_key(private_key_ciphertext, request.private_key, request.password.clone())?;
         let mut program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).or_reject()?;
         program_manager.add_program(&request.program).or_reject()?;
 
-        // Get the fee record if it is not provided in the request
+        // Get the fee record if it is not provided in the request, reserving it until the deployment settles
+        let mut reserved_records = vec![];
         let fee_record = if request.fee_record.is_none() {
-            spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?
+            let record_finder = record_finder.clone();
+            let fee_record = spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?;
+            reserved_records.push(fee_record.clone());
+            fee_record
         } else {
             request.fee_record.unwrap()
         };
 
         // Deploy the program and return the resulting transaction id
-        let transaction_id =
-            spawn_blocking!(program_manager.deploy_program(request.program.id(), request.fee, fee_record, None))?;
+        let transaction_id = spawn_blocking!(result: program_manager.deploy_program(
+            request.program.id(),
+            request.fee,
+            fee_record,
+            None,
+        ));
+        Self::release_reserved_records(record_finder, reserved_records, &transaction_id);
 
-        Ok(reply::json(&transaction_id))
+        Ok(reply::json(&transaction_id?))
     }
 
     // Execute a program on the network specified
@@ -127,6 +245,7 @@ impl<N: Network> Rest<N> {
         record_finder: RecordFinder<N>,
         private_key_ciphertext: Option<Ciphertext<N>>,
         api_client: AleoAPIClient<N>,
+        key_cache: Option<KeyCache>,
     ) -> Result<impl Reply, Rejection> {
         if request.fee == 0 {
             return Err(reject::custom(RestError::Request(
@@ -135,27 +254,45 @@ impl<N: Network> Rest<N> {
         }
         // Get API client and private key and create a program manager
         let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let record_finder = Self::get_record_finder(record_finder, &request.coin_selection)?;
         let private_key = Self::get_private_key(private_key_ciphertext, request.private_key, request.password.clone())?;
+
+        // Check the inputs against the function signature before any proving work
+        let (program_id, function, inputs) = (request.program_id, request.program_function, request.inputs.clone());
+        let validation_client = api_client.clone();
+        let (program, imports) =
+            spawn_blocking!(Self::validate_inputs(&validation_client, program_id, function, &inputs, &private_key))?;
+
         let mut program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).or_reject()?;
+        if let Some(key_cache) = key_cache {
+            program_manager = program_manager.with_key_cache(key_cache);
+        }
 
-        // Find a fee record if a fee is specified and a fee record is not provided
+        // Find a fee record if a fee is specified and a fee record is not provided, reserving it until
+        // the execution settles
+        let mut reserved_records = vec![];
         let fee_record = if request.fee_record.is_none() {
-            spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?
+            let record_finder = record_finder.clone();
+            let fee_record = spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?;
+            reserved_records.push(fee_record.clone());
+            fee_record
         } else {
             request.fee_record.take().unwrap()
         };
 
         // Execute the program and return the resulting transaction id
-        let transaction_id = spawn_blocking!(program_manager.execute_program(
-            request.program_id,
+        let transaction_id = spawn_blocking!(result: program_manager.execute_program_with_imports(
+            &program,
+            &imports,
             request.program_function,
             request.inputs.iter(),
             request.fee,
             fee_record,
             None,
-        ))?;
+        ));
+        Self::release_reserved_records(record_finder, reserved_records, &transaction_id);
 
-        Ok(reply::json(&transaction_id))
+        Ok(reply::json(&transaction_id?))
     }
 
     // Create a value transfer on the network specified
@@ -172,6 +309,7 @@ impl<N: Network> Rest<N> {
             )));
         }
         let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let record_finder = Self::get_record_finder(record_finder, &request.coin_selection)?;
         let private_key = Self::get_private_key(private_key_ciphertext, request.private_key, request.password.clone())?;
         let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).or_reject()?;
 
@@ -185,56 +323,174 @@ impl<N: Network> Rest<N> {
             _ => Err(anyhow!("Invalid transfer type specified, type must be one of the following: private, public, private-to-public, public-to-private")).or_reject()?,
         };
 
-        let (amount_record, fee_record) =
-            match transfer_type {
-                TransferType::Public => {
-                    if let Some(fee_record) = request.fee_record {
-                        (None, fee_record)
-                    } else {
-                        (None, spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?)
-                    }
+        // Find the records that are not provided in the request, reserving them until the transfer settles
+        let mut reserved_records = vec![];
+        let (amount_record, fee_record) = match transfer_type {
+            TransferType::Public | TransferType::PublicToPrivate => {
+                // The transfer is drawing from a public account balance, so only a fee record is needed
+                if let Some(fee_record) = request.fee_record {
+                    (None, fee_record)
+                } else {
+                    let record_finder = record_finder.clone();
+                    let fee_record = spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?;
+                    reserved_records.push(fee_record.clone());
+                    (None, fee_record)
                 }
-                TransferType::PublicToPrivate => {
-                    if let Some(fee_record) = request.fee_record {
-                        (None, fee_record)
-                    } else {
-                        (None, spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?)
-                    }
+            }
+            _ => match (request.amount_record, request.fee_record) {
+                (Some(amount_record), Some(fee_record)) => (Some(amount_record), fee_record),
+                (Some(amount_record), None) => {
+                    // Find a fee record if a fee is specified and a fee record is not provided
+                    let record_finder = record_finder.clone();
+                    let fee_record = spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?;
+                    reserved_records.push(fee_record.clone());
+                    (Some(amount_record), fee_record)
                 }
-                _ => {
-                    match (request.amount_record, request.fee_record) {
-                        (Some(amount_record), Some(fee_record)) => (Some(amount_record), fee_record),
-                        (Some(amount_record), None) => {
-                            // Find a fee record if a fee is specified and a fee record is not provided
-                            (
-                                Some(amount_record),
-                                spawn_blocking!(record_finder.find_one_record(&private_key, request.fee))?,
-                            )
-                        }
-                        (None, Some(fee_record)) => (
-                            Some(spawn_blocking!(record_finder.find_one_record(&private_key, request.amount))?),
-                            fee_record,
-                        ),
-                        (None, None) => {
-                            let (amount_record, fee_record) = spawn_blocking!(
-                                record_finder.find_amount_and_fee_records(request.amount, request.fee, &private_key)
-                            )?;
-                            (Some(amount_record), fee_record)
-                        }
-                    }
+                (None, Some(fee_record)) => {
+                    let record_finder = record_finder.clone();
+                    let amount_record = spawn_blocking!(record_finder.find_one_record(&private_key, request.amount))?;
+                    reserved_records.push(amount_record.clone());
+                    (Some(amount_record), fee_record)
                 }
-            };
+                (None, None) => {
+                    let record_finder = record_finder.clone();
+                    let (amount_record, fee_record) = spawn_blocking!(record_finder.find_amount_and_fee_records(
+                        request.amount,
+                        request.fee,
+                        &private_key
+                    ))?;
+                    reserved_records.extend([amount_record.clone(), fee_record.clone()]);
+                    (Some(amount_record), fee_record)
+                }
+            },
+        };
 
         // Run the transfer program within credits.aleo and return the resulting transaction id
-        let transaction_id = spawn_blocking!(program_manager.transfer(
+        let transaction_id = spawn_blocking!(result: program_manager.transfer(
             request.amount,
             request.fee,
             request.recipient,
             transfer_type,
             None,
             amount_record,
+            fee_record,
+        ));
+        Self::release_reserved_records(record_finder, reserved_records, &transaction_id);
+
+        Ok(reply::json(&transaction_id?))
+    }
+
+    // Split a credits record into two records on the network specified
+    async fn split(
+        request: SplitRequest<N>,
+        record_finder: RecordFinder<N>,
+        private_key_ciphertext: Option<Ciphertext<N>>,
+        api_client: AleoAPIClient<N>,
+    ) -> Result<impl Reply, Rejection> {
+        if request.amount == 0 {
+            return Err(reject::custom(RestError::Request(
+                "Amount must be greater than zero in order to split a record".to_string(),
+            )));
+        }
+        // Get API client and private key and create a program manager
+        let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let record_finder = Self::get_record_finder(record_finder, &request.coin_selection)?;
+        let private_key = Self::get_private_key(private_key_ciphertext, request.private_key, request.password.clone())?;
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).or_reject()?;
+
+        // Find a record holding more than the amount if one is not provided, reserving it until the
+        // split settles
+        let mut reserved_records = vec![];
+        let record = if let Some(record) = request.record {
+            record
+        } else {
+            let record_finder = record_finder.clone();
+            let amount = request.amount.saturating_add(1);
+            let record = spawn_blocking!(record_finder.find_one_record(&private_key, amount))?;
+            reserved_records.push(record.clone());
+            record
+        };
+
+        // Run the split function within credits.aleo and return the resulting transaction id
+        let transaction_id = spawn_blocking!(result: program_manager.split_record(request.amount, record, None));
+        Self::release_reserved_records(record_finder, reserved_records, &transaction_id);
+
+        Ok(reply::json(&transaction_id?))
+    }
+
+    // Join two credits records into a single record on the network specified
+    async fn join(
+        request: JoinRequest<N>,
+        record_finder: RecordFinder<N>,
+        private_key_ciphertext: Option<Ciphertext<N>>,
+        api_client: AleoAPIClient<N>,
+    ) -> Result<impl Reply, Rejection> {
+        if request.fee == 0 {
+            return Err(reject::custom(RestError::Request(
+                "Fee must be greater than zero in order to join records on the Aleo Network".to_string(),
+            )));
+        }
+        // Get API client and private key and create a program manager
+        let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let record_finder = Self::get_record_finder(record_finder, &request.coin_selection)?;
+        let private_key = Self::get_private_key(private_key_ciphertext, request.private_key, request.password.clone())?;
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).or_reject()?;
+
+        // Reserve the records being joined so they are not chosen to pay the fee of this or another
+        // request, then find a fee record if one is not provided
+        let mut reserved_records = vec![];
+        if let Some(reservations) = record_finder.reservations() {
+            let joined_records = [request.first_record.clone(), request.second_record.clone()];
+            reservations.reserve(&joined_records).or_reject()?;
+            reserved_records.extend(joined_records);
+        }
+        let fee_record = if let Some(fee_record) = request.fee_record {
+            fee_record
+        } else {
+            let finder = record_finder.clone();
+            let fee_record = spawn_blocking!(result: finder.find_one_record(&private_key, request.fee));
+            // Release the records being joined right away if no fee record could be found
+            if fee_record.is_err() {
+                record_finder.release_records(&reserved_records).or_reject()?;
+            }
+            let fee_record = fee_record?;
+            reserved_records.push(fee_record.clone());
             fee_record
-        ))?;
+        };
+
+        // Run the join function within credits.aleo and return the resulting transaction id
+        let transaction_id = spawn_blocking!(result: program_manager.join_records(
+            request.first_record,
+            request.second_record,
+            request.fee,
+            fee_record,
+            None,
+        ));
+        Self::release_reserved_records(record_finder, reserved_records, &transaction_id);
+
+        Ok(reply::json(&transaction_id?))
+    }
+
+    // Verify a transaction built elsewhere and broadcast it to the network specified
+    async fn broadcast(request: BroadcastRequest<N>, api_client: AleoAPIClient<N>) -> Result<impl Reply, Rejection> {
+        let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+
+        let transaction = request.transaction;
+        let transaction_id = spawn_blocking!({
+            ProgramManager::<N>::verify_transaction::<A>(&transaction, &api_client)
+                .and_then(|_| api_client.transaction_broadcast(transaction))
+        })?;
+
         Ok(reply::json(&transaction_id))
     }
+
+    // Prove an execution authorized elsewhere and return the resulting transaction without
+    // broadcasting it
+    async fn prove(request: ProveRequest<N>, api_client: AleoAPIClient<N>) -> Result<impl Reply, Rejection> {
+        let api_client = Self::get_api_client(api_client, &request.peer_url)?;
+        let authorization = request.authorization;
+        let transaction = spawn_blocking!(ProgramManager::<N>::prove_authorization::<A>(&authorization, &api_client))?;
+
+        Ok(reply::json(transaction.transaction()))
+    }
 }
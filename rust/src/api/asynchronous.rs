@@ -0,0 +1,398 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Asynchronous Aleo API client for interacting with the Aleo Beacon API
+///
+/// This client exposes the same methods as the blocking [AleoAPIClient], but each request is
+/// performed without blocking the calling thread, allowing many queries to be issued concurrently
+/// from within an async runtime such as tokio.
+#[derive(Clone, Debug)]
+pub struct AsyncAleoAPIClient<N: Network> {
+    client: reqwest::Client,
+    base_url: String,
+    network_id: String,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> AsyncAleoAPIClient<N> {
+    pub fn new(base_url: &str, chain: &str) -> Result<Self> {
+        let client = reqwest::Client::new();
+        ensure!(
+            base_url.starts_with("http://") || base_url.starts_with("https://"),
+            "specified url {base_url} invalid, the base url must start with or https:// (or http:// if doing local development)"
+        );
+        Ok(AsyncAleoAPIClient {
+            client,
+            base_url: base_url.to_string(),
+            network_id: chain.to_string(),
+            _network: PhantomData,
+        })
+    }
+
+    pub fn testnet3() -> Self {
+        Self::new("https://vm.aleo.org/api", "testnet3").unwrap()
+    }
+
+    pub fn local_testnet3(port: &str) -> Self {
+        Self::new(&format!("http://0.0.0.0:{}", port), "testnet3").unwrap()
+    }
+
+    /// Get base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get network ID being interacted with
+    pub fn network_id(&self) -> &str {
+        &self.network_id
+    }
+
+    /// Perform a GET request and deserialize the json response
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ApiError> {
+        let response = self.client.get(url).send().await.map_err(|error| ApiError::from_reqwest(url, error))?;
+        match response.status().as_u16() {
+            200..=299 => response.json().await.map_err(|error| ApiError::from_reqwest(url, error)),
+            code => Err(ApiError::from_status(url, code, response.text().await.unwrap_or_default())),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+impl<N: Network> AsyncAleoAPIClient<N> {
+    /// Get the latest block height
+    pub async fn latest_height(&self) -> Result<u32> {
+        let url = format!("{}/{}/latest/height", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the latest block hash
+    pub async fn latest_hash(&self) -> Result<N::BlockHash> {
+        let url = format!("{}/{}/latest/hash", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the latest block
+    pub async fn latest_block(&self) -> Result<Block<N>> {
+        let url = format!("{}/{}/latest/block", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the block matching the specific height from the network
+    pub async fn get_block(&self, height: u32) -> Result<Block<N>> {
+        let url = format!("{}/{}/block/{height}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the height of the block with the given hash
+    pub async fn get_height(&self, block_hash: N::BlockHash) -> Result<u32> {
+        let url = format!("{}/{}/height/{block_hash}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get a range of blocks from the network (limited 50 blocks at a time)
+    pub async fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
+        if start_height >= end_height {
+            bail!("Start height must be less than end height");
+        } else if end_height - start_height > 50 {
+            bail!("Cannot request more than 50 blocks at a time");
+        }
+
+        let url = format!("{}/{}/blocks?start={start_height}&end={end_height}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Retrieve a transaction by via its transaction id
+    pub async fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        let url = format!("{}/{}/transaction/{transaction_id}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get pending transactions currently in the mempool.
+    pub async fn get_memory_pool_transactions(&self) -> Result<Vec<Transaction<N>>> {
+        let url = format!("{}/{}/memoryPool/transactions", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get a program from the network by its ID. This method will return an error if it does not exist.
+    pub async fn get_program(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<Program<N>> {
+        // Prepare the program ID.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        // Perform the request.
+        let url = format!("{}/{}/program/{program_id}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Resolve imports of a program in a depth-first-search order from a program id
+    pub async fn get_program_imports(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+    ) -> Result<IndexMap<ProgramID<N>, Program<N>>> {
+        let program = self.get_program(program_id).await?;
+        self.get_program_imports_from_source(&program).await
+    }
+
+    /// Resolve imports of a program in a depth-first-search order from program source code
+    pub async fn get_program_imports_from_source(
+        &self,
+        program: &Program<N>,
+    ) -> Result<IndexMap<ProgramID<N>, Program<N>>> {
+        // Walk the import graph iteratively, since async functions cannot recurse directly
+        let mut found_imports = IndexMap::new();
+        let mut stack = program.imports().keys().rev().map(|id| (*id, false)).collect::<Vec<_>>();
+        let mut fetched = IndexMap::<ProgramID<N>, Program<N>>::new();
+        while let Some((import_id, expanded)) = stack.pop() {
+            if expanded {
+                // All nested imports have been resolved, so the import itself can be added
+                if let Some(imported_program) = fetched.remove(&import_id) {
+                    found_imports.insert(import_id, imported_program);
+                }
+                continue;
+            }
+            if found_imports.contains_key(&import_id) {
+                continue;
+            }
+            ensure!(!fetched.contains_key(&import_id), "Circular dependency discovered in program imports");
+            let imported_program = self.get_program(import_id).await?;
+            stack.push((import_id, true));
+            stack.extend(imported_program.imports().keys().rev().map(|id| (*id, false)));
+            fetched.insert(import_id, imported_program);
+        }
+        Ok(found_imports)
+    }
+
+    /// Get all mappings associated with a program.
+    pub async fn get_program_mappings(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<Vec<Identifier<N>>> {
+        // Prepare the program ID.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        // Perform the request.
+        let url = format!("{}/{}/program/{program_id}/mappings", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the current value of a mapping given a specific program, mapping name, and mapping key
+    pub async fn get_mapping_value(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        mapping_name: impl TryInto<Identifier<N>>,
+        key: impl TryInto<Plaintext<N>>,
+    ) -> Result<Value<N>> {
+        // Prepare the program ID.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        // Prepare the mapping name.
+        let mapping_name = mapping_name.try_into().map_err(|_| anyhow!("Invalid mapping name"))?;
+        // Prepare the key.
+        let key = key.try_into().map_err(|_| anyhow!("Invalid key"))?;
+        // Perform the request.
+        let url = format!("{}/{}/program/{program_id}/mapping/{mapping_name}/{key}", self.base_url, self.network_id);
+        // The node responds with null if the key is not present in the mapping
+        match self.get_json::<Option<Value<N>>>(&url).await? {
+            Some(value) => Ok(value),
+            None => Err(ApiError::NotFound { url }.into()),
+        }
+    }
+
+    pub async fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash> {
+        let url = format!("{}/{}/find/blockHash/{transaction_id}", self.base_url, self.network_id);
+        // The node responds with null if the transaction is not in a block
+        match self.get_json::<Option<N::BlockHash>>(&url).await? {
+            Some(block_hash) => Ok(block_hash),
+            None => Err(ApiError::NotFound { url }.into()),
+        }
+    }
+
+    /// Returns the transition ID that contains the given `input ID` or `output ID`.
+    pub async fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID> {
+        let url = format!("{}/{}/find/transitionID/{input_or_output_id}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the latest global state root
+    pub async fn latest_state_root(&self) -> Result<N::StateRoot> {
+        let url = format!("{}/{}/latest/stateRoot", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Get the inclusion path of a record commitment against the latest global state root
+    pub async fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        let url = format!("{}/{}/statePath/{commitment}", self.base_url, self.network_id);
+        Ok(self.get_json(&url).await?)
+    }
+
+    /// Scans the ledger for records that match the given view key.
+    ///
+    /// Use [AsyncAleoAPIClient::scan_with_cursor] to track the progress of the scan or resume it.
+    pub async fn scan(
+        &self,
+        view_key: impl TryInto<ViewKey<N>>,
+        block_heights: Range<u32>,
+        max_records: Option<usize>,
+    ) -> Result<Vec<(Field<N>, Record<N, Ciphertext<N>>)>> {
+        // Prepare the view key.
+        let view_key = view_key.try_into().map_err(|_| anyhow!("Invalid view key"))?;
+        let mut cursor = ScanCursor::new(block_heights.start);
+        self.scan_with_cursor(&view_key, block_heights.end, &mut cursor, max_records, |_| ()).await?;
+        Ok(cursor.records)
+    }
+
+    /// Scan the blocks from the cursor's `next_height` up to (but excluding) `end_height` for
+    /// records owned by the view key, one window of 50 blocks at a time, in the same way as
+    /// [BlockScanner::scan]. `on_progress` is invoked after each window is scanned.
+    pub async fn scan_with_cursor(
+        &self,
+        view_key: &ViewKey<N>,
+        end_height: u32,
+        cursor: &mut ScanCursor<N>,
+        max_records: Option<usize>,
+        on_progress: impl Fn(&ScanProgress),
+    ) -> Result<()> {
+        // Compute the x-coordinate of the address.
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        let max_records = max_records.unwrap_or(usize::MAX);
+
+        while cursor.next_height < end_height && cursor.records.len() < max_records {
+            let window_end = cursor.next_height.saturating_add(MAX_WINDOW_SIZE).min(end_height);
+            let blocks = self.get_blocks(cursor.next_height, window_end).await?;
+            cursor.records.extend(owned_records(blocks, view_key, &address_x_coordinate));
+            cursor.next_height = window_end;
+            on_progress(&ScanProgress { next_height: window_end, end_height, records_found: cursor.records.len() });
+        }
+        Ok(())
+    }
+
+    /// Search for unspent records in the ledger
+    ///
+    /// Records are searched for in the same way as the blocking [AleoAPIClient] does. Use
+    /// [AsyncAleoAPIClient::scan_unspent] to track the progress of the search or resume it.
+    pub async fn get_unspent_records(
+        &self,
+        private_key: &PrivateKey<N>,
+        block_heights: Range<u32>,
+        max_gates: Option<u64>,
+        specified_amounts: Option<&Vec<u64>>,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>> {
+        let search = UnspentSearch::new(&block_heights, max_gates, specified_amounts)?;
+        let latest_height = self.latest_height().await?;
+        let mut cursor = UnspentScanCursor::new(block_heights.start);
+        loop {
+            let until = |records: &[FoundRecord<N>]| search.is_complete(records);
+            self.scan_unspent(private_key, block_heights.end, &mut cursor, until, |_| ()).await?;
+            if cursor.next_height > latest_height {
+                break;
+            }
+            // Records may have been spent in blocks after the scanned ones, keep scanning if any were
+            let found = cursor.records.len();
+            let mut unspent = Vec::with_capacity(found);
+            for record in std::mem::take(&mut cursor.records) {
+                if is_unspent(self.find_transition_id(record.serial_number).await)? {
+                    unspent.push(record);
+                }
+            }
+            cursor.records = unspent;
+            if cursor.records.len() == found || cursor.next_height >= block_heights.end {
+                break;
+            }
+        }
+        search.finish(cursor.records)
+    }
+
+    /// Scan the blocks from the cursor's `next_height` up to (but excluding) `end_height` for
+    /// unspent records owned by the private key, one window of 50 blocks at a time, in the same way
+    /// as [BlockScanner::scan_unspent]. `on_progress` is invoked after each window is scanned.
+    pub async fn scan_unspent(
+        &self,
+        private_key: &PrivateKey<N>,
+        end_height: u32,
+        cursor: &mut UnspentScanCursor<N>,
+        until: impl Fn(&[FoundRecord<N>]) -> bool,
+        on_progress: impl Fn(&ScanProgress),
+    ) -> Result<()> {
+        let view_key = ViewKey::try_from(private_key)?;
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+
+        while cursor.next_height < end_height {
+            let window_end = cursor.next_height.saturating_add(MAX_WINDOW_SIZE).min(end_height);
+            let blocks = self.get_blocks(cursor.next_height, window_end).await?;
+            UnspentWindow::new(blocks, private_key, &view_key, &address_x_coordinate)?.commit(&mut cursor.records);
+            cursor.next_height = window_end;
+            on_progress(&ScanProgress { next_height: window_end, end_height, records_found: cursor.records.len() });
+            if until(&cursor.records) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Broadcast a deploy or execute transaction to the Aleo network
+    pub async fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String> {
+        let url = format!("{}/{}/transaction/broadcast", self.base_url, self.network_id);
+        let response = self
+            .client
+            .post(&url)
+            .json(&transaction)
+            .send()
+            .await
+            .map_err(|error| ApiError::from_reqwest(&url, error))?;
+        let code = response.status().as_u16();
+        if response.status().is_success() {
+            response.text().await.map_err(|error| ApiError::from_reqwest(&url, error).into())
+        } else {
+            let message = response.text().await.unwrap_or_default();
+            Err(ApiError::TransactionRejected { url, code, message }.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_async_api_get_blocks() {
+        let client = AsyncAleoAPIClient::<Testnet3>::testnet3();
+        let blocks = client.get_blocks(0, 3).await.unwrap();
+
+        // Check height matches
+        assert_eq!(blocks[0].height(), 0);
+        assert_eq!(blocks[1].height(), 1);
+        assert_eq!(blocks[2].height(), 2);
+
+        // Check block hashes
+        assert_eq!(blocks[1].previous_hash(), blocks[0].hash());
+        assert_eq!(blocks[2].previous_hash(), blocks[1].hash());
+    }
+
+    #[tokio::test]
+    async fn test_async_import_resolution() {
+        let client = AsyncAleoAPIClient::<Testnet3>::testnet3();
+        let imports = client.get_program_imports("imported_add_mul.aleo").await.unwrap();
+        let id1 = ProgramID::<Testnet3>::from_str("multiply_test.aleo").unwrap();
+        let id2 = ProgramID::<Testnet3>::from_str("double_test.aleo").unwrap();
+        let id3 = ProgramID::<Testnet3>::from_str("addition_test.aleo").unwrap();
+
+        assert!(imports.contains_key(&id1));
+        assert!(imports.contains_key(&id2));
+        assert!(imports.contains_key(&id3));
+        assert_eq!(imports.len(), 3);
+    }
+
+    #[test]
+    fn test_async_client_rejects_invalid_urls() {
+        assert!(AsyncAleoAPIClient::<Testnet3>::new("localhost:3030", "testnet3").is_err());
+        assert!(AsyncAleoAPIClient::<Testnet3>::new("http://localhost:3030", "testnet3").is_ok());
+    }
+}
@@ -16,43 +16,31 @@
 
 use super::*;
 
-#[cfg(not(feature = "async"))]
 #[allow(clippy::type_complexity)]
 impl<N: Network> AleoAPIClient<N> {
     /// Get the latest block height
     pub fn latest_height(&self) -> Result<u32> {
-        let url = format!("{}/{}/latest/height", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(height) => Ok(height),
-            Err(error) => bail!("Failed to parse the latest block height: {error}"),
-        }
+        Ok(self.get_json("latest/height")?)
     }
 
     /// Get the latest block hash
     pub fn latest_hash(&self) -> Result<N::BlockHash> {
-        let url = format!("{}/{}/latest/hash", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(hash) => Ok(hash),
-            Err(error) => bail!("Failed to parse the latest block hash: {error}"),
-        }
+        Ok(self.get_json("latest/hash")?)
     }
 
     /// Get the latest block
     pub fn latest_block(&self) -> Result<Block<N>> {
-        let url = format!("{}/{}/latest/block", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(block) => Ok(block),
-            Err(error) => bail!("Failed to parse the latest block: {error}"),
-        }
+        Ok(self.get_json("latest/block")?)
     }
 
     /// Get the block matching the specific height from the network
     pub fn get_block(&self, height: u32) -> Result<Block<N>> {
-        let url = format!("{}/{}/block/{height}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(block) => Ok(block),
-            Err(error) => bail!("Failed to parse block {height}: {error}"),
-        }
+        Ok(self.get_json(&format!("block/{height}"))?)
+    }
+
+    /// Get the height of the block with the given hash
+    pub fn get_height(&self, block_hash: N::BlockHash) -> Result<u32> {
+        Ok(self.get_json(&format!("height/{block_hash}"))?)
     }
 
     /// Get a range of blocks from the network (limited 50 blocks at a time)
@@ -63,31 +51,17 @@ impl<N: Network> AleoAPIClient<N> {
             bail!("Cannot request more than 50 blocks at a time");
         }
 
-        let url = format!("{}/{}/blocks?start={start_height}&end={end_height}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(blocks) => Ok(blocks),
-            Err(error) => {
-                bail!("Failed to parse blocks {start_height} (inclusive) to {end_height} (exclusive): {error}")
-            }
-        }
+        Ok(self.get_json(&format!("blocks?start={start_height}&end={end_height}"))?)
     }
 
     /// Retrieve a transaction by via its transaction id
     pub fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
-        let url = format!("{}/{}/transaction/{transaction_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(transaction) => Ok(transaction),
-            Err(error) => bail!("Failed to parse transaction '{transaction_id}': {error}"),
-        }
+        Ok(self.get_json(&format!("transaction/{transaction_id}"))?)
     }
 
     /// Get pending transactions currently in the mempool.
     pub fn get_memory_pool_transactions(&self) -> Result<Vec<Transaction<N>>> {
-        let url = format!("{}/{}/memoryPool/transactions", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(transactions) => Ok(transactions),
-            Err(error) => bail!("Failed to parse memory pool transactions: {error}"),
-        }
+        Ok(self.get_json("memoryPool/transactions")?)
     }
 
     /// Get a program from the network by its ID. This method will return an error if it does not exist.
@@ -95,11 +69,7 @@ impl<N: Network> AleoAPIClient<N> {
         // Prepare the program ID.
         let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
         // Perform the request.
-        let url = format!("{}/{}/program/{program_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(program) => Ok(program),
-            Err(error) => bail!("Failed to parse program {program_id}: {error}"),
-        }
+        Ok(self.get_json(&format!("program/{program_id}"))?)
     }
 
     /// Resolve imports of a program in a depth-first-search order from a program id
@@ -113,18 +83,7 @@ impl<N: Network> AleoAPIClient<N> {
 
     /// Resolve imports of a program in a depth-first-search order from program source code
     pub fn get_program_imports_from_source(&self, program: &Program<N>) -> Result<IndexMap<ProgramID<N>, Program<N>>> {
-        let mut found_imports = IndexMap::new();
-        for (import_id, _) in program.imports().iter() {
-            let imported_program = self.get_program(import_id)?;
-            let nested_imports = self.get_program_imports_from_source(&imported_program)?;
-            for (id, import) in nested_imports.into_iter() {
-                found_imports.contains_key(&id).then(|| anyhow!("Circular dependency discovered in program imports"));
-                found_imports.insert(id, import);
-            }
-            found_imports.contains_key(import_id).then(|| anyhow!("Circular dependency discovered in program imports"));
-            found_imports.insert(*import_id, imported_program);
-        }
-        Ok(found_imports)
+        NetworkClient::get_program_imports_from_source(self, program)
     }
 
     /// Get all mappings associated with a program.
@@ -132,11 +91,7 @@ impl<N: Network> AleoAPIClient<N> {
         // Prepare the program ID.
         let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
         // Perform the request.
-        let url = format!("{}/{}/program/{program_id}/mappings", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(program_mappings) => Ok(program_mappings),
-            Err(error) => bail!("Failed to parse program {program_id}: {error}"),
-        }
+        Ok(self.get_json(&format!("program/{program_id}/mappings"))?)
     }
 
     /// Get the current value of a mapping given a specific program, mapping name, and mapping key
@@ -153,28 +108,36 @@ impl<N: Network> AleoAPIClient<N> {
         // Prepare the key.
         let key = key.try_into().map_err(|_| anyhow!("Invalid key"))?;
         // Perform the request.
-        let url = format!("{}/{}/program/{program_id}/mapping/{mapping_name}/{key}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(transition_id) => Ok(transition_id),
-            Err(error) => bail!("Failed to parse transition ID: {error}"),
+        let route = format!("program/{program_id}/mapping/{mapping_name}/{key}");
+        // The node responds with null if the key is not present in the mapping
+        match self.get_json::<Option<Value<N>>>(&route)? {
+            Some(value) => Ok(value),
+            None => Err(ApiError::NotFound { url: self.url(&route) }.into()),
         }
     }
 
     pub fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash> {
-        let url = format!("{}/{}/find/blockHash/{transaction_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(hash) => Ok(hash),
-            Err(error) => bail!("Failed to parse block hash: {error}"),
+        let route = format!("find/blockHash/{transaction_id}");
+        // The node responds with null if the transaction is not in a block
+        match self.get_json::<Option<N::BlockHash>>(&route)? {
+            Some(block_hash) => Ok(block_hash),
+            None => Err(ApiError::NotFound { url: self.url(&route) }.into()),
         }
     }
 
     /// Returns the transition ID that contains the given `input ID` or `output ID`.
     pub fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID> {
-        let url = format!("{}/{}/find/transitionID/{input_or_output_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
-            Ok(transition_id) => Ok(transition_id),
-            Err(error) => bail!("Failed to parse transition ID: {error}"),
-        }
+        Ok(self.get_json(&format!("find/transitionID/{input_or_output_id}"))?)
+    }
+
+    /// Get the latest global state root
+    pub fn latest_state_root(&self) -> Result<N::StateRoot> {
+        Ok(self.get_json("latest/stateRoot")?)
+    }
+
+    /// Get the inclusion path of a record commitment against the latest global state root
+    pub fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        Ok(self.get_json(&format!("statePath/{commitment}"))?)
     }
 
     /// Scans the ledger for records that match the given view key.
@@ -186,43 +149,7 @@ impl<N: Network> AleoAPIClient<N> {
     ) -> Result<Vec<(Field<N>, Record<N, Ciphertext<N>>)>> {
         // Prepare the view key.
         let view_key = view_key.try_into().map_err(|_| anyhow!("Invalid view key"))?;
-        // Compute the x-coordinate of the address.
-        let address_x_coordinate = view_key.to_address().to_x_coordinate();
-
-        // Prepare the starting block height, by rounding down to the nearest step of 50.
-        let start_block_height = block_heights.start - (block_heights.start % 50);
-        // Prepare the ending block height, by rounding up to the nearest step of 50.
-        let end_block_height = block_heights.end + (50 - (block_heights.end % 50));
-
-        // Initialize a vector for the records.
-        let mut records = Vec::new();
-
-        for start_height in (start_block_height..end_block_height).step_by(50) {
-            println!("Searching blocks {} to {} for records...", start_height, end_block_height);
-            if start_height >= block_heights.end {
-                break;
-            }
-            let end = start_height + 50;
-            let end_height = if end > block_heights.end { block_heights.end } else { end };
-
-            // Prepare the URL.
-            let records_iter =
-                self.get_blocks(start_height, end_height)?.into_iter().flat_map(|block| block.into_records());
-
-            // Filter the records by the view key.
-            records.extend(records_iter.filter_map(|(commitment, record)| {
-                match record.is_owner_with_address_x_coordinate(&view_key, &address_x_coordinate) {
-                    true => Some((commitment, record)),
-                    false => None,
-                }
-            }));
-
-            if records.len() >= max_records.unwrap_or(usize::MAX) {
-                break;
-            }
-        }
-
-        Ok(records)
+        NetworkClient::scan(self, &view_key, block_heights, max_records)
     }
 
     /// Search for unspent records in the ledger
@@ -233,125 +160,21 @@ impl<N: Network> AleoAPIClient<N> {
         max_gates: Option<u64>,
         specified_amounts: Option<&Vec<u64>>,
     ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>> {
-        let view_key = ViewKey::try_from(private_key)?;
-        let address_x_coordinate = view_key.to_address().to_x_coordinate();
-
-        let step_size = 49;
-        let required_amounts = if let Some(amounts) = specified_amounts {
-            ensure!(!amounts.is_empty(), "If specific amounts are specified, there must be one amount specified");
-            let mut required_amounts = amounts.clone();
-            required_amounts.sort_by(|a, b| b.cmp(a));
-            required_amounts
-        } else {
-            vec![]
-        };
-
-        ensure!(
-            block_heights.start < block_heights.end,
-            "The start block height must be less than the end block height"
-        );
-
-        // Initialize a vector for the records.
-        let mut records = vec![];
-
-        let mut total_gates = 0u64;
-        let mut end_height = block_heights.end;
-        let mut start_height = block_heights.end.saturating_sub(step_size);
-
-        for _ in (block_heights.start..block_heights.end).step_by(step_size as usize) {
-            println!("Searching blocks {} to {} for records...", start_height, end_height);
-            // Get blocks
-            let records_iter =
-                self.get_blocks(start_height, end_height)?.into_iter().flat_map(|block| block.into_records());
-
-            // Search in reverse order from the latest block to the earliest block
-            end_height = start_height;
-            start_height = start_height.saturating_sub(step_size);
-            if start_height < block_heights.start {
-                start_height = block_heights.start
-            };
-            // Filter the records by the view key.
-            records.extend(records_iter.filter_map(|(commitment, record)| {
-                match record.is_owner_with_address_x_coordinate(&view_key, &address_x_coordinate) {
-                    true => {
-                        let sn = Record::<N, Ciphertext<N>>::serial_number(*private_key, commitment).ok()?;
-                        if self.find_transition_id(sn).is_err() {
-                            let record = record.decrypt(&view_key);
-                            if let Ok(record) = record {
-                                total_gates += record.microcredits().unwrap_or(0);
-                                Some((commitment, record))
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    }
-                    false => None,
-                }
-            }));
-            // If a maximum number of gates is specified, stop searching when the total gates
-            // exceeds the specified limit
-            if max_gates.is_some() && total_gates >= max_gates.unwrap() {
-                break;
-            }
-            // If a list of specified amounts is specified, stop searching when records matching
-            // those amounts are found
-            if !required_amounts.is_empty() {
-                records.sort_by(|(_, first), (_, second)| {
-                    second.microcredits().unwrap_or(0).cmp(&first.microcredits().unwrap_or(0))
-                });
-                let mut found_indices = std::collections::HashSet::<usize>::new();
-                required_amounts.iter().for_each(|amount| {
-                    for (pos, (_, found_record)) in records.iter().enumerate() {
-                        let found_amount = found_record.microcredits().unwrap_or(0);
-                        if !found_indices.contains(&pos) && found_amount >= *amount {
-                            found_indices.insert(pos);
-                        }
-                    }
-                });
-                if found_indices.len() >= required_amounts.len() {
-                    let found_records = records[0..required_amounts.len()].to_vec();
-                    return Ok(found_records);
-                }
-            }
-        }
-        if !required_amounts.is_empty() {
-            bail!(
-                "Could not find enough records with the specified amounts, consider splitting records into smaller amounts"
-            );
-        }
-        Ok(records)
+        NetworkClient::get_unspent_records(self, private_key, block_heights, max_gates, specified_amounts)
     }
 
     /// Broadcast a deploy or execute transaction to the Aleo network
     pub fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String> {
-        let url = format!("{}/{}/transaction/broadcast", self.base_url, self.network_id);
+        let url = self.url("transaction/broadcast");
         match self.client.post(&url).send_json(&transaction) {
-            Ok(response) => match response.into_string() {
-                Ok(success_response) => Ok(success_response),
-                Err(error) => bail!("❌ Transaction response was malformed {}", error),
-            },
-            Err(error) => {
-                let error_message = match error {
-                    ureq::Error::Status(code, response) => {
-                        format!("(status code {code}: {:?})", response.into_string()?)
-                    }
-                    ureq::Error::Transport(err) => format!("({err})"),
-                };
-
-                match transaction {
-                    Transaction::Deploy(..) => {
-                        bail!("❌ Failed to deploy program to {}: {}", &url, error_message)
-                    }
-                    Transaction::Execute(..) => {
-                        bail!("❌ Failed to broadcast execution to {}: {}", &url, error_message)
-                    }
-                    Transaction::Fee(..) => {
-                        bail!("❌ Failed to broadcast fee execution to {}: {}", &url, error_message)
-                    }
-                }
+            Ok(response) => response
+                .into_string()
+                .map_err(|error| ApiError::Transport { url, message: format!("Malformed response: {error}") }.into()),
+            Err(ureq::Error::Status(code, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                Err(ApiError::TransactionRejected { url, code, message }.into())
             }
+            Err(error) => Err(ApiError::from_ureq(&url, error).into()),
         }
     }
 }
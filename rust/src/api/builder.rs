@@ -0,0 +1,242 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::sync::atomic::AtomicUsize;
+
+/// Time to wait for a connection to a peer used by the SDK's command line tools and servers
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time to wait for a response from a peer used by the SDK's command line tools and servers
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Policy for retrying idempotent requests that fail with a transient error
+///
+/// A failed request is first retried against each of the client's other peers. Once every peer
+/// has failed, the client waits for an exponentially increasing backoff before trying them again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of times the peers are retried after all of them have failed
+    pub max_retries: u32,
+    /// Time to wait before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the time to wait between retries
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self { max_retries, initial_backoff, max_backoff }
+    }
+
+    /// A policy that never retries a request
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Get the time to wait before the given retry, starting from zero
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let multiplier = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.initial_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retry up to 3 times, waiting 0.5, 1 and 2 seconds between attempts
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500), Duration::from_secs(10))
+    }
+}
+
+/// Builder for an [AleoAPIClient] with timeouts, retries and fallback peers
+///
+/// Basic Usage:
+/// let api_client = AleoAPIClient::<Testnet3>::builder()
+///     .peer("https://vm.aleo.org/api")
+///     .peer("http://localhost:3030")
+///     .read_timeout(Duration::from_secs(30))
+///     .build()?;
+#[derive(Clone, Debug)]
+pub struct AleoAPIClientBuilder<N: Network> {
+    peers: Vec<String>,
+    network_id: String,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    _network: PhantomData<N>,
+}
+
+impl<N: Network> Default for AleoAPIClientBuilder<N> {
+    fn default() -> Self {
+        Self {
+            peers: vec![],
+            network_id: "testnet3".to_string(),
+            connect_timeout: None,
+            read_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            _network: PhantomData,
+        }
+    }
+}
+
+impl<N: Network> AleoAPIClientBuilder<N> {
+    /// Add a peer to send requests to. Peers are tried in the order they are added.
+    pub fn peer(mut self, base_url: &str) -> Self {
+        self.peers.push(base_url.to_string());
+        self
+    }
+
+    /// Add several peers to send requests to. Peers are tried in the order they are added.
+    pub fn peers<S: AsRef<str>>(mut self, base_urls: impl IntoIterator<Item = S>) -> Self {
+        self.peers.extend(base_urls.into_iter().map(|base_url| base_url.as_ref().to_string()));
+        self
+    }
+
+    /// Set the network ID requests are made for [default: testnet3]
+    pub fn network_id(mut self, network_id: &str) -> Self {
+        self.network_id = network_id.to_string();
+        self
+    }
+
+    /// Set the maximum time to wait for a connection to a peer to be established
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum time to wait for a response from a peer
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the policy for retrying failed GET requests [default: RetryPolicy::default()]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the API client
+    pub fn build(self) -> Result<AleoAPIClient<N>> {
+        ensure!(!self.peers.is_empty(), "At least one peer url must be specified");
+        for base_url in self.peers.iter() {
+            ensure!(
+                base_url.starts_with("http://") || base_url.starts_with("https://"),
+                "specified url {base_url} invalid, the base url must start with or https:// (or http:// if doing local development)"
+            );
+        }
+
+        let mut agent = ureq::AgentBuilder::new();
+        if let Some(timeout) = self.connect_timeout {
+            agent = agent.timeout_connect(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            agent = agent.timeout_read(timeout);
+        }
+
+        Ok(AleoAPIClient {
+            client: agent.build(),
+            peers: self.peers,
+            active_peer: Arc::new(AtomicUsize::new(0)),
+            network_id: self.network_id,
+            retry_policy: self.retry_policy,
+            _network: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve a fixed json body to every request made to the returned url
+    fn serve_json(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let _ = stream.read(&mut [0u8; 1024]);
+                let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    /// Get the url of a local port nothing is listening on
+    fn closed_port_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(500));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+        assert_eq!(RetryPolicy::none().backoff(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_peers() {
+        assert!(AleoAPIClient::<Testnet3>::builder().build().is_err());
+        assert!(AleoAPIClient::<Testnet3>::builder()
+            .peer("http://localhost:3030")
+            .peer("localhost:3030")
+            .build()
+            .is_err());
+        assert!(AleoAPIClient::<Testnet3>::builder()
+            .peers(["http://localhost:3030", "https://vm.aleo.org/api"])
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_client_fails_over_to_next_peer() {
+        let live_url = serve_json("7");
+        let api_client = AleoAPIClient::<Testnet3>::builder()
+            .peer(&closed_port_url())
+            .peer(&live_url)
+            .connect_timeout(Duration::from_secs(1))
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        assert_eq!(api_client.latest_height().unwrap(), 7);
+        // The peer that answered is used for subsequent requests
+        assert_eq!(api_client.base_url(), live_url);
+
+        // A transport failure on every peer is surfaced once the retries are exhausted
+        let api_client = AleoAPIClient::<Testnet3>::builder()
+            .peer(&closed_port_url())
+            .retry_policy(RetryPolicy::new(1, Duration::from_millis(10), Duration::from_millis(10)))
+            .build()
+            .unwrap();
+        let error = api_client.latest_height().unwrap_err();
+        assert!(matches!(error.downcast_ref::<ApiError>(), Some(ApiError::Transport { .. })));
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use thiserror::Error;
+
+/// Errors returned by the Aleo API clients
+///
+/// API client methods return an [anyhow::Error] wrapping one of these variants, which can be
+/// recovered with `error.downcast_ref::<ApiError>()` to tell the failures apart.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ApiError {
+    /// The requested resource does not exist on the node
+    #[error("Nothing was found at {url}")]
+    NotFound { url: String },
+    /// The node responded with an unsuccessful status code
+    #[error("Request to {url} failed with status code {code}: {message}")]
+    Status { url: String, code: u16, message: String },
+    /// The request could not be delivered to the node or the response could not be read
+    #[error("Request to {url} failed: {message}")]
+    Transport { url: String, message: String },
+    /// The node's response could not be deserialized into the expected type
+    #[error("Failed to parse the response from {url}: {message}")]
+    Deserialization { url: String, message: String },
+    /// The node refused to accept a broadcast transaction
+    #[error("Transaction was rejected by {url} with status code {code}: {message}")]
+    TransactionRejected { url: String, code: u16, message: String },
+}
+
+impl ApiError {
+    /// Check if an error returned by a [NetworkClient] means the requested resource does not exist
+    pub fn is_not_found(error: &Error) -> bool {
+        matches!(error.downcast_ref::<ApiError>(), Some(ApiError::NotFound { .. }))
+    }
+
+    /// Check if the request may succeed if it is retried or sent to another peer, which is only the
+    /// case for transport failures, rate limiting and gateway or unavailability responses
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Transport { .. } => true,
+            Self::Status { code, .. } => matches!(code, 429 | 502 | 503 | 504),
+            _ => false,
+        }
+    }
+
+    /// Convert an unsuccessful response to a request to the given url
+    ///
+    /// Nodes answer requests for resources they do not have, such as a program that is not
+    /// deployed, with a 500 status and a body like `Something went wrong: Missing program for ID
+    /// hello.aleo` rather than a 404, so such responses are also treated as not found.
+    pub(crate) fn from_status(url: &str, code: u16, message: String) -> Self {
+        let url = url.to_string();
+        let is_missing_resource = code == 500 && {
+            let message = message.to_lowercase();
+            message.starts_with("something went wrong")
+                && ["missing", "not found", "does not exist"].iter().any(|phrase| message.contains(phrase))
+        };
+        if code == 404 || is_missing_resource { Self::NotFound { url } } else { Self::Status { url, code, message } }
+    }
+
+    /// Convert an error returned by a ureq request to the given url
+    pub(crate) fn from_ureq(url: &str, error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(code, response) => {
+                Self::from_status(url, code, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(error) => Self::Transport { url: url.to_string(), message: error.to_string() },
+        }
+    }
+
+    /// Convert an error returned while sending a reqwest request to the given url or reading its
+    /// response
+    #[cfg(feature = "async")]
+    pub(crate) fn from_reqwest(url: &str, error: reqwest::Error) -> Self {
+        let url = url.to_string();
+        if error.is_decode() {
+            Self::Deserialization { url, message: error.to_string() }
+        } else {
+            Self::Transport { url, message: error.to_string() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_not_found_detection() {
+        let not_found = Error::from(ApiError::NotFound { url: "http://localhost:3030/testnet3/program/a.aleo".into() });
+        assert!(ApiError::is_not_found(&not_found));
+
+        let status = Error::from(ApiError::Status {
+            url: "http://localhost:3030/testnet3/program/a.aleo".into(),
+            code: 500,
+            message: "Internal Server Error".into(),
+        });
+        assert!(!ApiError::is_not_found(&status));
+        assert!(!ApiError::is_not_found(&anyhow!("Invalid program ID")));
+    }
+
+    #[test]
+    fn test_api_error_from_node_responses() {
+        let url = "https://vm.aleo.org/api/testnet3/program/missing.aleo";
+
+        // Nodes report missing resources with a 500 status rather than a 404
+        let missing_program =
+            ApiError::from_status(url, 500, "Something went wrong: Missing program for ID missing.aleo".into());
+        assert_eq!(missing_program, ApiError::NotFound { url: url.into() });
+        let missing_transaction =
+            ApiError::from_status(url, 500, "Something went wrong: Missing transaction for ID at1".into());
+        assert_eq!(missing_transaction, ApiError::NotFound { url: url.into() });
+        assert_eq!(ApiError::from_status(url, 404, String::new()), ApiError::NotFound { url: url.into() });
+
+        // Other server errors are not mistaken for missing resources
+        let internal_error = ApiError::from_status(url, 500, "Something went wrong: Invalid block height".into());
+        assert!(matches!(internal_error, ApiError::Status { code: 500, .. }));
+        assert!(!internal_error.is_transient());
+        let unavailable = ApiError::from_status(url, 503, "Service Unavailable".into());
+        assert!(matches!(unavailable, ApiError::Status { code: 503, .. }));
+        assert!(unavailable.is_transient());
+    }
+}
@@ -21,28 +21,52 @@ use super::*;
 pub mod blocking;
 pub use blocking::*;
 
+pub mod builder;
+pub use builder::*;
+
+pub mod error;
+pub use error::*;
+
+pub mod network_client;
+pub use network_client::*;
+
+pub mod scanner;
+pub use scanner::*;
+
+#[cfg(feature = "async")]
+pub mod asynchronous;
+#[cfg(feature = "async")]
+pub use asynchronous::*;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
 /// Aleo API client for interacting with the Aleo Beacon API
+///
+/// Requests are sent to one of the client's peers, moving on to the next peer when the current
+/// one fails. Failed GET requests are retried according to the client's [RetryPolicy]. Clients
+/// with fallback peers, timeouts and retries are created with [AleoAPIClient::builder].
 #[derive(Clone, Debug)]
 pub struct AleoAPIClient<N: Network> {
     client: ureq::Agent,
-    base_url: String,
+    peers: Vec<String>,
+    active_peer: Arc<AtomicUsize>,
     network_id: String,
+    retry_policy: RetryPolicy,
     _network: PhantomData<N>,
 }
 
 impl<N: Network> AleoAPIClient<N> {
+    /// Create a client for a single peer that does not retry failed requests
     pub fn new(base_url: &str, chain: &str) -> Result<Self> {
-        let client = ureq::Agent::new();
-        ensure!(
-            base_url.starts_with("http://") || base_url.starts_with("https://"),
-            "specified url {base_url} invalid, the base url must start with or https:// (or http:// if doing local development)"
-        );
-        Ok(AleoAPIClient {
-            client,
-            base_url: base_url.to_string(),
-            network_id: chain.to_string(),
-            _network: PhantomData,
-        })
+        Self::builder().peer(base_url).network_id(chain).retry_policy(RetryPolicy::none()).build()
+    }
+
+    /// Create a builder to configure the client's peers, timeouts and retry policy
+    pub fn builder() -> AleoAPIClientBuilder<N> {
+        AleoAPIClientBuilder::default()
     }
 
     pub fn testnet3() -> Self {
@@ -53,13 +77,62 @@ impl<N: Network> AleoAPIClient<N> {
         Self::new(&format!("http://0.0.0.0:{}", port), "testnet3").unwrap()
     }
 
-    /// Get base URL
+    /// Get base URL of the peer requests are currently sent to
     pub fn base_url(&self) -> &str {
-        &self.base_url
+        &self.peers[self.active_peer.load(Ordering::Relaxed)]
+    }
+
+    /// Get the base URLs of all peers in the order they are tried
+    pub fn peers(&self) -> &[String] {
+        &self.peers
     }
 
     /// Get network ID being interacted with
     pub fn network_id(&self) -> &str {
         &self.network_id
     }
+
+    /// Get the url of a route on the peer requests are currently sent to
+    fn url(&self, route: &str) -> String {
+        format!("{}/{}/{route}", self.base_url(), self.network_id)
+    }
+
+    /// Perform a GET request for a route and deserialize the json response, failing over to the
+    /// other peers and retrying on transient errors
+    fn get_json<T: serde::de::DeserializeOwned>(&self, route: &str) -> Result<T, ApiError> {
+        let mut retries = 0;
+        loop {
+            let active_peer = self.active_peer.load(Ordering::Relaxed);
+            let mut last_error = None;
+            for offset in 0..self.peers.len() {
+                let peer = (active_peer + offset) % self.peers.len();
+                let url = format!("{}/{}/{route}", self.peers[peer], self.network_id);
+                match self.get_json_from_url(&url) {
+                    Ok(value) => {
+                        self.active_peer.store(peer, Ordering::Relaxed);
+                        return Ok(value);
+                    }
+                    Err(error) if error.is_transient() => last_error = Some(error),
+                    Err(error) => return Err(error),
+                }
+            }
+            let error = last_error.ok_or_else(|| ApiError::Transport {
+                url: route.to_string(),
+                message: "No peers configured".to_string(),
+            })?;
+            if retries >= self.retry_policy.max_retries {
+                return Err(error);
+            }
+            std::thread::sleep(self.retry_policy.backoff(retries));
+            retries += 1;
+        }
+    }
+
+    /// Perform a single GET request and deserialize the json response
+    fn get_json_from_url<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ApiError> {
+        let response = self.client.get(url).call().map_err(|error| ApiError::from_ureq(url, error))?;
+        response
+            .into_json()
+            .map_err(|error| ApiError::Deserialization { url: url.to_string(), message: error.to_string() })
+    }
 }
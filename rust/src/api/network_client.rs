@@ -0,0 +1,419 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Number of blocks below the tip searched for a rejection of a transaction when waiting for it
+pub const REJECTION_SEARCH_WINDOW: u32 = 50;
+
+/// Outcome of waiting for a broadcast transaction to be included in a block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionStatus<N: Network> {
+    /// The transaction was accepted in the block with the given height and hash
+    Confirmed { block_height: u32, block_hash: N::BlockHash },
+    /// The transaction was included in the block with the given height and hash, but failed to
+    /// finalize and only its fee was consumed
+    Rejected { block_height: u32, block_hash: N::BlockHash },
+    /// The transaction was not found in a block before the timeout elapsed
+    TimedOut,
+}
+
+/// Source of chain data used by the [ProgramManager] and [RecordFinder]
+///
+/// The [AleoAPIClient] implements this trait on top of the REST API of an Aleo node. Other
+/// implementations (such as caching proxies, mocks or in-process ledgers) can be given to the
+/// [ProgramManager] and [RecordFinder] in its place. Only the primitive queries need to be
+/// implemented, import resolution and record scanning are provided on top of them.
+#[allow(clippy::type_complexity)]
+pub trait NetworkClient<N: Network> {
+    /// Get the url of the node used to fetch state roots and inclusion proofs when building
+    /// transactions
+    fn base_url(&self) -> &str;
+
+    /// Get the latest block height
+    fn latest_height(&self) -> Result<u32>;
+
+    /// Get the latest block hash
+    fn latest_hash(&self) -> Result<N::BlockHash>;
+
+    /// Get the latest block
+    fn latest_block(&self) -> Result<Block<N>>;
+
+    /// Get the block matching the specific height from the network
+    fn get_block(&self, height: u32) -> Result<Block<N>>;
+
+    /// Get the height of the block with the given hash
+    fn get_height(&self, block_hash: N::BlockHash) -> Result<u32>;
+
+    /// Get a range of blocks from the network (limited 50 blocks at a time)
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>>;
+
+    /// Retrieve a transaction by via its transaction id
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>>;
+
+    /// Get a program from the network by its ID. This method will return an [ApiError::NotFound]
+    /// if it does not exist.
+    fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>>;
+
+    /// Get all mappings associated with a program.
+    fn get_program_mappings(&self, program_id: &ProgramID<N>) -> Result<Vec<Identifier<N>>>;
+
+    /// Get the current value of a mapping given a specific program, mapping name, and mapping key
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Value<N>>;
+
+    /// Returns the hash of the block containing the given transaction
+    fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash>;
+
+    /// Returns the transition ID that contains the given `input ID` or `output ID`.
+    fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID>;
+
+    /// Get the latest global state root
+    fn latest_state_root(&self) -> Result<N::StateRoot>;
+
+    /// Get the inclusion path of a record commitment against the latest global state root
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>>;
+
+    /// Broadcast a deploy or execute transaction to the Aleo network
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String>;
+
+    /// Wait for a broadcast transaction to be included in a block
+    ///
+    /// The ledger is polled every `poll_interval` until the transaction is found or the `timeout`
+    /// elapses. A transaction rejected during finalization is stored on chain under the id of its
+    /// fee transaction, so blocks are also searched for a rejected deployment or execution with the
+    /// given id, from [REJECTION_SEARCH_WINDOW] blocks below the tip at the time of the call
+    /// onwards. A transaction rejected in an earlier block is reported as timed out.
+    fn wait_for_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<TransactionStatus<N>> {
+        let start = Instant::now();
+        let mut next_height = self.latest_height()?.saturating_sub(REJECTION_SEARCH_WINDOW);
+        loop {
+            // Check if the transaction has been accepted and locate the block containing it
+            match self.find_block_hash(transaction_id) {
+                Ok(block_hash) => {
+                    let block_height = self.get_height(block_hash)?;
+                    return Ok(TransactionStatus::Confirmed { block_height, block_hash });
+                }
+                Err(error) if ApiError::is_not_found(&error) => {}
+                Err(error) => return Err(error),
+            }
+
+            // Search the blocks produced since the last poll for a rejection of the transaction
+            let latest_height = self.latest_height()?;
+            while next_height <= latest_height {
+                let end_height = latest_height.saturating_add(1).min(next_height.saturating_add(50));
+                for block in self.get_blocks(next_height, end_height)? {
+                    for confirmed in block.transactions().iter().filter(|confirmed| confirmed.is_rejected()) {
+                        if unconfirmed_transaction_id(confirmed)? == transaction_id {
+                            return Ok(TransactionStatus::Rejected {
+                                block_height: block.height(),
+                                block_hash: block.hash(),
+                            });
+                        }
+                    }
+                }
+                next_height = end_height;
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Ok(TransactionStatus::TimedOut);
+            }
+            std::thread::sleep(poll_interval.min(timeout - elapsed));
+        }
+    }
+
+    /// Resolve imports of a program in a depth-first-search order from program source code
+    fn get_program_imports_from_source(&self, program: &Program<N>) -> Result<IndexMap<ProgramID<N>, Program<N>>> {
+        let mut found_imports = IndexMap::new();
+        for (import_id, _) in program.imports().iter() {
+            let imported_program = self.get_program(import_id)?;
+            let nested_imports = self.get_program_imports_from_source(&imported_program)?;
+            for (id, import) in nested_imports.into_iter() {
+                found_imports.contains_key(&id).then(|| anyhow!("Circular dependency discovered in program imports"));
+                found_imports.insert(id, import);
+            }
+            found_imports.contains_key(import_id).then(|| anyhow!("Circular dependency discovered in program imports"));
+            found_imports.insert(*import_id, imported_program);
+        }
+        Ok(found_imports)
+    }
+
+    /// Scans the ledger for records that match the given view key.
+    ///
+    /// Blocks are fetched concurrently by a [BlockScanner] with its default settings, use the
+    /// scanner directly to configure it, track the progress of the scan or resume it.
+    fn scan(
+        &self,
+        view_key: &ViewKey<N>,
+        block_heights: Range<u32>,
+        max_records: Option<usize>,
+    ) -> Result<Vec<(Field<N>, Record<N, Ciphertext<N>>)>>
+    where
+        Self: Sized + Sync,
+    {
+        let mut cursor = ScanCursor::new(block_heights.start);
+        BlockScanner::new(self).scan(view_key, block_heights.end, &mut cursor, max_records)?;
+        Ok(cursor.records)
+    }
+
+    /// Search for unspent records in the ledger
+    ///
+    /// Blocks are scanned from the start of the range by a [BlockScanner] with its default
+    /// settings, which drops records whose serial numbers appear in later scanned blocks. The scan
+    /// stops once records covering the specified amounts, or adding up to the maximum number of
+    /// microcredits, are found. The node is then asked whether the records found were spent in the
+    /// blocks that were not scanned. Use [BlockScanner::scan_unspent] directly to track the
+    /// progress of the search or resume it.
+    fn get_unspent_records(
+        &self,
+        private_key: &PrivateKey<N>,
+        block_heights: Range<u32>,
+        max_gates: Option<u64>,
+        specified_amounts: Option<&Vec<u64>>,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>>
+    where
+        Self: Sized + Sync,
+    {
+        let search = UnspentSearch::new(&block_heights, max_gates, specified_amounts)?;
+        let latest_height = self.latest_height()?;
+        let scanner = BlockScanner::new(self);
+        let mut cursor = UnspentScanCursor::new(block_heights.start);
+        loop {
+            scanner.scan_unspent(private_key, block_heights.end, &mut cursor, |records| search.is_complete(records))?;
+            if cursor.next_height > latest_height {
+                break;
+            }
+            // Records may have been spent in blocks after the scanned ones, keep scanning if any were
+            let found = cursor.records.len();
+            let mut unspent = Vec::with_capacity(found);
+            for record in std::mem::take(&mut cursor.records) {
+                if is_unspent(self.find_transition_id(record.serial_number))? {
+                    unspent.push(record);
+                }
+            }
+            cursor.records = unspent;
+            if cursor.records.len() == found || cursor.next_height >= block_heights.end {
+                break;
+            }
+        }
+        search.finish(cursor.records)
+    }
+}
+
+/// Check whether a record is unspent from the result of looking up the transition spending its
+/// serial number. Only a missing transition means the record is unspent, other errors are returned
+/// so a failing node does not make spent records look spendable.
+pub(crate) fn is_unspent<T>(spending_transition: Result<T>) -> Result<bool> {
+    match spending_transition {
+        Ok(_) => Ok(false),
+        Err(error) if ApiError::is_not_found(&error) => Ok(true),
+        Err(error) => Err(error),
+    }
+}
+
+/// Get the id a confirmed transaction was broadcast with. Rejected deployments and executions are
+/// stored as fee transactions, so their original id is rebuilt from the rejected contents.
+fn unconfirmed_transaction_id<N: Network>(confirmed: &ConfirmedTransaction<N>) -> Result<N::TransactionID> {
+    match confirmed {
+        ConfirmedTransaction::RejectedDeploy(_, Transaction::Fee(_, fee), rejected) => {
+            let owner = rejected.program_owner().ok_or_else(|| anyhow!("Rejected deployment has no program owner"))?;
+            let deployment = rejected.deployment().ok_or_else(|| anyhow!("Rejected deployment has no deployment"))?;
+            Ok(Transaction::from_deployment(*owner, deployment.clone(), fee.clone())?.id())
+        }
+        ConfirmedTransaction::RejectedExecute(_, Transaction::Fee(_, fee), rejected) => {
+            let execution = rejected.execution().ok_or_else(|| anyhow!("Rejected execution has no execution"))?;
+            Ok(Transaction::from_execution(execution.clone(), Some(fee.clone()))?.id())
+        }
+        _ => Ok(confirmed.transaction().id()),
+    }
+}
+
+impl<N: Network> NetworkClient<N> for AleoAPIClient<N> {
+    fn base_url(&self) -> &str {
+        AleoAPIClient::base_url(self)
+    }
+
+    fn latest_height(&self) -> Result<u32> {
+        AleoAPIClient::latest_height(self)
+    }
+
+    fn latest_hash(&self) -> Result<N::BlockHash> {
+        AleoAPIClient::latest_hash(self)
+    }
+
+    fn latest_block(&self) -> Result<Block<N>> {
+        AleoAPIClient::latest_block(self)
+    }
+
+    fn get_block(&self, height: u32) -> Result<Block<N>> {
+        AleoAPIClient::get_block(self, height)
+    }
+
+    fn get_height(&self, block_hash: N::BlockHash) -> Result<u32> {
+        AleoAPIClient::get_height(self, block_hash)
+    }
+
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
+        AleoAPIClient::get_blocks(self, start_height, end_height)
+    }
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        AleoAPIClient::get_transaction(self, transaction_id)
+    }
+
+    fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
+        AleoAPIClient::get_program(self, *program_id)
+    }
+
+    fn get_program_mappings(&self, program_id: &ProgramID<N>) -> Result<Vec<Identifier<N>>> {
+        AleoAPIClient::get_program_mappings(self, *program_id)
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Value<N>> {
+        AleoAPIClient::get_mapping_value(self, *program_id, *mapping_name, key.clone())
+    }
+
+    fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash> {
+        AleoAPIClient::find_block_hash(self, transaction_id)
+    }
+
+    fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID> {
+        AleoAPIClient::find_transition_id(self, input_or_output_id)
+    }
+
+    fn latest_state_root(&self) -> Result<N::StateRoot> {
+        AleoAPIClient::latest_state_root(self)
+    }
+
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        AleoAPIClient::get_state_path_for_commitment(self, commitment)
+    }
+
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String> {
+        AleoAPIClient::transaction_broadcast(self, transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{random_program, MULTIPLY_IMPORT_PROGRAM, MULTIPLY_PROGRAM, MockClient, RECIPIENT_PRIVATE_KEY},
+        OnChainProgramState,
+        ProgramManager,
+    };
+
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn test_program_manager_with_custom_network_client() {
+        let multiply_program = Program::<Testnet3>::from_str(MULTIPLY_PROGRAM).unwrap();
+        let double_program = Program::<Testnet3>::from_str(MULTIPLY_IMPORT_PROGRAM).unwrap();
+        let mut programs = IndexMap::new();
+        programs.insert(*multiply_program.id(), multiply_program.clone());
+        programs.insert(*double_program.id(), double_program.clone());
+        let client = MockClient { programs };
+
+        // Ensure imports are resolved through the custom client
+        let imports = client.get_program_imports_from_source(&double_program).unwrap();
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports.get(multiply_program.id()).unwrap(), &multiply_program);
+
+        // Ensure the program manager uses the custom client to check on-chain program state
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(client), None).unwrap();
+        let state = program_manager.on_chain_program_state(&double_program).unwrap();
+        assert_eq!(state, OnChainProgramState::Same);
+        let state = program_manager.on_chain_program_state(&random_program()).unwrap();
+        assert_eq!(state, OnChainProgramState::NotDeployed);
+    }
+
+    #[test]
+    fn test_only_missing_spends_are_unspent() {
+        let url = "http://localhost:3030/testnet3/find/transitionID/1field".to_string();
+        assert!(!is_unspent(Ok(())).unwrap());
+        assert!(is_unspent::<()>(Err(ApiError::NotFound { url: url.clone() }.into())).unwrap());
+        let error = ApiError::Status { url: url.clone(), code: 500, message: String::new() };
+        assert!(is_unspent::<()>(Err(error.into())).is_err());
+        assert!(is_unspent::<()>(Err(ApiError::Transport { url, message: "timed out".to_string() }.into())).is_err());
+    }
+
+    #[test]
+    fn test_wait_for_transaction_times_out() {
+        let client = MockClient { programs: IndexMap::new() };
+        let transaction_id = <Testnet3 as Network>::TransactionID::default();
+        let status =
+            client.wait_for_transaction(transaction_id, Duration::from_millis(50), Duration::from_millis(10)).unwrap();
+        assert_eq!(status, TransactionStatus::TimedOut);
+    }
+
+    /// Serve a node at a height of 5 without blocks, answering block hash lookups with the given
+    /// status line and body
+    fn serve_block_hash_lookups(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0u8; 1024];
+                let read = stream.read(&mut request).unwrap_or_default();
+                let request = String::from_utf8_lossy(&request[..read]);
+                let (status, body) = match request.split_whitespace().nth(1).unwrap_or_default() {
+                    path if path.contains("/latest/height") => ("200 OK", "5"),
+                    path if path.contains("/blocks") => ("200 OK", "[]"),
+                    _ => (status, body),
+                };
+                let response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_wait_for_transaction_returns_node_errors() {
+        let transaction_id = <Testnet3 as Network>::TransactionID::default();
+        let (timeout, poll_interval) = (Duration::from_millis(200), Duration::from_millis(10));
+
+        // A transaction the node answers null for is not in a block yet
+        let client = AleoAPIClient::<Testnet3>::new(&serve_block_hash_lookups("200 OK", "null"), "testnet3").unwrap();
+        let status = client.wait_for_transaction(transaction_id, timeout, poll_interval).unwrap();
+        assert_eq!(status, TransactionStatus::TimedOut);
+
+        // Any other failure to look the transaction up is returned instead of timing out
+        let url = serve_block_hash_lookups("500 Internal Server Error", "");
+        let client = AleoAPIClient::<Testnet3>::new(&url, "testnet3").unwrap();
+        let error = client.wait_for_transaction(transaction_id, timeout, poll_interval).unwrap_err();
+        assert!(matches!(error.downcast_ref::<ApiError>(), Some(ApiError::Status { code: 500, .. })));
+    }
+}
@@ -0,0 +1,496 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of blocks that can be fetched in a single request
+pub(crate) const MAX_WINDOW_SIZE: u32 = 50;
+
+/// Progress of a block scan, reported each time a window of blocks has been scanned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Height of the next block to scan, every block of the scan below it has been scanned
+    pub next_height: u32,
+    /// Height the scan stops at (exclusive)
+    pub end_height: u32,
+    /// Number of records found so far
+    pub records_found: usize,
+}
+
+/// Position of a block scan along with the records it has found so far
+///
+/// A cursor can be persisted (it implements [Serialize] and [Deserialize]) and passed back to
+/// [BlockScanner::scan] to resume a scan that was interrupted or to extend it to newer blocks.
+#[allow(clippy::type_complexity)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ScanCursor<N: Network> {
+    /// Height the scan started at
+    pub start_height: u32,
+    /// Height of the next block to scan
+    pub next_height: u32,
+    /// Records owned by the view key found in the scanned blocks, in block order
+    pub records: Vec<(Field<N>, Record<N, Ciphertext<N>>)>,
+}
+
+impl<N: Network> ScanCursor<N> {
+    /// Create a cursor for a scan starting at the given block height
+    pub fn new(start_height: u32) -> Self {
+        Self { start_height, next_height: start_height, records: vec![] }
+    }
+
+    /// Get the height of the last block scanned, if any block has been scanned
+    pub fn last_scanned_height(&self) -> Option<u32> {
+        (self.next_height > self.start_height).then(|| self.next_height - 1)
+    }
+}
+
+/// Scanner that searches a range of blocks for records owned by a view key
+///
+/// Blocks are fetched in windows of up to 50 blocks, with several windows fetched concurrently by
+/// a pool of workers. Windows are committed to the [ScanCursor] in block order, so if a request
+/// fails the cursor holds every record found below its `next_height` and the scan can be resumed
+/// from there.
+///
+/// Basic Usage:
+/// let api_client = AleoAPIClient::<Testnet3>::testnet3();
+/// let scanner = BlockScanner::new(&api_client)
+///     .workers(8)
+///     .on_progress(|progress| println!("Scanned up to block {}", progress.next_height));
+/// let mut cursor = ScanCursor::new(0);
+/// scanner.scan(&view_key, api_client.latest_height()?, &mut cursor, None)?;
+#[allow(clippy::type_complexity)]
+pub struct BlockScanner<'a, N: Network, C: NetworkClient<N> + Sync> {
+    client: &'a C,
+    workers: usize,
+    window_size: u32,
+    on_progress: Option<Box<dyn Fn(&ScanProgress) + Send + Sync + 'a>>,
+    _network: PhantomData<N>,
+}
+
+impl<'a, N: Network, C: NetworkClient<N> + Sync> BlockScanner<'a, N, C> {
+    /// Create a scanner fetching blocks from the given client with 4 workers
+    pub fn new(client: &'a C) -> Self {
+        Self { client, workers: 4, window_size: MAX_WINDOW_SIZE, on_progress: None, _network: PhantomData }
+    }
+
+    /// Set the number of windows of blocks fetched concurrently [default: 4]
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Set the number of blocks fetched in a single request, at most 50 [default: 50]
+    pub fn window_size(mut self, window_size: u32) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// Set a callback invoked with the progress of the scan after each window of blocks is scanned
+    pub fn on_progress(mut self, on_progress: impl Fn(&ScanProgress) + Send + Sync + 'a) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Scan the blocks from the cursor's `next_height` up to (but excluding) `end_height` for
+    /// records owned by the view key, adding them to the cursor as each window is scanned.
+    ///
+    /// If `max_records` is specified the scan stops once the cursor holds at least that many
+    /// records. On error the cursor is left at the first window that could not be scanned.
+    pub fn scan(
+        &self,
+        view_key: &ViewKey<N>,
+        end_height: u32,
+        cursor: &mut ScanCursor<N>,
+        max_records: Option<usize>,
+    ) -> Result<()> {
+        self.check_settings()?;
+
+        // Compute the x-coordinate of the address.
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        let max_records = max_records.unwrap_or(usize::MAX);
+        if cursor.records.len() >= max_records {
+            return Ok(());
+        }
+
+        let ScanCursor { next_height, records, .. } = cursor;
+        self.scan_windows(
+            next_height,
+            end_height,
+            |window| Ok(owned_records(self.client.get_blocks(window.start, window.end)?, view_key, &address_x_coordinate)),
+            |window_records| {
+                records.extend(window_records);
+                Ok((records.len(), records.len() >= max_records))
+            },
+        )
+    }
+
+    /// Scan the blocks from the cursor's `next_height` up to (but excluding) `end_height` for
+    /// unspent records owned by the private key, adding them to the cursor as each window is
+    /// scanned and removing them once their serial number appears in a scanned block.
+    ///
+    /// The scan stops early once `until` returns true for the records of the cursor. Records in the
+    /// cursor may have been spent in blocks after its `next_height`. On error the cursor is left at
+    /// the first window that could not be scanned.
+    pub fn scan_unspent(
+        &self,
+        private_key: &PrivateKey<N>,
+        end_height: u32,
+        cursor: &mut UnspentScanCursor<N>,
+        until: impl Fn(&[FoundRecord<N>]) -> bool,
+    ) -> Result<()> {
+        self.check_settings()?;
+
+        let view_key = ViewKey::try_from(private_key)?;
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+
+        let UnspentScanCursor { next_height, records, .. } = cursor;
+        self.scan_windows(
+            next_height,
+            end_height,
+            |window| {
+                let blocks = self.client.get_blocks(window.start, window.end)?;
+                UnspentWindow::new(blocks, private_key, &view_key, &address_x_coordinate)
+            },
+            |window| {
+                window.commit(records);
+                Ok((records.len(), until(records)))
+            },
+        )
+    }
+
+    /// Ensure the scanner is configured with at least one worker and a valid window size
+    fn check_settings(&self) -> Result<()> {
+        ensure!(self.workers > 0, "At least one worker must be used to scan blocks");
+        ensure!(
+            self.window_size > 0 && self.window_size <= MAX_WINDOW_SIZE,
+            "The window size must be between 1 and {MAX_WINDOW_SIZE} blocks"
+        );
+        Ok(())
+    }
+
+    /// Scan windows of blocks from `next_height` up to `end_height`, fetching the windows of each
+    /// round concurrently with `scan_window` and committing them in block order with `commit`,
+    /// which returns the number of records found so far and whether to stop the scan
+    fn scan_windows<T: Send>(
+        &self,
+        next_height: &mut u32,
+        end_height: u32,
+        scan_window: impl Fn(Range<u32>) -> Result<T> + Sync,
+        mut commit: impl FnMut(T) -> Result<(usize, bool)>,
+    ) -> Result<()> {
+        while *next_height < end_height {
+            // Split the next blocks into a window for each worker
+            let mut windows = vec![];
+            let mut start = *next_height;
+            while windows.len() < self.workers && start < end_height {
+                let end = start.saturating_add(self.window_size).min(end_height);
+                windows.push(start..end);
+                start = end;
+            }
+
+            // Fetch and filter the windows concurrently
+            let scan_window = &scan_window;
+            let results = std::thread::scope(|scope| {
+                let handles =
+                    windows.iter().map(|window| scope.spawn(move || scan_window(window.clone()))).collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|_| Err(anyhow!("A block scanning worker panicked"))))
+                    .collect::<Vec<_>>()
+            });
+
+            // Commit the windows in block order, stopping at the first failure
+            for (window, result) in windows.into_iter().zip(results) {
+                let (records_found, stop) = commit(result?)?;
+                *next_height = window.end;
+                if let Some(on_progress) = &self.on_progress {
+                    on_progress(&ScanProgress { next_height: *next_height, end_height, records_found });
+                }
+                if stop {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An unspent record owned by an account, found by [BlockScanner::scan_unspent] or a [RecordQuery]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FoundRecord<N: Network> {
+    /// Commitment of the record
+    pub commitment: Field<N>,
+    /// Serial number revealed on chain when the record is spent
+    pub serial_number: Field<N>,
+    /// Height of the block the record was created in
+    pub block_height: u32,
+    /// The decrypted record
+    pub record: Record<N, Plaintext<N>>,
+}
+
+/// Position of a scan for unspent records along with the unspent records it has found so far
+///
+/// Like the [ScanCursor], it can be persisted and passed back to [BlockScanner::scan_unspent] to
+/// resume a scan or extend it to newer blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct UnspentScanCursor<N: Network> {
+    /// Height the scan started at
+    pub start_height: u32,
+    /// Height of the next block to scan
+    pub next_height: u32,
+    /// Decrypted records owned by the private key that were not spent in the scanned blocks, in
+    /// block order
+    pub records: Vec<FoundRecord<N>>,
+}
+
+impl<N: Network> UnspentScanCursor<N> {
+    /// Create a cursor for a scan starting at the given block height
+    pub fn new(start_height: u32) -> Self {
+        Self { start_height, next_height: start_height, records: vec![] }
+    }
+}
+
+/// Records owned by a private key and serial numbers revealed in a window of blocks
+pub(crate) struct UnspentWindow<N: Network> {
+    records: Vec<FoundRecord<N>>,
+    serial_numbers: std::collections::HashSet<Field<N>>,
+}
+
+impl<N: Network> UnspentWindow<N> {
+    /// Find the records owned by the private key in the blocks and the serial numbers they reveal
+    pub(crate) fn new(
+        blocks: Vec<Block<N>>,
+        private_key: &PrivateKey<N>,
+        view_key: &ViewKey<N>,
+        address_x_coordinate: &Field<N>,
+    ) -> Result<Self> {
+        let mut window = Self { records: vec![], serial_numbers: Default::default() };
+        for block in blocks {
+            let block_height = block.height();
+            window.serial_numbers.extend(block.serial_numbers().copied());
+            for (commitment, record) in block.into_records() {
+                if record.is_owner_with_address_x_coordinate(view_key, address_x_coordinate) {
+                    window.records.push(FoundRecord {
+                        commitment,
+                        serial_number: Record::<N, Plaintext<N>>::serial_number(*private_key, commitment)?,
+                        block_height,
+                        record: record.decrypt(view_key)?,
+                    });
+                }
+            }
+        }
+        Ok(window)
+    }
+
+    /// Add the records of the window to the records found so far and remove the spent ones.
+    /// Records can be spent in the window they are created in, so spent records are removed last.
+    pub(crate) fn commit(self, records: &mut Vec<FoundRecord<N>>) {
+        records.extend(self.records);
+        records.retain(|found| !self.serial_numbers.contains(&found.serial_number));
+    }
+}
+
+/// Find the records owned by the view key in a window of blocks
+#[allow(clippy::type_complexity)]
+pub(crate) fn owned_records<N: Network>(
+    blocks: Vec<Block<N>>,
+    view_key: &ViewKey<N>,
+    address_x_coordinate: &Field<N>,
+) -> Vec<(Field<N>, Record<N, Ciphertext<N>>)> {
+    blocks
+        .into_iter()
+        .flat_map(|block| block.into_records())
+        .filter(|(_, record)| record.is_owner_with_address_x_coordinate(view_key, address_x_coordinate))
+        .collect()
+}
+
+/// Search for unspent records paying for a set of amounts or adding up to a maximum number of
+/// microcredits, shared by the blocking and asynchronous `get_unspent_records`
+pub(crate) struct UnspentSearch {
+    /// Amounts to find a record for, largest first
+    required_amounts: Vec<u64>,
+    max_microcredits: Option<u64>,
+}
+
+impl UnspentSearch {
+    pub(crate) fn new(
+        block_heights: &Range<u32>,
+        max_microcredits: Option<u64>,
+        specified_amounts: Option<&Vec<u64>>,
+    ) -> Result<Self> {
+        let required_amounts = if let Some(amounts) = specified_amounts {
+            ensure!(!amounts.is_empty(), "If specific amounts are specified, there must be one amount specified");
+            let mut required_amounts = amounts.clone();
+            required_amounts.sort_by(|a, b| b.cmp(a));
+            required_amounts
+        } else {
+            vec![]
+        };
+        ensure!(
+            block_heights.start < block_heights.end,
+            "The start block height must be less than the end block height"
+        );
+        Ok(Self { required_amounts, max_microcredits })
+    }
+
+    /// Check if the records found are enough to stop searching
+    pub(crate) fn is_complete<N: Network>(&self, records: &[FoundRecord<N>]) -> bool {
+        if !self.required_amounts.is_empty() {
+            let mut microcredits = records.iter().map(|found| microcredits(&found.record)).collect::<Vec<_>>();
+            microcredits.sort_by(|a, b| b.cmp(a));
+            microcredits.len() >= self.required_amounts.len()
+                && microcredits.iter().zip(self.required_amounts.iter()).all(|(found, amount)| found >= amount)
+        } else if let Some(max_microcredits) = self.max_microcredits {
+            records.iter().map(|found| microcredits(&found.record)).fold(0u64, u64::saturating_add) >= max_microcredits
+        } else {
+            false
+        }
+    }
+
+    /// Return the records found, which are the largest records paying for the amounts if amounts
+    /// were specified
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn finish<N: Network>(
+        &self,
+        mut records: Vec<FoundRecord<N>>,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>> {
+        if !self.required_amounts.is_empty() {
+            ensure!(
+                self.is_complete(&records),
+                "Could not find enough records with the specified amounts, consider splitting records into smaller amounts"
+            );
+            records.sort_by_key(|found| std::cmp::Reverse(microcredits(&found.record)));
+            records.truncate(self.required_amounts.len());
+        }
+        Ok(records.into_iter().map(|found| (found.commitment, found.record)).collect())
+    }
+}
+
+fn microcredits<N: Network>(record: &Record<N, Plaintext<N>>) -> u64 {
+    record.microcredits().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::BEACON_PRIVATE_KEY, LocalLedger};
+    use snarkvm_console::network::Testnet3;
+
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_block_scanner_matches_sequential_scan() {
+        let private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let local_ledger = LocalLedger::<Testnet3>::new(&private_key).unwrap();
+        for _ in 0..4 {
+            local_ledger.advance_to_next_block().unwrap();
+        }
+        let end_height = local_ledger.latest_height().unwrap() + 1;
+
+        // Scan one block at a time with a single worker
+        let mut sequential = ScanCursor::new(0);
+        BlockScanner::new(&local_ledger)
+            .workers(1)
+            .window_size(1)
+            .scan(&view_key, end_height, &mut sequential, None)
+            .unwrap();
+        assert!(!sequential.records.is_empty());
+        assert_eq!(sequential.next_height, end_height);
+        assert_eq!(sequential.last_scanned_height(), Some(end_height - 1));
+
+        // Scan concurrently, reporting progress after every window
+        let reported_height = Arc::new(AtomicU32::new(0));
+        let progress_height = reported_height.clone();
+        let mut concurrent = ScanCursor::new(0);
+        BlockScanner::new(&local_ledger)
+            .workers(3)
+            .window_size(2)
+            .on_progress(move |progress| progress_height.store(progress.next_height, Ordering::SeqCst))
+            .scan(&view_key, end_height, &mut concurrent, None)
+            .unwrap();
+        assert_eq!(concurrent, sequential);
+        assert_eq!(reported_height.load(Ordering::SeqCst), end_height);
+    }
+
+    #[test]
+    fn test_block_scanner_resumes_from_cursor() {
+        let private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let local_ledger = LocalLedger::<Testnet3>::new(&private_key).unwrap();
+        for _ in 0..3 {
+            local_ledger.advance_to_next_block().unwrap();
+        }
+        let end_height = local_ledger.latest_height().unwrap() + 1;
+        let scanner = BlockScanner::new(&local_ledger).window_size(1);
+
+        let mut full = ScanCursor::new(0);
+        scanner.scan(&view_key, end_height, &mut full, None).unwrap();
+
+        // Scan part of the range, persist the cursor and resume from it
+        let mut partial = ScanCursor::new(0);
+        scanner.scan(&view_key, 2, &mut partial, None).unwrap();
+        assert_eq!(partial.last_scanned_height(), Some(1));
+        let mut resumed: ScanCursor<Testnet3> =
+            serde_json::from_str(&serde_json::to_string(&partial).unwrap()).unwrap();
+        scanner.scan(&view_key, end_height, &mut resumed, None).unwrap();
+        assert_eq!(resumed, full);
+
+        // An invalid configuration is rejected before any blocks are scanned
+        let mut invalid = ScanCursor::new(0);
+        assert!(BlockScanner::new(&local_ledger).workers(0).scan(&view_key, end_height, &mut invalid, None).is_err());
+        assert_eq!(invalid.last_scanned_height(), None);
+    }
+
+    #[test]
+    fn test_block_scanner_finds_unspent_records() {
+        let private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+        let local_ledger = LocalLedger::<Testnet3>::new(&private_key).unwrap();
+        for _ in 0..3 {
+            local_ledger.advance_to_next_block().unwrap();
+        }
+        let end_height = local_ledger.latest_height().unwrap() + 1;
+        let scanner = BlockScanner::new(&local_ledger).window_size(1);
+
+        let mut full = UnspentScanCursor::new(0);
+        scanner.scan_unspent(&private_key, end_height, &mut full, |_| false).unwrap();
+        assert!(!full.records.is_empty());
+        assert_eq!(full.next_height, end_height);
+        let unspent = local_ledger.get_unspent_records(&private_key, 0..end_height, None, None).unwrap();
+        assert_eq!(
+            unspent,
+            full.records.iter().map(|found| (found.commitment, found.record.clone())).collect::<Vec<_>>()
+        );
+
+        // The scan stops as soon as enough records are found and can be resumed from its cursor
+        let mut partial = UnspentScanCursor::new(0);
+        scanner.scan_unspent(&private_key, end_height, &mut partial, |records| !records.is_empty()).unwrap();
+        assert!(partial.next_height < end_height);
+        let mut resumed: UnspentScanCursor<Testnet3> =
+            serde_json::from_str(&serde_json::to_string(&partial).unwrap()).unwrap();
+        scanner.scan_unspent(&private_key, end_height, &mut resumed, |_| false).unwrap();
+        assert_eq!(resumed, full);
+    }
+}
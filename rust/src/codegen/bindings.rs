@@ -0,0 +1,402 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::program::LiteralType;
+use std::{fmt::Write, path::Path};
+
+/// Names that cannot be used for the fields of generated structs
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+    "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+    "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Generator of typed Rust bindings for an Aleo program
+///
+/// The bindings are a module named after the program. It holds a Rust struct for each struct and
+/// record of the program, with the conversions of [aleo_struct](crate::aleo_struct) and
+/// [aleo_record](crate::aleo_record), and a type wrapping a [ProgramManager] with a method per
+/// function. Each function `name` gets a `name` method executing it on the network through
+/// [ProgramManager::execute_program] and a `name_offline` method executing it through
+/// [ProgramManager::execute_program_offline] and decoding its outputs. Records of imported
+/// programs are passed as [Record] plaintexts.
+///
+/// The generated code requires the `full` feature of this crate.
+///
+/// Basic Usage (in build.rs):
+/// let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+/// BindingGenerator::<Testnet3>::from_file("token.aleo")?.write(out_dir.join("token.rs"))?;
+///
+/// Then in the crate:
+/// include!(concat!(env!("OUT_DIR"), "/token.rs"));
+/// let mut token = token::TokenProgram::new(&mut program_manager);
+/// token.transfer_private(record, recipient, 100, 0, fee_record, None)?;
+#[derive(Clone, Debug)]
+pub struct BindingGenerator<N: Network> {
+    program: Program<N>,
+    module_name: String,
+}
+
+impl<N: Network> BindingGenerator<N> {
+    /// Create a generator of the bindings of a program
+    pub fn new(program: Program<N>) -> Self {
+        let module_name = program.id().name().to_string();
+        Self { program, module_name }
+    }
+
+    /// Create a generator of the bindings of the program in a `.aleo` file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Could not read the program at {}: {e}", path.display()))?;
+        Ok(Self::new(Program::from_str(&source)?))
+    }
+
+    /// Set the name of the generated module, which defaults to the name of the program
+    pub fn with_module_name(mut self, module_name: &str) -> Self {
+        self.module_name = module_name.to_string();
+        self
+    }
+
+    /// Generate the source of the bindings
+    pub fn generate(&self) -> Result<String> {
+        let program_id = self.program.id();
+        let program_type = format!("{}Program", to_type_name(&program_id.name().to_string()));
+        ensure!(!RUST_KEYWORDS.contains(&self.module_name.as_str()), "Invalid module name '{}'", self.module_name);
+        ensure!(
+            self.program
+                .structs()
+                .keys()
+                .chain(self.program.records().keys())
+                .all(|name| to_type_name(&name.to_string()) != program_type),
+            "The program declares a struct or record named like its bindings type {program_type}"
+        );
+
+        let mut source = String::new();
+        writeln!(source, "// Rust bindings for {program_id}, generated by aleo-rust. Do not edit by hand.")?;
+        writeln!(source)?;
+        writeln!(source, "/// Typed bindings for {program_id}")?;
+        writeln!(source, "pub mod {} {{", self.module_name)?;
+        writeln!(source, "    #![allow(dead_code, clippy::too_many_arguments, clippy::type_complexity)]")?;
+        writeln!(source)?;
+        writeln!(source, "    /// The ID of the program")?;
+        writeln!(source, "    pub const PROGRAM_ID: &str = \"{program_id}\";")?;
+        writeln!(source)?;
+        writeln!(source, "    /// The source of the program the bindings were generated from")?;
+        writeln!(source, "    pub const PROGRAM: &str = {};", raw_string(&self.program.to_string()))?;
+
+        for (name, struct_) in self.program.structs() {
+            let type_name = to_type_name(&name.to_string());
+            let (declaration, type_name) = match self.struct_needs_network(name)? {
+                true => (format!("{type_name}<N: aleo_rust::Network>"), format!("{type_name}<N>")),
+                false => (type_name.clone(), type_name),
+            };
+            let members = struct_.members().keys().map(|member| member.to_string()).collect::<Vec<_>>();
+            writeln!(source)?;
+            writeln!(source, "    /// The `{name}` struct of {program_id}")?;
+            writeln!(source, "    #[derive(Clone, Debug, PartialEq, Eq)]")?;
+            writeln!(source, "    pub struct {declaration} {{")?;
+            for (member, member_type) in struct_.members() {
+                writeln!(source, "        pub {}: {},", field_name(member)?, self.plaintext_type(member_type)?)?;
+            }
+            writeln!(source, "    }}")?;
+            writeln!(source)?;
+            writeln!(source, "    aleo_rust::aleo_struct!({type_name} {{ {} }});", members.join(", "))?;
+        }
+
+        for (name, record_type) in self.program.records() {
+            let type_name = to_type_name(&name.to_string());
+            let mut entries = vec![];
+            if record_type.owner().is_public() {
+                entries.push("owner: public".to_string());
+            }
+            writeln!(source)?;
+            writeln!(source, "    /// The `{name}` record of {program_id}")?;
+            writeln!(source, "    #[derive(Clone, Debug, PartialEq, Eq)]")?;
+            writeln!(source, "    pub struct {type_name}<N: aleo_rust::Network> {{")?;
+            writeln!(source, "        pub owner: aleo_rust::Address<N>,")?;
+            for (entry, entry_type) in record_type.entries() {
+                let field = field_name(entry)?;
+                ensure!(field != "owner" && field != "nonce", "The record entry '{entry}' of {name} is reserved");
+                let (plaintext_type, visibility) = match entry_type {
+                    EntryType::Constant(plaintext_type) => (plaintext_type, "constant"),
+                    EntryType::Public(plaintext_type) => (plaintext_type, "public"),
+                    EntryType::Private(plaintext_type) => (plaintext_type, "private"),
+                };
+                writeln!(source, "        pub {field}: {},", self.plaintext_type(plaintext_type)?)?;
+                entries.push(format!("{field}: {visibility}"));
+            }
+            writeln!(source, "        pub nonce: aleo_rust::Group<N>,")?;
+            writeln!(source, "    }}")?;
+            writeln!(source)?;
+            writeln!(source, "    aleo_rust::aleo_record!({type_name}<N> {{ {} }});", entries.join(", "))?;
+        }
+
+        writeln!(source)?;
+        writeln!(source, "    /// Typed calls to the functions of {program_id}")?;
+        writeln!(
+            source,
+            "    pub struct {program_type}<'a, N: aleo_rust::Network, C: aleo_rust::NetworkClient<N> = aleo_rust::AleoAPIClient<N>> {{"
+        )?;
+        writeln!(source, "        manager: &'a mut aleo_rust::ProgramManager<N, C>,")?;
+        writeln!(source, "    }}")?;
+        writeln!(source)?;
+        writeln!(
+            source,
+            "    impl<'a, N: aleo_rust::Network, C: aleo_rust::NetworkClient<N>> {program_type}<'a, N, C> {{"
+        )?;
+        writeln!(source, "        /// Call the functions of the program with a program manager")?;
+        writeln!(source, "        pub fn new(manager: &'a mut aleo_rust::ProgramManager<N, C>) -> Self {{")?;
+        writeln!(source, "            Self {{ manager }}")?;
+        writeln!(source, "        }}")?;
+        writeln!(source)?;
+        writeln!(source, "        /// Get the program the bindings were generated from")?;
+        writeln!(source, "        pub fn program() -> aleo_rust::values::__private::Result<aleo_rust::Program<N>> {{")?;
+        writeln!(source, "            <aleo_rust::Program<N> as core::str::FromStr>::from_str(PROGRAM)")?;
+        writeln!(source, "        }}")?;
+
+        let mut methods = vec!["new".to_string(), "program".to_string()];
+        for (name, function) in self.program.functions() {
+            ensure!(
+                !RUST_KEYWORDS.contains(&name.to_string().as_str()),
+                "The function name '{name}' is a Rust keyword"
+            );
+            let offline = format!("{name}_offline");
+            for method in [name.to_string(), offline.clone()] {
+                ensure!(!methods.contains(&method), "The bindings of {program_id} would define {method} twice");
+                methods.push(method);
+            }
+
+            let input_types = function.input_types();
+            let parameters = input_types
+                .iter()
+                .enumerate()
+                .map(|(index, input_type)| Ok(format!("r{index}: {}", self.value_type(input_type)?)))
+                .collect::<Result<Vec<_>>>()?;
+            let inputs = (0..input_types.len())
+                .map(|index| format!("aleo_rust::ToValue::<N>::to_value(&r{index})?"))
+                .collect::<Vec<_>>();
+            let output_types = function
+                .output_types()
+                .iter()
+                .map(|output_type| self.value_type(output_type))
+                .collect::<Result<Vec<_>>>()?;
+            let outputs = (0..output_types.len())
+                .map(|index| format!("aleo_rust::values::decode_value(&outputs, {index})?"))
+                .collect::<Vec<_>>();
+
+            writeln!(source)?;
+            writeln!(
+                source,
+                "        /// Execute the `{name}` function on the network and get the ID of its transaction"
+            )?;
+            writeln!(source, "        pub fn {name}(")?;
+            writeln!(source, "            &mut self,")?;
+            for parameter in &parameters {
+                writeln!(source, "            {parameter},")?;
+            }
+            writeln!(source, "            priority_fee: u64,")?;
+            writeln!(source, "            fee_record: aleo_rust::Record<N, aleo_rust::Plaintext<N>>,")?;
+            writeln!(source, "            password: Option<&str>,")?;
+            writeln!(source, "        ) -> aleo_rust::values::__private::Result<String> {{")?;
+            writeln!(source, "            let inputs: Vec<aleo_rust::Value<N>> = vec![{}];", inputs.join(", "))?;
+            writeln!(
+                source,
+                "            self.manager.execute_program(PROGRAM_ID, \"{name}\", inputs.into_iter(), priority_fee, fee_record, password)"
+            )?;
+            writeln!(source, "        }}")?;
+            writeln!(source)?;
+            writeln!(source, "        /// Execute the `{name}` function offline and decode its outputs")?;
+            writeln!(source, "        pub fn {offline}<A: aleo_rust::Aleo<Network = N>>(")?;
+            writeln!(source, "            &self,")?;
+            writeln!(source, "            private_key: &aleo_rust::PrivateKey<N>,")?;
+            for parameter in &parameters {
+                writeln!(source, "            {parameter},")?;
+            }
+            writeln!(source, "            imports: &[aleo_rust::Program<N>],")?;
+            writeln!(source, "            url: &str,")?;
+            writeln!(
+                source,
+                "        ) -> aleo_rust::values::__private::Result<(aleo_rust::program::OfflineExecution<N>, {})> {{",
+                tuple(&output_types)
+            )?;
+            writeln!(source, "            let inputs: Vec<aleo_rust::Value<N>> = vec![{}];", inputs.join(", "))?;
+            writeln!(source, "            let program = Self::program()?;")?;
+            writeln!(
+                source,
+                "            let execution = self.manager.execute_program_offline::<A>(private_key, &program, \"{name}\", imports, inputs.into_iter(), true, url)?;"
+            )?;
+            if outputs.is_empty() {
+                writeln!(source, "            Ok((execution, ()))")?;
+            } else {
+                writeln!(source, "            let outputs = execution.outputs().unwrap_or_default();")?;
+                writeln!(source, "            let outputs = {};", tuple(&outputs))?;
+                writeln!(source, "            Ok((execution, outputs))")?;
+            }
+            writeln!(source, "        }}")?;
+        }
+        writeln!(source, "    }}")?;
+        writeln!(source, "}}")?;
+        Ok(source)
+    }
+
+    /// Generate the bindings and write them to a file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.generate()?)
+            .map_err(|e| anyhow!("Could not write the bindings to {}: {e}", path.display()))
+    }
+
+    /// Get the Rust type of a struct of the program
+    fn struct_type(&self, name: &Identifier<N>) -> Result<String> {
+        let type_name = to_type_name(&name.to_string());
+        Ok(match self.struct_needs_network(name)? {
+            true => format!("{type_name}<N>"),
+            false => type_name,
+        })
+    }
+
+    /// Check whether a struct of the program holds a network type such as an address
+    fn struct_needs_network(&self, name: &Identifier<N>) -> Result<bool> {
+        for member_type in self.program.get_struct(name)?.members().values() {
+            let needs_network = match member_type {
+                PlaintextType::Literal(literal_type) => {
+                    matches!(
+                        literal_type,
+                        LiteralType::Address | LiteralType::Field | LiteralType::Group | LiteralType::Scalar
+                    )
+                }
+                PlaintextType::Struct(name) => self.struct_needs_network(name)?,
+            };
+            if needs_network {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Get the Rust type of a plaintext type
+    fn plaintext_type(&self, plaintext_type: &PlaintextType<N>) -> Result<String> {
+        Ok(match plaintext_type {
+            PlaintextType::Literal(literal_type) => match literal_type {
+                LiteralType::Address => "aleo_rust::Address<N>",
+                LiteralType::Boolean => "bool",
+                LiteralType::Field => "aleo_rust::Field<N>",
+                LiteralType::Group => "aleo_rust::Group<N>",
+                LiteralType::I8 => "i8",
+                LiteralType::I16 => "i16",
+                LiteralType::I32 => "i32",
+                LiteralType::I64 => "i64",
+                LiteralType::I128 => "i128",
+                LiteralType::U8 => "u8",
+                LiteralType::U16 => "u16",
+                LiteralType::U32 => "u32",
+                LiteralType::U64 => "u64",
+                LiteralType::U128 => "u128",
+                LiteralType::Scalar => "aleo_rust::Scalar<N>",
+                LiteralType::String => "String",
+            }
+            .to_string(),
+            PlaintextType::Struct(name) => self.struct_type(name)?,
+        })
+    }
+
+    /// Get the Rust type of a function input or output
+    fn value_type(&self, value_type: &ValueType<N>) -> Result<String> {
+        match value_type {
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) => self.plaintext_type(plaintext_type),
+            ValueType::Record(name) => Ok(format!("{}<N>", to_type_name(&name.to_string()))),
+            ValueType::ExternalRecord(_) => Ok("aleo_rust::Record<N, aleo_rust::Plaintext<N>>".to_string()),
+        }
+    }
+}
+
+/// Convert a snake case Aleo identifier into an upper camel case Rust type name
+fn to_type_name(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Get the Rust field name of a struct member or record entry
+fn field_name<N: Network>(name: &Identifier<N>) -> Result<String> {
+    let name = name.to_string();
+    ensure!(!RUST_KEYWORDS.contains(&name.as_str()), "The member name '{name}' is a Rust keyword");
+    Ok(name)
+}
+
+/// Write a list of types or values as a tuple, or as the single item of the list
+fn tuple(items: &[String]) -> String {
+    match items {
+        [item] => item.clone(),
+        items => format!("({})", items.join(", ")),
+    }
+}
+
+/// Write a string as a raw string literal
+fn raw_string(string: &str) -> String {
+    let mut hashes = "#".to_string();
+    while string.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{string}\"{hashes}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    const BINDINGS_PROGRAM: &str = include_str!("../../tests/bindings/bindings_test.aleo");
+
+    #[test]
+    fn test_generate_bindings() {
+        let program = Program::<Testnet3>::from_str(BINDINGS_PROGRAM).unwrap();
+        let bindings = BindingGenerator::new(program.clone()).generate().unwrap();
+
+        assert!(bindings.contains("pub mod bindings_test {"));
+        assert!(bindings.contains("pub const PROGRAM_ID: &str = \"bindings_test.aleo\";"));
+        assert!(bindings.contains("pub struct Point {\n        pub x: u32,\n        pub y: u32,\n    }"));
+        assert!(bindings.contains("aleo_rust::aleo_struct!(Point { x, y });"));
+        assert!(bindings.contains("aleo_rust::aleo_struct!(Payment<N> { to, at });"));
+        assert!(bindings.contains("pub struct Payment<N: aleo_rust::Network> {\n        pub to: aleo_rust::Address<N>,\n        pub at: Point,"));
+        assert!(bindings.contains("aleo_rust::aleo_record!(Token<N> { amount: private, origin: public });"));
+        assert!(bindings.contains(
+            "pub fn mint(\n            &mut self,\n            r0: aleo_rust::Address<N>,\n            r1: u64,"
+        ));
+        assert!(bindings.contains("pub fn pay_offline<A: aleo_rust::Aleo<Network = N>>("));
+        assert!(bindings.contains("aleo_rust::program::OfflineExecution<N>, (Payment<N>, u64))"));
+        assert!(bindings.contains("aleo_rust::program::OfflineExecution<N>, Token<N>)"));
+
+        // The embedded source parses back into the program
+        let source = bindings.split("pub const PROGRAM: &str = r#\"").nth(1).unwrap().split("\"#;").next().unwrap();
+        assert_eq!(Program::<Testnet3>::from_str(source).unwrap(), program);
+
+        let renamed = BindingGenerator::new(program).with_module_name("generated").generate().unwrap();
+        assert!(renamed.contains("pub mod generated {"));
+        assert_eq!(to_type_name("my_long_name2"), "MyLongName2");
+        assert_eq!(raw_string("a \"# b"), "r##\"a \"# b\"##");
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use snarkvm_console::program::RegisterType;
+
+/// Version of the JSON schema of program interfaces, increased on any incompatible change
+pub const PROGRAM_INTERFACE_VERSION: u32 = 1;
+
+/// Description of everything a program exposes: its functions with their inputs, outputs and
+/// finalize signature, its closures, mappings, structs, records and imports
+///
+/// The interface serializes to a stable JSON schema versioned by [PROGRAM_INTERFACE_VERSION] so
+/// frontends and code generators can share one description of a program.
+///
+/// Basic Usage:
+/// let interface = ProgramInterface::new(&program);
+/// let json = interface.to_json()?;
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramInterface {
+    /// Version of the schema of the interface
+    pub version: u32,
+    /// ID of the program
+    pub program: String,
+    /// IDs of the programs imported by the program
+    pub imports: Vec<String>,
+    /// Structs declared by the program
+    pub structs: Vec<StructInterface>,
+    /// Records declared by the program
+    pub records: Vec<RecordInterface>,
+    /// Mappings declared by the program
+    pub mappings: Vec<MappingInterface>,
+    /// Closures declared by the program
+    pub closures: Vec<ClosureInterface>,
+    /// Functions declared by the program
+    pub functions: Vec<FunctionInterface>,
+}
+
+/// Kind of a type in a program interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeKind {
+    /// A literal such as `u64` or `address`
+    Literal,
+    /// A struct declared by the program
+    Struct,
+    /// A record declared by the program
+    Record,
+    /// A record declared by an imported program
+    ExternalRecord,
+}
+
+/// Visibility of a value in a program interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueVisibility {
+    Constant,
+    Public,
+    Private,
+}
+
+/// Type of a value in a program interface
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeInterface {
+    /// Kind of the type
+    pub kind: TypeKind,
+    /// Name of the type: the literal type, the name of the struct or record, or the locator of an
+    /// external record such as `credits.aleo/credits`
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Visibility of the value, absent for records and for the values of closures
+    pub visibility: Option<ValueVisibility>,
+}
+
+/// Named member of a struct or entry of a record
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberInterface {
+    /// Name of the member
+    pub name: String,
+    /// Type of the member
+    #[serde(flatten)]
+    pub value_type: TypeInterface,
+}
+
+/// Input of a function, closure or finalize block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputInterface {
+    /// Register the input is stored in, such as `r0`
+    pub register: String,
+    /// Type of the input
+    #[serde(flatten)]
+    pub value_type: TypeInterface,
+}
+
+/// Struct declared by a program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructInterface {
+    /// Name of the struct
+    pub name: String,
+    /// Members of the struct in their declared order
+    pub members: Vec<MemberInterface>,
+}
+
+/// Record declared by a program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordInterface {
+    /// Name of the record
+    pub name: String,
+    /// Visibility of the owner of the record
+    pub owner: ValueVisibility,
+    /// Entries of the record in their declared order
+    pub entries: Vec<MemberInterface>,
+}
+
+/// Mapping declared by a program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingInterface {
+    /// Name of the mapping
+    pub name: String,
+    /// Type of the keys of the mapping
+    pub key: TypeInterface,
+    /// Type of the values of the mapping
+    pub value: TypeInterface,
+}
+
+/// Closure declared by a program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClosureInterface {
+    /// Name of the closure
+    pub name: String,
+    /// Inputs of the closure
+    pub inputs: Vec<InputInterface>,
+    /// Types of the outputs of the closure
+    pub outputs: Vec<TypeInterface>,
+}
+
+/// Finalize block of a function
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizeInterface {
+    /// Inputs of the finalize block
+    pub inputs: Vec<InputInterface>,
+}
+
+/// Function declared by a program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionInterface {
+    /// Name of the function
+    pub name: String,
+    /// Inputs of the function
+    pub inputs: Vec<InputInterface>,
+    /// Types of the outputs of the function
+    pub outputs: Vec<TypeInterface>,
+    /// Finalize block run on chain after the function, if the function has one
+    pub finalize: Option<FinalizeInterface>,
+}
+
+impl ProgramInterface {
+    /// Describe the interface of a program
+    pub fn new<N: Network>(program: &Program<N>) -> Self {
+        let structs = program
+            .structs()
+            .iter()
+            .map(|(name, struct_)| StructInterface {
+                name: name.to_string(),
+                members: struct_
+                    .members()
+                    .iter()
+                    .map(|(member, member_type)| MemberInterface {
+                        name: member.to_string(),
+                        value_type: TypeInterface::plaintext(member_type, None),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let records = program
+            .records()
+            .iter()
+            .map(|(name, record_type)| RecordInterface {
+                name: name.to_string(),
+                owner: if record_type.owner().is_public() { ValueVisibility::Public } else { ValueVisibility::Private },
+                entries: record_type
+                    .entries()
+                    .iter()
+                    .map(|(entry, entry_type)| {
+                        let value_type = match entry_type {
+                            EntryType::Constant(plaintext_type) => {
+                                TypeInterface::plaintext(plaintext_type, Some(ValueVisibility::Constant))
+                            }
+                            EntryType::Public(plaintext_type) => {
+                                TypeInterface::plaintext(plaintext_type, Some(ValueVisibility::Public))
+                            }
+                            EntryType::Private(plaintext_type) => {
+                                TypeInterface::plaintext(plaintext_type, Some(ValueVisibility::Private))
+                            }
+                        };
+                        MemberInterface { name: entry.to_string(), value_type }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mappings = program
+            .mappings()
+            .iter()
+            .map(|(name, mapping)| MappingInterface {
+                name: name.to_string(),
+                key: TypeInterface::plaintext(mapping.key().plaintext_type(), Some(ValueVisibility::Public)),
+                value: TypeInterface::plaintext(mapping.value().plaintext_type(), Some(ValueVisibility::Public)),
+            })
+            .collect();
+
+        let closures = program
+            .closures()
+            .iter()
+            .map(|(name, closure)| ClosureInterface {
+                name: name.to_string(),
+                inputs: closure
+                    .inputs()
+                    .iter()
+                    .map(|input| InputInterface {
+                        register: input.register().to_string(),
+                        value_type: TypeInterface::register(input.register_type()),
+                    })
+                    .collect(),
+                outputs: closure
+                    .outputs()
+                    .iter()
+                    .map(|output| TypeInterface::register(output.register_type()))
+                    .collect(),
+            })
+            .collect();
+
+        let functions = program
+            .functions()
+            .iter()
+            .map(|(name, function)| FunctionInterface {
+                name: name.to_string(),
+                inputs: function
+                    .inputs()
+                    .iter()
+                    .map(|input| InputInterface {
+                        register: input.register().to_string(),
+                        value_type: TypeInterface::value(input.value_type()),
+                    })
+                    .collect(),
+                outputs: function.outputs().iter().map(|output| TypeInterface::value(output.value_type())).collect(),
+                finalize: function.finalize_logic().map(|finalize| FinalizeInterface {
+                    inputs: finalize
+                        .inputs()
+                        .iter()
+                        .map(|input| InputInterface {
+                            register: input.register().to_string(),
+                            value_type: TypeInterface::plaintext(input.plaintext_type(), Some(ValueVisibility::Public)),
+                        })
+                        .collect(),
+                }),
+            })
+            .collect();
+
+        Self {
+            version: PROGRAM_INTERFACE_VERSION,
+            program: program.id().to_string(),
+            imports: program.imports().keys().map(|import| import.to_string()).collect(),
+            structs,
+            records,
+            mappings,
+            closures,
+            functions,
+        }
+    }
+
+    /// Get the interface of a function of the program
+    pub fn function(&self, name: &str) -> Option<&FunctionInterface> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    /// Serialize the interface to pretty printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize an interface from JSON, failing if it was written with another schema version
+    pub fn from_json(json: &str) -> Result<Self> {
+        let interface: Self = serde_json::from_str(json)?;
+        ensure!(
+            interface.version == PROGRAM_INTERFACE_VERSION,
+            "Program interface version {} is not supported, expected version {PROGRAM_INTERFACE_VERSION}",
+            interface.version
+        );
+        Ok(interface)
+    }
+}
+
+impl<N: Network> From<&Program<N>> for ProgramInterface {
+    fn from(program: &Program<N>) -> Self {
+        Self::new(program)
+    }
+}
+
+impl TypeInterface {
+    fn plaintext<N: Network>(plaintext_type: &PlaintextType<N>, visibility: Option<ValueVisibility>) -> Self {
+        let (kind, type_name) = match plaintext_type {
+            PlaintextType::Literal(literal_type) => (TypeKind::Literal, literal_type.to_string()),
+            PlaintextType::Struct(name) => (TypeKind::Struct, name.to_string()),
+        };
+        Self { kind, type_name, visibility }
+    }
+
+    fn value<N: Network>(value_type: &ValueType<N>) -> Self {
+        match value_type {
+            ValueType::Constant(plaintext_type) => Self::plaintext(plaintext_type, Some(ValueVisibility::Constant)),
+            ValueType::Public(plaintext_type) => Self::plaintext(plaintext_type, Some(ValueVisibility::Public)),
+            ValueType::Private(plaintext_type) => Self::plaintext(plaintext_type, Some(ValueVisibility::Private)),
+            ValueType::Record(name) => Self { kind: TypeKind::Record, type_name: name.to_string(), visibility: None },
+            ValueType::ExternalRecord(locator) => {
+                Self { kind: TypeKind::ExternalRecord, type_name: locator.to_string(), visibility: None }
+            }
+        }
+    }
+
+    fn register<N: Network>(register_type: &RegisterType<N>) -> Self {
+        match register_type {
+            RegisterType::Plaintext(plaintext_type) => Self::plaintext(plaintext_type, None),
+            RegisterType::Record(name) => {
+                Self { kind: TypeKind::Record, type_name: name.to_string(), visibility: None }
+            }
+            RegisterType::ExternalRecord(locator) => {
+                Self { kind: TypeKind::ExternalRecord, type_name: locator.to_string(), visibility: None }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    const INTERFACE_PROGRAM: &str = "import credits.aleo;
+
+program interface_test.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+record badge:
+    owner as address.public;
+    level as u8.constant;
+    origin as point.private;
+
+mapping scores:
+    key player as address.public;
+    value points as u64.public;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function score:
+    input r0 as credits.aleo/credits.record;
+    input r1 as u64.public;
+    input r2 as address.public;
+    call twice r1 into r3;
+    output r0 as credits.aleo/credits.record;
+    finalize r2 r3;
+
+finalize score:
+    input r0 as address.public;
+    input r1 as u64.public;
+    set r1 into scores[r0];
+";
+
+    #[test]
+    fn test_program_interface() {
+        let program = Program::<Testnet3>::from_str(INTERFACE_PROGRAM).unwrap();
+        let interface = ProgramInterface::new(&program);
+
+        assert_eq!(interface.program, "interface_test.aleo");
+        assert_eq!(interface.imports, vec!["credits.aleo".to_string()]);
+        assert_eq!(interface.structs[0].members[1].name, "y");
+        assert_eq!(interface.records[0].owner, ValueVisibility::Public);
+        assert_eq!(interface.records[0].entries[0].value_type.visibility, Some(ValueVisibility::Constant));
+        assert_eq!(interface.records[0].entries[1].value_type.kind, TypeKind::Struct);
+        assert_eq!(interface.mappings[0].key.type_name, "address");
+        assert_eq!(interface.closures[0].inputs[0].value_type.visibility, None);
+
+        let function = interface.function("score").unwrap();
+        assert_eq!(function.inputs[0].register, "r0");
+        assert_eq!(function.inputs[0].value_type.kind, TypeKind::ExternalRecord);
+        assert_eq!(function.inputs[0].value_type.type_name, "credits.aleo/credits");
+        assert_eq!(function.outputs.len(), 1);
+        assert_eq!(function.finalize.as_ref().unwrap().inputs[1].value_type.type_name, "u64");
+        assert!(interface.function("missing").is_none());
+
+        // The JSON schema keeps the type of values next to their name or register
+        let json = interface.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], PROGRAM_INTERFACE_VERSION);
+        assert_eq!(
+            value["functions"][0]["inputs"][1],
+            serde_json::json!({ "register": "r1", "kind": "literal", "type": "u64", "visibility": "public" })
+        );
+        assert_eq!(value["records"][0]["entries"][1]["kind"], "struct");
+        assert_eq!(ProgramInterface::from_json(&json).unwrap(), interface);
+
+        let future = json.replace(&format!("\"version\": {PROGRAM_INTERFACE_VERSION}"), "\"version\": 99");
+        assert!(ProgramInterface::from_json(&future).is_err());
+    }
+}
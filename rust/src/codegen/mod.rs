@@ -0,0 +1,31 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Descriptions of the interface of Aleo programs and generation of typed Rust bindings for them
+//!
+//! The [ProgramInterface] describes the functions, closures, mappings, structs, records and imports
+//! of a program and serializes to JSON. The [BindingGenerator] is meant to be run from a build
+//! script. It emits Rust structs for the structs and records of a program and a type with a method
+//! for each of its functions, so that a change to a function signature shows up as a compile error
+//! in the code calling it.
+
+use super::*;
+
+pub mod bindings;
+pub use bindings::*;
+
+pub mod interface;
+pub use interface::*;
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+impl<N: Network> NetworkClient<N> for LocalLedger<N> {
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn latest_height(&self) -> Result<u32> {
+        Ok(self.ledger.latest_height())
+    }
+
+    fn latest_hash(&self) -> Result<N::BlockHash> {
+        Ok(self.ledger.latest_hash())
+    }
+
+    fn latest_block(&self) -> Result<Block<N>> {
+        Ok(self.ledger.latest_block())
+    }
+
+    fn get_block(&self, height: u32) -> Result<Block<N>> {
+        ensure!(self.ledger.contains_block_height(height)?, self.not_found(&format!("block/{height}")));
+        self.ledger.get_block(height)
+    }
+
+    fn get_height(&self, block_hash: N::BlockHash) -> Result<u32> {
+        ensure!(self.ledger.contains_block_hash(&block_hash)?, self.not_found(&format!("height/{block_hash}")));
+        self.ledger.get_height(&block_hash)
+    }
+
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
+        if start_height >= end_height {
+            bail!("Start height must be less than end height");
+        } else if end_height - start_height > 50 {
+            bail!("Cannot request more than 50 blocks at a time");
+        }
+        // Blocks past the tip of the ledger are omitted, as they are by an Aleo node
+        let end_height = end_height.min(self.ledger.latest_height().saturating_add(1));
+        self.ledger.get_blocks(start_height..end_height)
+    }
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        ensure!(
+            self.ledger.contains_transaction_id(&transaction_id)?,
+            self.not_found(&format!("transaction/{transaction_id}"))
+        );
+        self.ledger.get_transaction(transaction_id)
+    }
+
+    fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
+        ensure!(self.ledger.contains_program_id(program_id)?, self.not_found(&format!("program/{program_id}")));
+        self.ledger.get_program(*program_id)
+    }
+
+    fn get_program_mappings(&self, program_id: &ProgramID<N>) -> Result<Vec<Identifier<N>>> {
+        Ok(self.get_program(program_id)?.mappings().keys().cloned().collect())
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Value<N>> {
+        self.ledger
+            .vm()
+            .finalize_store()
+            .get_value_confirmed(program_id, mapping_name, key)?
+            .ok_or_else(|| self.not_found(&format!("program/{program_id}/mapping/{mapping_name}/{key}")).into())
+    }
+
+    fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash> {
+        self.ledger
+            .find_block_hash(&transaction_id)?
+            .ok_or_else(|| self.not_found(&format!("find/blockHash/{transaction_id}")).into())
+    }
+
+    fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID> {
+        let transition_store = self.ledger.vm().transition_store();
+        ensure!(
+            transition_store.contains_input_id(&input_or_output_id)?
+                || transition_store.contains_output_id(&input_or_output_id)?,
+            self.not_found(&format!("find/transitionID/{input_or_output_id}"))
+        );
+        self.ledger.find_transition_id(&input_or_output_id)
+    }
+
+    fn latest_state_root(&self) -> Result<N::StateRoot> {
+        Ok(self.ledger.latest_state_root())
+    }
+
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.ledger.get_state_path_for_commitment(commitment)
+    }
+
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String> {
+        Ok(self.add_transaction(transaction)?.to_string())
+    }
+}
+
+impl<N: Network> LocalLedger<N> {
+    /// Create the error returned when the resource at the given route does not exist
+    fn not_found(&self, route: &str) -> ApiError {
+        ApiError::NotFound { url: format!("{}/testnet3/{route}", self.base_url) }
+    }
+}
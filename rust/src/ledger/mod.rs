@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! In-process Aleo ledger for tests and offline development
+
+use super::*;
+
+pub mod client;
+pub use client::*;
+
+mod server;
+
+use snarkvm::ledger::Ledger;
+use std::{
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+/// In-memory Aleo ledger that stands in for a live node
+///
+/// The ledger is backed by the snarkVM [VM] over a [ConsensusMemory] store. Its genesis block
+/// mints the starting supply of credits to the private key it is created with, and that same key
+/// signs every block produced afterwards. Transactions submitted to the ledger are verified and
+/// included in a new block immediately, so there is no waiting for a block to be produced.
+///
+/// The ledger implements [NetworkClient] so it can be given directly to the [ProgramManager] and
+/// [RecordFinder], and it serves the same REST routes as an Aleo node on a local socket so the
+/// [AleoAPIClient] (and the state queries made while building transactions) can be pointed at it.
+///
+/// The REST server runs on a background thread for the remaining lifetime of the process.
+#[derive(Clone)]
+pub struct LocalLedger<N: Network> {
+    ledger: Arc<Ledger<N, ConsensusMemory<N>>>,
+    private_key: PrivateKey<N>,
+    base_url: String,
+    block_lock: Arc<Mutex<()>>,
+}
+
+impl<N: Network> LocalLedger<N> {
+    /// Create a new ledger funding the given private key and serve it on a random local port
+    pub fn new(private_key: &PrivateKey<N>) -> Result<Self> {
+        Self::bind(private_key, "127.0.0.1:0")
+    }
+
+    /// Create a new ledger funding the given private key and serve it on the specified socket
+    /// address (i.e. "0.0.0.0:3030" to stand in for a local snarkOS beacon node)
+    pub fn bind(private_key: &PrivateKey<N>, socket_address: &str) -> Result<Self> {
+        let rng = &mut rand::thread_rng();
+
+        // Create the genesis block, minting the starting supply to the private key
+        let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+        let genesis = VM::from(store)?.genesis(private_key, rng)?;
+        let ledger = Ledger::<N, ConsensusMemory<N>>::load(genesis, None)?;
+
+        let listener = TcpListener::bind(socket_address)?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+
+        let local_ledger = Self {
+            ledger: Arc::new(ledger),
+            private_key: *private_key,
+            base_url,
+            block_lock: Arc::new(Mutex::new(())),
+        };
+
+        // Record searches cover the blocks below the latest height, so advance past the genesis
+        // block to make the genesis records discoverable
+        local_ledger.advance_to_next_block()?;
+
+        local_ledger.serve(listener);
+        Ok(local_ledger)
+    }
+
+    /// Get the url the ledger's REST routes are served on
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get the underlying snarkVM ledger
+    pub fn ledger(&self) -> &Ledger<N, ConsensusMemory<N>> {
+        &self.ledger
+    }
+
+    /// Produce a new block without any transactions
+    pub fn advance_to_next_block(&self) -> Result<Block<N>> {
+        self.add_block(vec![])
+    }
+
+    /// Verify a transaction and include it in a new block, returning the transaction id
+    pub fn add_transaction(&self, transaction: Transaction<N>) -> Result<N::TransactionID> {
+        let transaction_id = transaction.id();
+        self.ledger.check_transaction_basic(&transaction, None)?;
+        self.add_block(vec![transaction])?;
+        ensure!(
+            self.ledger.contains_transaction_id(&transaction_id)?,
+            "Transaction {transaction_id} was rejected by the local ledger"
+        );
+        Ok(transaction_id)
+    }
+
+    /// Produce, verify and append the next block containing the given transactions
+    fn add_block(&self, transactions: Vec<Transaction<N>>) -> Result<Block<N>> {
+        let _guard = self.block_lock.lock().map_err(|_| anyhow!("The local ledger block lock was poisoned"))?;
+        let block = self.ledger.prepare_advance_to_next_block(
+            &self.private_key,
+            transactions,
+            None,
+            &mut rand::thread_rng(),
+        )?;
+        self.ledger.check_next_block(&block)?;
+        self.ledger.advance_to_next_block(&block)?;
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::setup_local_ledger, TransferType};
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_local_ledger_serves_api_routes() {
+        let (_, local_ledger, _, _) = setup_local_ledger();
+        let api_client = AleoAPIClient::<Testnet3>::new(local_ledger.base_url(), "testnet3").unwrap();
+
+        assert_eq!(api_client.latest_height().unwrap(), 1);
+        let block = local_ledger.advance_to_next_block().unwrap();
+        assert_eq!(api_client.latest_height().unwrap(), 2);
+        assert_eq!(api_client.latest_hash().unwrap(), block.hash());
+        assert_eq!(api_client.get_height(block.hash()).unwrap(), 2);
+        assert_eq!(api_client.get_blocks(0, 3).unwrap().len(), 3);
+
+        let credits = api_client.get_program("credits.aleo").unwrap();
+        assert_eq!(credits.id().to_string(), "credits.aleo");
+        let error = api_client.get_program("nonexistent.aleo").unwrap_err();
+        assert!(ApiError::is_not_found(&error));
+    }
+
+    #[test]
+    fn test_local_ledger_transfer_end_to_end() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+
+        let recipient_private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+        let (amount_record, fee_record) =
+            record_finder.find_amount_and_fee_records(100, 500_000, &private_key).unwrap();
+        program_manager
+            .transfer(
+                100,
+                500_000,
+                recipient_address,
+                TransferType::Private,
+                None,
+                Some(amount_record.clone()),
+                fee_record.clone(),
+            )
+            .unwrap();
+        local_ledger.advance_to_next_block().unwrap();
+
+        // The spent records are recognized from the serial numbers in the searched blocks
+        let latest_height = local_ledger.latest_height().unwrap();
+        let unspent = local_ledger.get_unspent_records(&private_key, 0..latest_height + 1, None, None).unwrap();
+        assert!(!unspent.is_empty());
+        assert!(unspent.iter().all(|(_, record)| record != &amount_record && record != &fee_record));
+
+        let records = RecordFinder::<Testnet3, LocalLedger<Testnet3>>::new(local_ledger)
+            .find_unspent_records_on_chain(None, None, &recipient_private_key)
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].microcredits().unwrap(), 100);
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::Serialize;
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    thread,
+};
+
+/// A parsed HTTP request made to the local ledger
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LedgerRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) query: Option<String>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl LedgerRequest {
+    /// Read a single HTTP/1.1 request from a stream
+    pub(crate) fn read(stream: impl Read) -> Result<Self> {
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().ok_or_else(|| anyhow!("Missing request method"))?.to_string();
+        let target = parts.next().ok_or_else(|| anyhow!("Missing request path"))?;
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(query.to_string())),
+            None => (target.to_string(), None),
+        };
+
+        let mut content_length = 0usize;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header)?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse()?;
+                }
+            }
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+        Ok(Self { method, path, query, body })
+    }
+
+    /// Get the value of a query parameter
+    pub(crate) fn query_param(&self, name: &str) -> Option<&str> {
+        let mut pairs = self.query.as_deref()?.split('&').filter_map(|pair| pair.split_once('='));
+        pairs.find(|(key, _)| *key == name).map(|(_, value)| value)
+    }
+}
+
+impl<N: Network> LocalLedger<N> {
+    /// Serve the ledger's REST routes from the listener on a background thread
+    pub(crate) fn serve(&self, listener: TcpListener) {
+        let local_ledger = self.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let local_ledger = local_ledger.clone();
+                thread::spawn(move || local_ledger.handle_connection(stream));
+            }
+        });
+    }
+
+    /// Answer a single request, replying with a JSON body or an error message
+    fn handle_connection(&self, mut stream: TcpStream) {
+        let (status, body) = match LedgerRequest::read(&mut stream) {
+            Ok(request) => match self.route(&request) {
+                Ok(Some(body)) => ("200 OK", body),
+                Ok(None) => ("404 Not Found", format!("No route found for {} {}", request.method, request.path)),
+                Err(error) if ApiError::is_not_found(&error) => ("404 Not Found", error.to_string()),
+                Err(error) => ("500 Internal Server Error", error.to_string()),
+            },
+            Err(error) => ("400 Bad Request", error.to_string()),
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+
+    /// Dispatch a request to the matching route of the Aleo node REST API
+    fn route(&self, request: &LedgerRequest) -> Result<Option<String>> {
+        let segments = request.path.trim_matches('/').split('/').collect::<Vec<_>>();
+        let response = match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["testnet3", "latest", "height"]) => to_json(&self.latest_height()?),
+            ("GET", ["testnet3", "latest", "hash"]) => to_json(&self.latest_hash()?),
+            ("GET", ["testnet3", "latest", "block"]) => to_json(&self.latest_block()?),
+            ("GET", ["testnet3", "latest", "stateRoot"]) => to_json(&self.ledger.latest_state_root()),
+            ("GET", ["testnet3", "block", height]) => to_json(&self.get_block(height.parse()?)?),
+            ("GET", ["testnet3", "height", block_hash]) => {
+                let block_hash = N::BlockHash::from_str(block_hash).map_err(|_| anyhow!("Invalid block hash"))?;
+                to_json(&self.get_height(block_hash)?)
+            }
+            ("GET", ["testnet3", "blocks"]) => {
+                let start = request.query_param("start").ok_or_else(|| anyhow!("Missing start height"))?;
+                let end = request.query_param("end").ok_or_else(|| anyhow!("Missing end height"))?;
+                to_json(&self.get_blocks(start.parse()?, end.parse()?)?)
+            }
+            ("GET", ["testnet3", "transaction", transaction_id]) => {
+                to_json(&self.get_transaction(parse_transaction_id::<N>(transaction_id)?)?)
+            }
+            ("GET", ["testnet3", "memoryPool", "transactions"]) => to_json(&Vec::<Transaction<N>>::new()),
+            ("GET", ["testnet3", "program", program_id]) => {
+                to_json(&self.get_program(&ProgramID::from_str(program_id)?)?)
+            }
+            ("GET", ["testnet3", "program", program_id, "mappings"]) => {
+                to_json(&self.get_program_mappings(&ProgramID::from_str(program_id)?)?)
+            }
+            ("GET", ["testnet3", "program", program_id, "mapping", mapping_name, key]) => {
+                let program_id = ProgramID::from_str(program_id)?;
+                let mapping_name = Identifier::from_str(mapping_name)?;
+                to_json(&self.get_mapping_value(&program_id, &mapping_name, &Plaintext::from_str(key)?)?)
+            }
+            ("GET", ["testnet3", "statePath", commitment]) => {
+                to_json(&self.ledger.get_state_path_for_commitment(&Field::from_str(commitment)?)?)
+            }
+            ("GET", ["testnet3", "find", "blockHash", transaction_id]) => {
+                to_json(&self.find_block_hash(parse_transaction_id::<N>(transaction_id)?)?)
+            }
+            ("GET", ["testnet3", "find", "transitionID", input_or_output_id]) => {
+                to_json(&self.find_transition_id(Field::from_str(input_or_output_id)?)?)
+            }
+            ("POST", ["testnet3", "transaction", "broadcast"]) => {
+                let transaction = serde_json::from_slice::<Transaction<N>>(&request.body)?;
+                to_json(&self.add_transaction(transaction)?)
+            }
+            _ => return Ok(None),
+        };
+        response.map(Some)
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+fn parse_transaction_id<N: Network>(transaction_id: &str) -> Result<N::TransactionID> {
+    N::TransactionID::from_str(transaction_id).map_err(|_| anyhow!("Invalid transaction ID {transaction_id}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_ledger_request() {
+        let raw = "GET /testnet3/blocks?start=0&end=50 HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n";
+        let request = LedgerRequest::read(raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/testnet3/blocks");
+        assert_eq!(request.query_param("start"), Some("0"));
+        assert_eq!(request.query_param("end"), Some("50"));
+        assert_eq!(request.query_param("step"), None);
+        assert!(request.body.is_empty());
+
+        let raw = "POST /testnet3/transaction/broadcast HTTP/1.1\r\nContent-Length: 4\r\n\r\n{}\r\n";
+        let request = LedgerRequest::read(raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.query, None);
+        assert_eq!(request.body, b"{}\r\n");
+
+        assert!(LedgerRequest::read("".as_bytes()).is_err());
+    }
+}
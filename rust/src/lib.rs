@@ -171,15 +171,91 @@ pub use account::Encryptor;
 
 #[cfg(feature = "full")]
 pub mod api;
+#[cfg(feature = "async")]
+#[doc(inline)]
+pub use api::AsyncAleoAPIClient;
+#[cfg(feature = "full")]
+#[doc(inline)]
+pub use api::{
+    AleoAPIClient,
+    AleoAPIClientBuilder,
+    ApiError,
+    BlockScanner,
+    DEFAULT_CONNECT_TIMEOUT,
+    DEFAULT_READ_TIMEOUT,
+    FoundRecord,
+    NetworkClient,
+    REJECTION_SEARCH_WINDOW,
+    RetryPolicy,
+    ScanCursor,
+    ScanProgress,
+    TransactionStatus,
+    UnspentScanCursor,
+};
+
+#[cfg(feature = "full")]
+pub mod ledger;
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use api::AleoAPIClient;
+pub use ledger::LocalLedger;
 
 #[cfg(feature = "full")]
 pub mod program;
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use program::{OnChainProgramState, ProgramManager, RecordFinder, TransferType};
+pub use program::{
+    BuiltTransaction,
+    CoinSelection,
+    CoinSelector,
+    Comparison,
+    ConsolidationPlan,
+    ConsolidationStep,
+    ExecutionAuthorization,
+    FeeBreakdown,
+    FieldPredicate,
+    FinalizeSimulation,
+    KeyCache,
+    MappingStore,
+    MappingWrite,
+    OnChainProgramState,
+    PlannedRecord,
+    ProgramManager,
+    RecordFinder,
+    RecordQuery,
+    RecordReservations,
+    RecordStore,
+    StoredRecord,
+    TransferType,
+};
+#[cfg(feature = "offline")]
+#[doc(inline)]
+pub use program::StateBundle;
+
+pub mod validation;
+#[doc(inline)]
+pub use validation::{InputError, InputValidator};
+
+pub mod codegen;
+#[doc(inline)]
+pub use codegen::{BindingGenerator, ProgramInterface};
+
+pub mod values;
+#[doc(inline)]
+pub use values::{
+    decode_output,
+    decode_value,
+    record_entry,
+    record_owner,
+    struct_member,
+    FromPlaintext,
+    FromRecord,
+    FromValue,
+    RecordBuilder,
+    StructBuilder,
+    ToPlaintext,
+    ToRecord,
+    ToValue,
+};
 
 #[cfg(test)]
 #[cfg(feature = "full")]
@@ -211,13 +287,14 @@ pub mod snarkvm_types {
             ProgramOwner,
             Record,
             Response,
+            StatePath,
             Value,
             ValueType,
         },
-        types::Field,
+        types::{Field, Group, Scalar},
     };
-    pub use snarkvm_ledger_block::{Block, Deployment, Execution, Transaction};
-    pub use snarkvm_ledger_query::Query;
+    pub use snarkvm_ledger_block::{Block, ConfirmedTransaction, Deployment, Execution, Transaction};
+    pub use snarkvm_ledger_query::{Query, QueryTrait};
     pub use snarkvm_ledger_store::{
         helpers::memory::{BlockMemory, ConsensusMemory},
         BlockStore,
@@ -250,6 +327,7 @@ use std::{
     io::Read,
     ops::{Add, Range},
     path::PathBuf,
+    time::{Duration, Instant},
 };
 use std::{iter::FromIterator, marker::PhantomData, str::FromStr};
 
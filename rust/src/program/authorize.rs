@@ -0,0 +1,183 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::program::Request;
+use snarkvm_ledger_block::Transition;
+use snarkvm_synthesizer::{program::Operand, Authorization};
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Authorize the execution of a program function and the payment of its fee without proving
+    /// them, so the proofs can be delegated to another machine with
+    /// [ProgramManager::prove_authorization].
+    ///
+    /// No keys are synthesized, the ID of the execution the fee pays for is derived by evaluating
+    /// the signed requests. Signing the requests of the calls the function makes still builds the
+    /// circuit of each function called, as snarkVM 0.14.5 only derives them that way.
+    ///
+    /// The fee is the total fee in microcredits, as the cost of the execution is only known once
+    /// it is proven. It must cover the cost of the execution, which can be estimated with
+    /// [ProgramManager::estimate_execution_fee]. To run this function successfully, the program
+    /// must already be deployed on the Aleo Network.
+    pub fn authorize_execution<A: Aleo<Network = N>>(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        function: impl TryInto<Identifier<N>>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<ExecutionAuthorization<N>> {
+        ensure!(fee_record.microcredits()? >= fee, "The fee record does not hold the fee of {fee} microcredits");
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let function_name = function.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        let program = self
+            .api_client()?
+            .get_program(&program_id)
+            .map_err(|_| anyhow!("Program {program_id:?} does not exist on the Aleo Network. Try deploying the program first before executing."))?;
+        ensure!(
+            program.contains_function(&function_name),
+            "Program {program_id:?} does not contain function {function_name:?}, aborting execution"
+        );
+
+        // Sign the requests of the execution
+        let rng = &mut rand::thread_rng();
+        let private_key = self.get_private_key(password)?;
+        let vm = Self::initialize_vm(self.api_client()?, &program, true)?;
+        let authorization = vm.authorize(&private_key, program_id, function_name, inputs, rng)?;
+        let requests = authorization.to_vec_deque().into_iter().collect::<Vec<_>>();
+
+        // The ID of the execution only depends on its transitions, which are known by evaluating
+        // the requests without synthesizing any circuit
+        let (transitions, _) = evaluate_transitions::<N, A>(&vm.process().read(), &requests, 0)?;
+        let execution_id = Execution::from(transitions.into_iter(), Default::default(), None)?.to_execution_id()?;
+
+        // Sign the request paying the fee for the execution
+        let credits_id = ProgramID::from_str("credits.aleo")?;
+        let fee_function = Identifier::from_str("fee")?;
+        let input_types = vm.process().read().get_program(credits_id)?.get_function(&fee_function)?.input_types();
+        let fee_inputs = [
+            Value::Record(fee_record),
+            fee.to_value()?,
+            Value::from(Literal::Field(execution_id)),
+        ];
+        let fee_request = Request::sign(&private_key, credits_id, fee_function, fee_inputs.iter(), &input_types, rng)?;
+
+        ExecutionAuthorization::new(requests, fee_request, execution_id)
+    }
+
+    /// Prove an execution authorized with [ProgramManager::authorize_execution] and assemble its
+    /// transaction without broadcasting it. The programs of the execution and the state of the
+    /// records it spends are fetched with the given network client.
+    pub fn prove_authorization<A: Aleo<Network = N>>(
+        authorization: &ExecutionAuthorization<N>,
+        api_client: &C,
+    ) -> Result<BuiltTransaction<N>> {
+        let rng = &mut rand::thread_rng();
+        let query = Query::<N, BlockMemory<N>>::from(api_client.base_url());
+        let program = api_client.get_program(authorization.program_id())?;
+        let vm = Self::initialize_vm(api_client, &program, true)?;
+
+        // Prove the execution and ensure it is the one the fee was authorized for
+        let locator = Locator::new(*authorization.program_id(), *authorization.function_name());
+        let (_, mut trace) = vm.process().read().execute::<A>(authorization.authorization())?;
+        trace.prepare(query.clone())?;
+        let execution = trace.prove_execution::<A, _>(&locator.to_string(), rng)?;
+        ensure!(
+            execution.to_execution_id()? == authorization.execution_id(),
+            "The execution does not match the execution {} the fee was authorized for",
+            authorization.execution_id()
+        );
+
+        // Ensure the fee covers the cost of the execution and prove it
+        let (minimum_execution_cost, _) = execution_cost(&vm, &execution)?;
+        let fee = authorization.fee()?;
+        ensure!(
+            fee >= minimum_execution_cost,
+            "The authorized fee of {fee} microcredits does not cover the execution cost of {minimum_execution_cost} microcredits"
+        );
+        let (_, mut trace) = vm.process().read().execute::<A>(authorization.fee_authorization())?;
+        trace.prepare(query)?;
+        let fee_transition = trace.prove_fee::<A, _>(rng)?;
+
+        let transaction = Transaction::from_execution(execution, Some(fee_transition))?;
+        BuiltTransaction::new(transaction, fee - minimum_execution_cost)
+    }
+}
+
+/// Evaluate the request at the given index and the requests of the calls it makes into the
+/// transitions an execution of them holds, with the transitions of the calls before that of the
+/// calling function. Returns the transitions along with the index of the first request that is not
+/// a call made by the evaluated request.
+fn evaluate_transitions<N: Network, A: Aleo<Network = N>>(
+    process: &Process<N>,
+    requests: &[Request<N>],
+    index: usize,
+) -> Result<(Vec<Transition<N>>, usize)> {
+    // Evaluating a request consumes the requests of the calls it makes from the authorization
+    let request = &requests[index];
+    let authorization = Authorization::new(&requests[index..]);
+    let response = process.evaluate::<A>(authorization.clone())?;
+    let end = requests.len() - authorization.len();
+
+    let mut transitions = vec![];
+    let mut call = index + 1;
+    while call < end {
+        let (call_transitions, next_call) = evaluate_transitions::<N, A>(process, requests, call)?;
+        transitions.extend(call_transitions);
+        call = next_call;
+    }
+
+    let function = process.get_program(request.program_id())?.get_function(request.function_name())?;
+    let output_registers = function
+        .outputs()
+        .iter()
+        .map(|output| match output.operand() {
+            Operand::Register(register) => Some(register.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    transitions.push(Transition::from(request, &response, None, &function.output_types(), &output_registers)?);
+    Ok((transitions, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::setup_local_ledger;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_execution_proven_from_serialized_authorization() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+        let fee_record = record_finder.find_one_record(&private_key, 500_000).unwrap();
+
+        // Authorize a public transfer and hand it over as json
+        let recipient = Address::try_from(&PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let inputs = [Value::from_str(&recipient.to_string()).unwrap(), Value::from_str("100u64").unwrap()];
+        let authorization = program_manager
+            .authorize_execution::<AleoV0>("credits.aleo", "transfer_public", inputs.iter(), 500_000, fee_record, None)
+            .unwrap();
+        let json = serde_json::to_string(&authorization).unwrap();
+        let authorization = serde_json::from_str::<ExecutionAuthorization<Testnet3>>(&json).unwrap();
+
+        // The prover assembles a transaction paying the authorized fee that the network accepts
+        let transaction = ProgramManager::prove_authorization::<AleoV0>(&authorization, &local_ledger).unwrap();
+        assert_eq!(transaction.fee().total_fee, 500_000);
+        local_ledger.add_transaction(transaction.into_transaction()).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Interval at which the network is polled for the transaction of each consolidation step
+const CONSOLIDATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Records output by an executed consolidation step
+struct StepOutputs<N: Network> {
+    outputs: Vec<Record<N, Plaintext<N>>>,
+    fee_change: Option<Record<N, Plaintext<N>>>,
+}
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Execute the steps of a consolidation plan one after the other, waiting up to `timeout` for
+    /// each step to be confirmed before executing the next one. Joins are executed with the given
+    /// priority fee, which the plan's join fee must cover along with the cost of the execution.
+    /// Returns the record holding the plan's target amount.
+    pub fn execute_consolidation_plan(
+        &self,
+        plan: &ConsolidationPlan<N>,
+        priority_fee: u64,
+        password: Option<&str>,
+        timeout: Duration,
+    ) -> Result<Record<N, Plaintext<N>>> {
+        let view_key = ViewKey::try_from(&self.get_private_key(password)?)?;
+
+        let mut executed = Vec::<StepOutputs<N>>::with_capacity(plan.steps().len());
+        for (step, consolidation_step) in plan.steps().iter().enumerate() {
+            // Create the execution of the step from the records output by the previous steps
+            let transaction = match consolidation_step {
+                ConsolidationStep::Join { first, second, fee_record } => self.build_join(
+                    resolve(&executed, first)?,
+                    resolve(&executed, second)?,
+                    priority_fee,
+                    resolve(&executed, fee_record)?,
+                    password,
+                )?,
+                ConsolidationStep::Split { record, amount } => {
+                    self.build_split(*amount, resolve(&executed, record)?, password)?
+                }
+            }
+            .into_transaction();
+            let outputs = decrypt_outputs(&transaction, &view_key)?;
+
+            // Wait for the step to be confirmed, as the next step spends the records it outputs
+            println!("Executing step {} of {} of the consolidation plan", step + 1, plan.steps().len());
+            let transaction_id = transaction.id();
+            self.broadcast_transaction(transaction)?;
+            match self.wait_for_transaction(transaction_id, timeout, CONSOLIDATION_POLL_INTERVAL)? {
+                TransactionStatus::Confirmed { .. } => executed.push(outputs),
+                TransactionStatus::Rejected { block_height, .. } => {
+                    bail!("Step {} of the consolidation plan was rejected in block {block_height}", step + 1)
+                }
+                TransactionStatus::TimedOut => {
+                    bail!("Step {} of the consolidation plan was not confirmed within {}s", step + 1, timeout.as_secs())
+                }
+            }
+        }
+
+        resolve(&executed, plan.result())
+    }
+}
+
+/// Get the record a step of a consolidation plan refers to from the outputs of the executed steps
+fn resolve<N: Network>(executed: &[StepOutputs<N>], record: &PlannedRecord<N>) -> Result<Record<N, Plaintext<N>>> {
+    let resolved = match record {
+        PlannedRecord::Existing(record) => Some(record),
+        PlannedRecord::Output { step, index } => executed.get(*step).and_then(|outputs| outputs.outputs.get(*index)),
+        PlannedRecord::FeeChange { step } => executed.get(*step).and_then(|outputs| outputs.fee_change.as_ref()),
+    };
+    resolved
+        .cloned()
+        .ok_or_else(|| anyhow!("The consolidation plan refers to a record of a step that was not executed"))
+}
+
+/// Decrypt the records output by the credits.aleo execution of a consolidation step and the change
+/// of its fee record
+fn decrypt_outputs<N: Network>(transaction: &Transaction<N>, view_key: &ViewKey<N>) -> Result<StepOutputs<N>> {
+    let fee_function = Identifier::from_str("fee")?;
+    let mut outputs = StepOutputs { outputs: vec![], fee_change: None };
+    for transition in transaction.transitions() {
+        let records = transition.records().map(|(_, record)| record.decrypt(view_key)).collect::<Result<Vec<_>>>()?;
+        if transition.function_name() == &fee_function {
+            outputs.fee_change = records.into_iter().next();
+        } else {
+            outputs.outputs.extend(records);
+        }
+    }
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ledger::LocalLedger, test_utils::setup_local_ledger};
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_execute_consolidation_plan_chains_steps() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+
+        // Fund a new account with two records that only cover the target together
+        let recipient_private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let recipient = Address::try_from(&recipient_private_key).unwrap();
+        let fee = 5_000_000;
+        for amount in [4_000_000, 5_000_000] {
+            let (amount_record, fee_record) =
+                record_finder.find_amount_and_fee_records(amount, fee, &private_key).unwrap();
+            program_manager
+                .transfer(amount, fee, recipient, TransferType::Private, None, Some(amount_record), fee_record)
+                .unwrap();
+        }
+        local_ledger.advance_to_next_block().unwrap();
+
+        // No other record can pay the fee of the join, so it is split off the largest record first
+        let join_fee = 500_000;
+        let plan = record_finder.plan_consolidation(8_000_000, join_fee, &recipient_private_key).unwrap();
+        assert_eq!(plan.steps().len(), 2);
+        assert!(matches!(plan.steps()[0], ConsolidationStep::Split { amount: 500_000, .. }));
+        assert!(matches!(&plan.steps()[1], ConsolidationStep::Join {
+            first: PlannedRecord::Output { step: 0, index: 1 },
+            fee_record: PlannedRecord::Output { step: 0, index: 0 },
+            ..
+        }));
+
+        // Each step is confirmed in a block of its own before the next one spends its outputs
+        let program_manager = ProgramManager::<Testnet3, LocalLedger<Testnet3>>::new(
+            Some(recipient_private_key),
+            None,
+            Some(local_ledger.clone()),
+            None,
+        )
+        .unwrap();
+        let start_height = local_ledger.latest_height().unwrap();
+        let record =
+            program_manager.execute_consolidation_plan(&plan, join_fee, None, Duration::from_secs(60)).unwrap();
+        assert_eq!(local_ledger.latest_height().unwrap(), start_height + 2);
+        assert_eq!(record.microcredits().unwrap(), 8_500_000);
+
+        // The joined records and the split off fee record are spent, the joined record and the
+        // change of the fee are left
+        let latest_height = local_ledger.latest_height().unwrap();
+        let unspent =
+            local_ledger.get_unspent_records(&recipient_private_key, 0..latest_height + 1, None, None).unwrap();
+        assert_eq!(unspent.len(), 2);
+        assert!(unspent.iter().any(|(_, unspent)| unspent == &record));
+        assert!(unspent.iter().all(|(_, unspent)| unspent.microcredits().unwrap() < 500_000 || unspent == &record));
+    }
+}
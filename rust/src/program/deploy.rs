@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
     /// Deploy a program to the network
     pub fn deploy_program(
         &mut self,
@@ -25,6 +25,146 @@ impl<N: Network> ProgramManager<N> {
         fee_record: Record<N, Plaintext<N>>,
         password: Option<&str>,
     ) -> Result<String> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let transaction = self.build_deployment(program_id, priority_fee, fee_record, password)?;
+
+        println!(
+            "Attempting to broadcast a deploy transaction for program {:?} to node {:?}",
+            program_id,
+            self.api_client().unwrap().base_url()
+        );
+
+        let result = self.broadcast_transaction(transaction.into_transaction());
+
+        // Notify the developer of the result
+        if result.is_ok() {
+            println!("✅ Deployment transaction for {program_id:?} broadcast successfully");
+        } else {
+            println!("❌ Deployment transaction for {program_id:?} failed to broadcast");
+        };
+
+        result
+    }
+
+    /// Deploy a program along with those of its imports that are not deployed on chain yet. The
+    /// imports are deployed in dependency order, each paid for with a record found by the record
+    /// finder, and each deployment is waited for until it is confirmed before the next one is
+    /// built. Deployment is refused if an import deployed on chain differs from the local import.
+    ///
+    /// Returns the response of the network to the broadcast of the deployment of the program
+    /// itself, which is not waited for. Fee records reserved by the record finder are released
+    /// once each deployment has been settled or broadcast.
+    pub fn deploy_program_with_imports<R: NetworkClient<N> + Sync>(
+        &mut self,
+        program_id: impl TryInto<ProgramID<N>>,
+        priority_fee: u64,
+        record_finder: &RecordFinder<N, R>,
+        password: Option<&str>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<String> {
+        let mut plan = self.deployment_plan(program_id)?;
+        let program_id = *plan.pop().ok_or_else(|| anyhow!("❌ The deployment plan is empty"))?.id();
+        let private_key = self.get_private_key(password)?;
+
+        for import in plan {
+            let import_id = *import.id();
+            println!("Deploying import {import_id:?} of {program_id:?}..");
+            let fee_record = record_finder.find_one_record(&private_key, priority_fee)?;
+            let status =
+                self.build_deployment(import_id, priority_fee, fee_record.clone(), password).and_then(|transaction| {
+                    let transaction_id = transaction.id();
+                    self.broadcast_transaction(transaction.into_transaction())?;
+                    self.wait_for_transaction(transaction_id, timeout, poll_interval)
+                });
+            record_finder.release_records(std::slice::from_ref(&fee_record))?;
+            match status? {
+                TransactionStatus::Confirmed { .. } => (),
+                TransactionStatus::Rejected { block_height, .. } => {
+                    bail!("❌ Deployment of import {import_id:?} was rejected in block {block_height}")
+                }
+                TransactionStatus::TimedOut => {
+                    bail!("❌ Deployment of import {import_id:?} was not confirmed within {}s", timeout.as_secs())
+                }
+            }
+        }
+
+        let fee_record = record_finder.find_one_record(&private_key, priority_fee)?;
+        let response = self.deploy_program(program_id, priority_fee, fee_record.clone(), password);
+        record_finder.release_records(std::slice::from_ref(&fee_record))?;
+        response
+    }
+
+    /// Get the programs that must be deployed for a program to be deployed, in the order they must
+    /// be deployed in: imports that are not deployed on chain yet come before the programs that
+    /// import them, and the program itself comes last. Imports are loaded from the program manager,
+    /// the imports directory of the local program directory, or the network, in that order.
+    ///
+    /// Fails if the program is already deployed or if an import deployed on chain differs from the
+    /// local import.
+    pub fn deployment_plan(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<Vec<Program<N>>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let program = self.get_program(program_id).or_else(|_| self.find_program_on_disk(&program_id))?;
+        if self.on_chain_program_state(&program)? != OnChainProgramState::NotDeployed {
+            bail!("❌ Program {program_id:?} already deployed on chain, cancelling deployment");
+        }
+
+        let mut plan = vec![];
+        self.plan_imports(&program, &mut vec![program_id], &mut plan)?;
+        plan.push(program);
+        Ok(plan)
+    }
+
+    /// Add the imports of a program that are not deployed on chain to a deployment plan after their
+    /// own imports, keeping track of the programs whose imports are being planned to detect cycles
+    fn plan_imports(
+        &self,
+        program: &Program<N>,
+        planning: &mut Vec<ProgramID<N>>,
+        plan: &mut Vec<Program<N>>,
+    ) -> Result<()> {
+        for import_id in program.imports().keys() {
+            if plan.iter().any(|planned| planned.id() == import_id) {
+                continue;
+            }
+            ensure!(!planning.contains(import_id), "❌ Program {import_id:?} imports itself through its imports");
+
+            // Use the import in memory if there is one, else look on disk or on the network for it
+            let import = if self.contains_program(import_id)? {
+                self.get_program(import_id)
+            } else {
+                self.find_program(import_id)
+            }
+            .map_err(|_| {
+                anyhow!("❌ Imported program {import_id:?} could not be found locally or on the Aleo Network")
+            })?;
+            match self.on_chain_program_state(&import)? {
+                OnChainProgramState::NotDeployed => {
+                    planning.push(*import_id);
+                    self.plan_imports(&import, planning, plan)?;
+                    planning.pop();
+                    plan.push(import);
+                }
+                OnChainProgramState::Different => {
+                    bail!(
+                        "❌ Imported program {import_id:?} is already deployed on chain and did not match local import"
+                    );
+                }
+                OnChainProgramState::Same => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a deployment transaction for a program without broadcasting it, after running the same
+    /// checks as [ProgramManager::deploy_program]
+    pub fn build_deployment(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        priority_fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<BuiltTransaction<N>> {
         // Ensure a network client is configured, otherwise deployment is not possible
         ensure!(
             self.api_client.is_some(),
@@ -35,11 +175,11 @@ impl<N: Network> ProgramManager<N> {
         let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
 
         // Check if program is already deployed on chain, cancel deployment if so
-        ensure!(
-            self.api_client()?.get_program(program_id).is_err(),
-            "❌ Program {:?} already deployed on chain, cancelling deployment",
-            program_id
-        );
+        match self.api_client()?.get_program(&program_id) {
+            Ok(_) => bail!("❌ Program {:?} already deployed on chain, cancelling deployment", program_id),
+            Err(error) if ApiError::is_not_found(&error) => (),
+            Err(error) => bail!("❌ Could not check if program {program_id:?} is already deployed on chain: {error}"),
+        }
 
         // Get the program if it already exists, otherwise find it
         println!("Loading program {program_id:?}..");
@@ -76,8 +216,8 @@ impl<N: Network> ProgramManager<N> {
             let imported_program_id = imported_program.id();
             match self.on_chain_program_state(&imported_program)? {
                 OnChainProgramState::NotDeployed => {
-                    // For now enforce that users deploy imports individually. In the future, create a more detailed program resolution method for local imports
-                    bail!("❌ Imported program {imported_program_id:?} could not be found on the Aleo Network, please deploy this imported program first before continuing with deployment of {program_id:?}");
+                    // Imports missing on chain are deployed along with the program by deploy_program_with_imports
+                    bail!("❌ Imported program {imported_program_id:?} could not be found on the Aleo Network, please deploy this imported program first or deploy {program_id:?} along with its imports");
                 }
                 OnChainProgramState::Different => {
                     // If the on-chain program is different, cancel deployment
@@ -104,22 +244,7 @@ impl<N: Network> ProgramManager<N> {
             self.api_client()?,
         )?;
 
-        println!(
-            "Attempting to broadcast a deploy transaction for program {:?} to node {:?}",
-            program_id,
-            self.api_client().unwrap().base_url()
-        );
-
-        let result = self.broadcast_transaction(transaction);
-
-        // Notify the developer of the result
-        if result.is_ok() {
-            println!("✅ Deployment transaction for {program_id:?} broadcast successfully");
-        } else {
-            println!("❌ Deployment transaction for {program_id:?} failed to broadcast");
-        };
-
-        result
+        BuiltTransaction::new(transaction, priority_fee)
     }
 
     /// Create a deploy transaction for a program without instantiating the program manager
@@ -129,7 +254,7 @@ impl<N: Network> ProgramManager<N> {
         priority_fee: u64,
         fee_record: Record<N, Plaintext<N>>,
         node_url: String,
-        api_client: &AleoAPIClient<N>,
+        api_client: &C,
     ) -> Result<Transaction<N>> {
         // Initialize an RNG.
         let rng = &mut rand::thread_rng();
@@ -142,6 +267,37 @@ impl<N: Network> ProgramManager<N> {
         vm.deploy(private_key, program, (fee_record, priority_fee), Some(query), rng)
     }
 
+    /// Create a deploy transaction without network access, using a state bundle fetched beforehand
+    /// in place of a query of the network. The bundle must hold the inclusion path of the fee
+    /// record, and the imports of the program must be given in the order they are to be added.
+    #[cfg(feature = "offline")]
+    pub fn create_deploy_transaction_offline<A: Aleo<Network = N>>(
+        program: &Program<N>,
+        imports: &[Program<N>],
+        private_key: &PrivateKey<N>,
+        priority_fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        state: &StateBundle<N>,
+    ) -> Result<Transaction<N>> {
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM and create the deployment
+        let vm = Self::initialize_offline_vm(program, imports, false)?;
+        let deployment = vm.deploy_raw(program, rng)?;
+        ensure!(!deployment.program().functions().is_empty(), "Attempted to create an empty transaction deployment");
+
+        // Pay the cost of the deployment and the priority fee with the fee record
+        let (minimum_deployment_cost, _) = deployment_cost(&deployment)?;
+        let fee = minimum_deployment_cost
+            .checked_add(priority_fee)
+            .ok_or_else(|| anyhow!("Fee overflowed for a deployment transaction"))?;
+        let deployment_id = deployment.to_deployment_id()?;
+        let fee = state.prove_fee::<A>(&vm.process().read(), private_key, fee_record, fee, deployment_id)?;
+
+        let owner = ProgramOwner::new(private_key, deployment_id, rng)?;
+        Transaction::from_deployment(owner, deployment, fee)
+    }
+
     /// Estimate deployment fee for a program in microcredits. The result will be in the form
     /// (total_cost, (storage_cost, namespace_cost))
     ///
@@ -174,120 +330,130 @@ impl<N: Network> ProgramManager<N> {
 mod tests {
     use super::*;
     use crate::{
+        ledger::LocalLedger,
         test_utils::{
             random_program,
             random_program_id,
             setup_directory,
-            transfer_to_test_account,
+            setup_local_ledger,
+            teardown_directory,
             CREDITS_IMPORT_TEST_PROGRAM,
+            FINALIZE_TEST_PROGRAM,
             HELLO_PROGRAM,
             MULTIPLY_IMPORT_PROGRAM,
             MULTIPLY_PROGRAM,
+            MockClient,
+            RECIPIENT_PRIVATE_KEY,
             RECORD_2000000001_MICROCREDITS,
             RECORD_5_MICROCREDITS,
         },
         AleoAPIClient,
-        RecordFinder,
     };
     use snarkvm_console::network::Testnet3;
 
-    use std::{ops::Add, str::FromStr, thread};
+    use std::{ops::Add, str::FromStr};
 
     #[test]
-    #[ignore]
     fn test_deploy() {
-        let recipient_private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
-        let finalize_program = Program::<Testnet3>::from_str(FINALIZE_TEST_PROGRAM).unwrap();
-        let multiply_program = Program::<Testnet3>::from_str(MULTIPLY_PROGRAM).unwrap();
-        let multiply_import_program = Program::<Testnet3>::from_str(MULTIPLY_IMPORT_PROGRAM).unwrap();
-
-        // Wait for the node to bootup
-        thread::sleep(std::time::Duration::from_secs(5));
-        transfer_to_test_account(2000000001, 14, recipient_private_key, "3030").unwrap();
-        let api_client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
-        let record_finder = RecordFinder::<Testnet3>::new(api_client.clone());
+        let (private_key, local_ledger, record_finder, _) = setup_local_ledger();
         let temp_dir = setup_directory("aleo_test_deploy", CREDITS_IMPORT_TEST_PROGRAM, vec![]).unwrap();
-
-        // Ensure that program manager creation fails if no key is provided
-        let mut program_manager =
-            ProgramManager::<Testnet3>::new(Some(recipient_private_key), None, Some(api_client), Some(temp_dir))
-                .unwrap();
-
-        // Wait for the transactions to show up on chain
-        thread::sleep(std::time::Duration::from_secs(30));
+        let mut program_manager = ProgramManager::<Testnet3, LocalLedger<Testnet3>>::new(
+            Some(private_key),
+            None,
+            Some(local_ledger.clone()),
+            Some(temp_dir),
+        )
+        .unwrap();
         let deployment_fee = 200_000_001;
-        let fee_record = record_finder.find_one_record(&recipient_private_key, deployment_fee).unwrap();
-        program_manager.deploy_program("credits_import_test.aleo", deployment_fee, fee_record, None).unwrap();
-
-        // Wait for the program to show up on chain
-        thread::sleep(std::time::Duration::from_secs(45));
-        for _ in 0..4 {
-            let deployed_program = program_manager.api_client().unwrap().get_program("credits_import_test.aleo");
-
-            if deployed_program.is_ok() {
-                assert_eq!(deployed_program.unwrap(), Program::from_str(CREDITS_IMPORT_TEST_PROGRAM).unwrap());
-                break;
-            }
-            println!("Program has not yet appeared on chain, waiting another 15 seconds");
-            thread::sleep(std::time::Duration::from_secs(15));
-        }
-
-        // Deploy a program with a finalize scope
-        program_manager.add_program(&finalize_program).unwrap();
-
-        let fee_record = record_finder.find_one_record(&recipient_private_key, deployment_fee).unwrap();
-        program_manager.deploy_program("finalize_test.aleo", deployment_fee, fee_record, None).unwrap();
-
-        // Wait for the program to show up on chain
-        thread::sleep(std::time::Duration::from_secs(45));
-        for _ in 0..4 {
-            let deployed_program = program_manager.api_client().unwrap().get_program("finalize_test.aleo");
 
-            if deployed_program.is_ok() {
-                assert_eq!(deployed_program.unwrap(), Program::from_str(FINALIZE_TEST_PROGRAM).unwrap());
-                break;
-            }
-            println!("Program has not yet appeared on chain, waiting another 15 seconds");
-            thread::sleep(std::time::Duration::from_secs(15));
+        // Deploy a program found in the local program directory
+        let fee_record = record_finder.find_one_record(&private_key, deployment_fee).unwrap();
+        program_manager.deploy_program("credits_import_test.aleo", deployment_fee, fee_record, None).unwrap();
+        let deployed_program = local_ledger.get_program(&ProgramID::from_str("credits_import_test.aleo").unwrap());
+        assert_eq!(deployed_program.unwrap(), Program::from_str(CREDITS_IMPORT_TEST_PROGRAM).unwrap());
+
+        // Deploy a program with a finalize scope, a program other than credits.aleo to be imported
+        // and a program importing it
+        for program in [FINALIZE_TEST_PROGRAM, MULTIPLY_PROGRAM, MULTIPLY_IMPORT_PROGRAM] {
+            let program = Program::<Testnet3>::from_str(program).unwrap();
+            program_manager.add_program(&program).unwrap();
+
+            let fee_record = record_finder.find_one_record(&private_key, deployment_fee).unwrap();
+            program_manager.deploy_program(*program.id(), deployment_fee, fee_record, None).unwrap();
+            assert_eq!(local_ledger.get_program(program.id()).unwrap(), program);
         }
+    }
 
-        // Deploy a program other than credits.aleo to be imported
+    #[test]
+    fn test_deploy_program_with_imports() {
+        let (_, local_ledger, record_finder, mut program_manager) = setup_local_ledger();
+        let multiply_program = Program::<Testnet3>::from_str(MULTIPLY_PROGRAM).unwrap();
+        let double_program = Program::<Testnet3>::from_str(MULTIPLY_IMPORT_PROGRAM).unwrap();
         program_manager.add_program(&multiply_program).unwrap();
+        program_manager.add_program(&double_program).unwrap();
 
-        let fee_record = record_finder.find_one_record(&recipient_private_key, deployment_fee).unwrap();
-        program_manager.deploy_program("multiply_test.aleo", deployment_fee, fee_record, None).unwrap();
-
-        // Wait for the program to show up on chain
-        thread::sleep(std::time::Duration::from_secs(45));
-        for _ in 0..4 {
-            let deployed_program = program_manager.api_client().unwrap().get_program("multiply_test.aleo");
+        // The missing import is deployed and confirmed before the program importing it
+        let deployment_fee = 200_000_001;
+        let response = program_manager
+            .deploy_program_with_imports(
+                "double_test.aleo",
+                deployment_fee,
+                &record_finder,
+                None,
+                Duration::from_secs(60),
+                Duration::from_millis(100),
+            )
+            .unwrap();
+        assert_eq!(local_ledger.get_program(multiply_program.id()).unwrap(), multiply_program);
+        assert_eq!(local_ledger.get_program(double_program.id()).unwrap(), double_program);
+
+        // Each deployment is included in a block of its own, the program's after the import's
+        let transaction_id = <Testnet3 as Network>::TransactionID::from_str(response.trim().trim_matches('"')).unwrap();
+        let block_hash = local_ledger.find_block_hash(transaction_id).unwrap();
+        assert_eq!(local_ledger.get_height(block_hash).unwrap(), 3);
+        assert_eq!(local_ledger.latest_height().unwrap(), 3);
+    }
 
-            if deployed_program.is_ok() {
-                assert_eq!(deployed_program.unwrap(), Program::from_str(MULTIPLY_PROGRAM).unwrap());
-                break;
-            }
-            println!("Program has not yet appeared on chain, waiting another 15 seconds");
-            thread::sleep(std::time::Duration::from_secs(15));
-        }
+    #[test]
+    fn test_deployment_plan_orders_missing_imports() {
+        let multiply_program = Program::<Testnet3>::from_str(MULTIPLY_PROGRAM).unwrap();
+        let double_program = Program::<Testnet3>::from_str(MULTIPLY_IMPORT_PROGRAM).unwrap();
+        let quadruple_program = "import multiply_test.aleo;
+import double_test.aleo;
+
+program quadruple_test.aleo;
+
+function quadruple:
+    input r0 as u32.private;
+    call double_test.aleo/double_it r0 into r1;
+    call multiply_test.aleo/multiply 2u32 r1 into r2;
+    output r2 as u32.private;
+";
+        let imports = vec![("multiply_test.aleo", MULTIPLY_PROGRAM), ("double_test.aleo", MULTIPLY_IMPORT_PROGRAM)];
+        let directory = setup_directory("aleo_test_deployment_plan", quadruple_program, imports).unwrap();
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let plan = |programs: Vec<&Program<Testnet3>>| {
+            let client =
+                MockClient { programs: programs.into_iter().map(|program| (*program.id(), program.clone())).collect() };
+            ProgramManager::new(Some(private_key), None, Some(client), Some(directory.clone()))
+                .unwrap()
+                .deployment_plan("quadruple_test.aleo")
+                .map(|plan| plan.iter().map(|program| program.id().to_string()).collect::<Vec<_>>())
+        };
 
-        // Deploy a program with imports other than credits.aleo
-        program_manager.add_program(&multiply_import_program).unwrap();
+        // Imports are deployed before the programs importing them, and only once
+        assert_eq!(plan(vec![]).unwrap(), vec!["multiply_test.aleo", "double_test.aleo", "quadruple_test.aleo"]);
 
-        let fee_record = record_finder.find_one_record(&recipient_private_key, deployment_fee).unwrap();
-        program_manager.deploy_program("double_test.aleo", deployment_fee, fee_record, None).unwrap();
+        // Imports already deployed are left out
+        assert_eq!(plan(vec![&multiply_program]).unwrap(), vec!["double_test.aleo", "quadruple_test.aleo"]);
+        assert_eq!(plan(vec![&multiply_program, &double_program]).unwrap(), vec!["quadruple_test.aleo"]);
 
-        // Wait for the program to show up on chain
-        thread::sleep(std::time::Duration::from_secs(45));
-        for _ in 0..4 {
-            let deployed_program = program_manager.api_client().unwrap().get_program("double_test.aleo");
+        // Deployment is refused if an import on chain differs from the local import
+        let different_multiply_program =
+            Program::<Testnet3>::from_str(&MULTIPLY_PROGRAM.replace("mul r0 r1", "add r0 r1")).unwrap();
+        assert!(plan(vec![&different_multiply_program]).is_err());
 
-            if deployed_program.is_ok() {
-                assert_eq!(deployed_program.unwrap(), Program::from_str(MULTIPLY_IMPORT_PROGRAM).unwrap());
-                break;
-            }
-            println!("Program has not yet appeared on chain, waiting another 15 seconds");
-            thread::sleep(std::time::Duration::from_secs(15));
-        }
+        teardown_directory(&directory);
     }
 
     #[test]
@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use snarkvm_console::program::Request;
+use snarkvm_synthesizer::Authorization;
+
+/// Signed requests to execute a program function and pay its fee, which any machine can prove
+///
+/// Signing is cheap while proving is not, so a client can authorize an execution with
+/// [ProgramManager::authorize_execution] and hand the serialized authorization to a stronger
+/// machine that proves it and assembles the transaction with [ProgramManager::prove_authorization].
+/// The fee request is signed over the ID of the execution, so the prover cannot change the
+/// execution or the fee.
+///
+/// An authorization reveals the inputs of the execution along with key material derived from the
+/// signer's view key, so it should only be handed to a trusted prover.
+///
+/// Basic Usage:
+/// let authorization = program_manager.authorize_execution::<AleoV0>("hello.aleo", "hello", inputs, 1_000_000, fee_record, None)?;
+/// std::fs::write("authorization.json", serde_json::to_string(&authorization)?)?;
+///
+/// let authorization: ExecutionAuthorization<Testnet3> = serde_json::from_str(&std::fs::read_to_string("authorization.json")?)?;
+/// let transaction = ProgramManager::prove_authorization::<AleoV0>(&authorization, &api_client)?;
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ExecutionAuthorization<N: Network> {
+    requests: Vec<Request<N>>,
+    fee_request: Request<N>,
+    execution_id: Field<N>,
+}
+
+impl<N: Network> ExecutionAuthorization<N> {
+    /// Create an authorization from the requests of an execution, the request of its fee and the
+    /// ID of the execution the fee request was signed over
+    pub fn new(requests: Vec<Request<N>>, fee_request: Request<N>, execution_id: Field<N>) -> Result<Self> {
+        ensure!(!requests.is_empty(), "An execution authorization must hold at least one request");
+        ensure!(
+            fee_request.program_id().to_string() == "credits.aleo" && fee_request.function_name().to_string() == "fee",
+            "The fee of an execution must be authorized by a request to credits.aleo/fee"
+        );
+        ensure!(
+            fee_request.inputs().get(2) == Some(&Value::from(Literal::Field(execution_id))),
+            "The fee request of the authorization was not signed over execution {execution_id}"
+        );
+        Ok(Self { requests, fee_request, execution_id })
+    }
+
+    /// Get the ID of the program whose function is executed
+    pub fn program_id(&self) -> &ProgramID<N> {
+        self.requests[0].program_id()
+    }
+
+    /// Get the name of the function that is executed
+    pub fn function_name(&self) -> &Identifier<N> {
+        self.requests[0].function_name()
+    }
+
+    /// Get the requests of the execution, the requests of the functions it calls follow the request
+    /// of the executed function
+    pub fn requests(&self) -> &[Request<N>] {
+        &self.requests
+    }
+
+    /// Get the request paying the fee of the execution
+    pub fn fee_request(&self) -> &Request<N> {
+        &self.fee_request
+    }
+
+    /// Get the ID of the execution the fee is paid for
+    pub fn execution_id(&self) -> Field<N> {
+        self.execution_id
+    }
+
+    /// Get the total fee in microcredits the fee request pays
+    pub fn fee(&self) -> Result<u64> {
+        match self.fee_request.inputs().get(1) {
+            Some(Value::Plaintext(Plaintext::Literal(Literal::U64(fee), _))) => Ok(**fee),
+            _ => bail!("The fee request of the authorization does not hold a fee amount"),
+        }
+    }
+
+    /// Get the authorization of the execution
+    pub fn authorization(&self) -> Authorization<N> {
+        Authorization::new(&self.requests)
+    }
+
+    /// Get the authorization of the fee
+    pub fn fee_authorization(&self) -> Authorization<N> {
+        Authorization::new(std::slice::from_ref(&self.fee_request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RECIPIENT_PRIVATE_KEY;
+    use snarkvm_console::network::Testnet3;
+
+    fn sign_request(function: &str, inputs: &[Value<Testnet3>]) -> Request<Testnet3> {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let function_name = Identifier::from_str(function).unwrap();
+        let input_types = Program::<Testnet3>::credits().unwrap().get_function(&function_name).unwrap().input_types();
+        let program_id = ProgramID::from_str("credits.aleo").unwrap();
+        Request::sign(&private_key, program_id, function_name, inputs.iter(), &input_types, &mut rand::thread_rng())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_execution_authorization() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let fee_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: 5000000u64.private, _nonce: 0group.public }}"
+        ))
+        .unwrap();
+        let execution_id = Field::from_str("1field").unwrap();
+        let transfer = sign_request(
+            "transfer_public",
+            &[Value::from_str(&address.to_string()).unwrap(), Value::from_str("100u64").unwrap()],
+        );
+        let fee = sign_request(
+            "fee",
+            &[
+                Value::Record(fee_record),
+                Value::from_str("300000u64").unwrap(),
+                Value::from(Literal::Field(execution_id)),
+            ],
+        );
+
+        let authorization = ExecutionAuthorization::new(vec![transfer.clone()], fee.clone(), execution_id).unwrap();
+        assert_eq!(authorization.program_id().to_string(), "credits.aleo");
+        assert_eq!(authorization.function_name().to_string(), "transfer_public");
+        assert_eq!(authorization.fee().unwrap(), 300_000);
+        assert_eq!(authorization.authorization().len(), 1);
+        let json = serde_json::to_string(&authorization).unwrap();
+        assert_eq!(serde_json::from_str::<ExecutionAuthorization<Testnet3>>(&json).unwrap(), authorization);
+
+        // The fee must be paid by a fee request signed over the execution
+        assert!(ExecutionAuthorization::new(vec![transfer.clone()], fee.clone(), Field::from_str("2field").unwrap())
+            .is_err());
+        assert!(ExecutionAuthorization::new(vec![transfer.clone()], transfer, execution_id).is_err());
+        assert!(ExecutionAuthorization::new(vec![], fee, execution_id).is_err());
+    }
+}
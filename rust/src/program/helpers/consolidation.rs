@@ -0,0 +1,221 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// A credits record used by a step of a [ConsolidationPlan]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlannedRecord<N: Network> {
+    /// An unspent record the account already owns
+    Existing(Record<N, Plaintext<N>>),
+    /// A record output by an earlier step. A join outputs a single record, a split outputs the
+    /// split amount first and the rest second.
+    Output { step: usize, index: usize },
+    /// The change of the fee record paid by an earlier join
+    FeeChange { step: usize },
+}
+
+/// A credits.aleo execution of a [ConsolidationPlan]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsolidationStep<N: Network> {
+    /// Join two records into one, paying the fee with the fee record
+    Join { first: PlannedRecord<N>, second: PlannedRecord<N>, fee_record: PlannedRecord<N> },
+    /// Split an amount off a record, splits do not pay a fee
+    Split { record: PlannedRecord<N>, amount: u64 },
+}
+
+/// Sequence of credits.aleo `join` and `split` executions producing a single record holding at
+/// least a target amount of microcredits out of smaller records
+///
+/// Each step spends records output by the steps before it, so every step must be confirmed on
+/// chain before the next one is executed. [ProgramManager::execute_consolidation_plan] executes
+/// a plan this way.
+///
+/// Basic Usage:
+/// let records = record_finder.find_unspent_records_on_chain(None, None, &private_key)?;
+/// let plan = ConsolidationPlan::new(5_000_000, 1_000_000, &records)?;
+/// let record = program_manager.execute_consolidation_plan(&plan, 0, None, Duration::from_secs(300))?;
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsolidationPlan<N: Network> {
+    target: u64,
+    join_fee: u64,
+    steps: Vec<ConsolidationStep<N>>,
+    result: PlannedRecord<N>,
+}
+
+impl<N: Network> ConsolidationPlan<N> {
+    /// Plan how to produce a record holding at least the target amount from the given records.
+    /// `join_fee` is the fee in microcredits each join is paid with, which must cover the cost of
+    /// the execution along with any priority fee.
+    ///
+    /// As few records as possible are joined. The fees of the joins are paid by another record if
+    /// one holds enough, otherwise they are split off one of the joined records.
+    pub fn new(target: u64, join_fee: u64, records: &[Record<N, Plaintext<N>>]) -> Result<Self> {
+        ensure!(target > 0, "The target amount of a consolidation must be greater than 0 microcredits");
+        ensure!(join_fee > 0, "The fee paid by each join must be greater than 0 microcredits");
+        let mut records =
+            records.iter().filter_map(|record| Some((record.microcredits().ok()?, record.clone()))).collect::<Vec<_>>();
+        records.sort_by_key(|(microcredits, _)| std::cmp::Reverse(*microcredits));
+
+        // If a record already covers the target no steps are needed
+        if let Some((_, record)) = records.iter().rev().find(|(microcredits, _)| *microcredits >= target) {
+            let result = PlannedRecord::Existing(record.clone());
+            return Ok(Self { target, join_fee, steps: vec![], result });
+        }
+
+        // Join the fewest of the largest records that add up to the target
+        let mut joined = 0u64;
+        for count in 2..=records.len() {
+            joined = joined.saturating_add(records[count - 2].0);
+            let total = joined.saturating_add(records[count - 1].0);
+            if total < target {
+                continue;
+            }
+            let fees = join_fee.saturating_mul(count as u64 - 1);
+
+            // Pay the fees with the smallest of the remaining records holding enough
+            let fee_record = records[count..].iter().rev().find(|(microcredits, _)| *microcredits >= fees);
+            if let Some((_, fee_record)) = fee_record {
+                let joined = records[..count].iter().map(|(_, record)| PlannedRecord::Existing(record.clone()));
+                return Ok(Self::join(target, join_fee, vec![], joined, PlannedRecord::Existing(fee_record.clone())));
+            }
+
+            // Otherwise split the fees off the largest joined record if the rest still covers the target
+            if total.saturating_sub(fees) >= target && records[0].0 > fees {
+                let split =
+                    ConsolidationStep::Split { record: PlannedRecord::Existing(records[0].1.clone()), amount: fees };
+                let joined = std::iter::once(PlannedRecord::Output { step: 0, index: 1 })
+                    .chain(records[1..count].iter().map(|(_, record)| PlannedRecord::Existing(record.clone())));
+                let fee_record = PlannedRecord::Output { step: 0, index: 0 };
+                return Ok(Self::join(target, join_fee, vec![split], joined, fee_record));
+            }
+        }
+        bail!("Insufficient funds, the unspent records cannot be joined into a record of {target} microcredits")
+    }
+
+    /// Build a plan joining the records one after the other, each join paying its fee with the
+    /// change of the fee record paid by the previous join
+    fn join(
+        target: u64,
+        join_fee: u64,
+        mut steps: Vec<ConsolidationStep<N>>,
+        mut joined: impl Iterator<Item = PlannedRecord<N>>,
+        mut fee_record: PlannedRecord<N>,
+    ) -> Self {
+        let mut result = joined.next().expect("At least two records are joined");
+        for second in joined {
+            let step = steps.len();
+            steps.push(ConsolidationStep::Join { first: result, second, fee_record });
+            result = PlannedRecord::Output { step, index: 0 };
+            fee_record = PlannedRecord::FeeChange { step };
+        }
+        Self { target, join_fee, steps, result }
+    }
+
+    /// Get the amount of microcredits the resulting record holds at least
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+
+    /// Get the fee in microcredits each join is paid with
+    pub fn join_fee(&self) -> u64 {
+        self.join_fee
+    }
+
+    /// Get the steps of the plan in the order they must be executed
+    pub fn steps(&self) -> &[ConsolidationStep<N>] {
+        &self.steps
+    }
+
+    /// Get the record holding the target amount once every step has been executed
+    pub fn result(&self) -> &PlannedRecord<N> {
+        &self.result
+    }
+
+    /// Get the total fee in microcredits paid by the joins of the plan
+    pub fn total_fee(&self) -> u64 {
+        let joins = self.steps.iter().filter(|step| matches!(step, ConsolidationStep::Join { .. })).count();
+        self.join_fee.saturating_mul(joins as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::credits_records;
+
+    #[test]
+    fn test_plan_joins_largest_records_and_pays_fees_with_another_record() {
+        let records = credits_records(&[100, 3_000, 4_000, 5_000]);
+
+        // A record covering the target needs no steps
+        let plan = ConsolidationPlan::new(3_500, 50, &records).unwrap();
+        assert!(plan.steps().is_empty());
+        assert_eq!(plan.result(), &PlannedRecord::Existing(records[2].clone()));
+
+        // The two largest records are joined and the smallest remaining record pays the fee
+        let plan = ConsolidationPlan::new(9_000, 50, &records).unwrap();
+        assert_eq!(
+            plan.steps(),
+            &[ConsolidationStep::Join {
+                first: PlannedRecord::Existing(records[3].clone()),
+                second: PlannedRecord::Existing(records[2].clone()),
+                fee_record: PlannedRecord::Existing(records[0].clone()),
+            }]
+        );
+        assert_eq!(plan.result(), &PlannedRecord::Output { step: 0, index: 0 });
+        assert_eq!(plan.total_fee(), 50);
+
+        // Each further join pays its fee with the change of the previous one
+        let plan = ConsolidationPlan::new(11_000, 50, &records).unwrap();
+        assert_eq!(plan.steps().len(), 2);
+        assert_eq!(
+            plan.steps()[1],
+            ConsolidationStep::Join {
+                first: PlannedRecord::Output { step: 0, index: 0 },
+                second: PlannedRecord::Existing(records[1].clone()),
+                fee_record: PlannedRecord::FeeChange { step: 0 },
+            }
+        );
+        assert_eq!(plan.result(), &PlannedRecord::Output { step: 1, index: 0 });
+    }
+
+    #[test]
+    fn test_plan_splits_fees_off_a_joined_record() {
+        let records = credits_records(&[4_000, 5_000]);
+
+        // No other record can pay the fee, so it is split off the largest record first
+        let plan = ConsolidationPlan::new(8_000, 500, &records).unwrap();
+        assert_eq!(
+            plan.steps(),
+            &[
+                ConsolidationStep::Split { record: PlannedRecord::Existing(records[1].clone()), amount: 500 },
+                ConsolidationStep::Join {
+                    first: PlannedRecord::Output { step: 0, index: 1 },
+                    second: PlannedRecord::Existing(records[0].clone()),
+                    fee_record: PlannedRecord::Output { step: 0, index: 0 },
+                },
+            ]
+        );
+        assert_eq!(plan.total_fee(), 500);
+
+        // The records cannot cover both the target and the fee
+        assert!(ConsolidationPlan::new(8_600, 500, &records).is_err());
+        assert!(ConsolidationPlan::new(10_000, 500, &records).is_err());
+        assert!(ConsolidationPlan::new(8_000, 0, &records).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::utilities::ToBits;
+use snarkvm_console::prelude::FromBytes;
+use std::{fs, path::Path};
+
+/// Directory of the proving and verifying keys synthesized for the functions of programs
+///
+/// Synthesizing the keys of a function is the most expensive part of executing it for the first
+/// time. The key cache keeps them on disk between runs, keyed by a checksum of the program and the
+/// name of the function, so a program that changes under the same ID never reuses stale keys.
+///
+/// Keys are stored as `<directory>/<program id>/<checksum>/<function>.prover` and `.verifier`.
+#[derive(Clone, Debug)]
+pub struct KeyCache {
+    directory: PathBuf,
+}
+
+impl KeyCache {
+    /// Open the key cache in the given directory, creating the directory if it does not exist
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)
+            .map_err(|e| anyhow!("Failed to create the key cache at {}: {e}", directory.display()))?;
+        Ok(Self { directory })
+    }
+
+    /// Get the directory the keys are stored in
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Get the checksum of a program the keys of its functions are stored under
+    pub fn checksum<N: Network>(program: &Program<N>) -> Result<Field<N>> {
+        N::hash_bhp1024(&program.to_bytes_le()?.to_bits_le())
+    }
+
+    /// Load the cached keys of the functions of a program and its imports into a process the
+    /// programs were added to. Functions whose keys are already in the process or are not cached
+    /// are skipped. Returns the number of functions whose keys were loaded.
+    pub fn load<N: Network>(&self, process: &Process<N>, program: &Program<N>) -> Result<usize> {
+        Self::programs(process, program)?
+            .into_iter()
+            .try_fold(0, |loaded, program| Ok(loaded + self.load_program(process, program)?))
+    }
+
+    /// Store the keys synthesized in a process for the functions of a program and its imports.
+    /// Functions without synthesized keys or whose keys are already cached are skipped. Returns the
+    /// number of functions whose keys were stored.
+    pub fn store<N: Network>(&self, process: &Process<N>, program: &Program<N>) -> Result<usize> {
+        Self::programs(process, program)?
+            .into_iter()
+            .try_fold(0, |stored, program| Ok(stored + self.store_program(process, program)?))
+    }
+
+    /// Get a program and its imports, except credits.aleo whose keys are loaded from the network
+    /// parameters
+    fn programs<'a, N: Network>(process: &'a Process<N>, program: &'a Program<N>) -> Result<Vec<&'a Program<N>>> {
+        let credits_id = ProgramID::<N>::from_str("credits.aleo")?;
+        let mut programs = vec![program];
+        let mut pending = program.imports().keys().copied().collect::<Vec<_>>();
+        while let Some(program_id) = pending.pop() {
+            if program_id == credits_id || programs.iter().any(|program| program.id() == &program_id) {
+                continue;
+            }
+            let import = process.get_program(program_id)?;
+            pending.extend(import.imports().keys().copied());
+            programs.push(import);
+        }
+        Ok(programs)
+    }
+
+    /// Load the cached keys of the functions of a single program
+    fn load_program<N: Network>(&self, process: &Process<N>, program: &Program<N>) -> Result<usize> {
+        let program_id = program.id();
+        let stack = process.get_stack(program_id)?;
+        let program_directory = self.program_directory(program)?;
+
+        let mut loaded = 0;
+        for function_name in program.functions().keys() {
+            if stack.contains_proving_key(function_name) && stack.contains_verifying_key(function_name) {
+                continue;
+            }
+            let (prover_path, verifier_path) = Self::key_paths(&program_directory, function_name);
+            if !prover_path.exists() || !verifier_path.exists() {
+                continue;
+            }
+            // Keys that cannot be read are a cache miss, and are removed so they are stored again once
+            // they are synthesized
+            let (Some(proving_key), Some(verifying_key)) =
+                (Self::read::<ProvingKey<N>>(&prover_path), Self::read::<VerifyingKey<N>>(&verifier_path))
+            else {
+                let _ = fs::remove_file(&prover_path);
+                let _ = fs::remove_file(&verifier_path);
+                continue;
+            };
+            process.insert_proving_key(program_id, function_name, proving_key)?;
+            process.insert_verifying_key(program_id, function_name, verifying_key)?;
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Store the synthesized keys of the functions of a single program
+    fn store_program<N: Network>(&self, process: &Process<N>, program: &Program<N>) -> Result<usize> {
+        let stack = process.get_stack(program.id())?;
+        let program_directory = self.program_directory(program)?;
+
+        let mut stored = 0;
+        for function_name in program.functions().keys() {
+            if !stack.contains_proving_key(function_name) || !stack.contains_verifying_key(function_name) {
+                continue;
+            }
+            let (prover_path, verifier_path) = Self::key_paths(&program_directory, function_name);
+            if prover_path.exists() && verifier_path.exists() {
+                continue;
+            }
+            fs::create_dir_all(&program_directory)?;
+            Self::write(&prover_path, &stack.get_proving_key(function_name)?.to_bytes_le()?)?;
+            Self::write(&verifier_path, &stack.get_verifying_key(function_name)?.to_bytes_le()?)?;
+            stored += 1;
+        }
+        Ok(stored)
+    }
+
+    /// Get the directory the keys of the functions of a program are stored in
+    fn program_directory<N: Network>(&self, program: &Program<N>) -> Result<PathBuf> {
+        Ok(self.directory.join(program.id().to_string()).join(Self::checksum(program)?.to_string()))
+    }
+
+    /// Get the paths of the proving and verifying keys of a function
+    fn key_paths<N: Network>(program_directory: &Path, function_name: &Identifier<N>) -> (PathBuf, PathBuf) {
+        (
+            program_directory.join(format!("{function_name}.prover")),
+            program_directory.join(format!("{function_name}.verifier")),
+        )
+    }
+
+    /// Read a key, or nothing if it cannot be read
+    fn read<K: FromBytes>(path: &Path) -> Option<K> {
+        K::from_bytes_le(&fs::read(path).ok()?).ok()
+    }
+
+    /// Write a key to a temporary file first so an interrupted write never leaves a corrupt key.
+    /// The temporary file is unique to the key and the writer, so processes storing the same keys
+    /// concurrently never write to the same file.
+    fn write(path: &Path, bytes: &[u8]) -> Result<()> {
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let temp_path =
+            path.with_file_name(format!("{file_name}.{}-{:016x}.tmp", std::process::id(), rand::random::<u64>()));
+        fs::write(&temp_path, bytes)?;
+        fs::rename(&temp_path, path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            anyhow!("Failed to store the key at {}: {e}", path.display())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{random_program_id, HELLO_PROGRAM, HELLO_PROGRAM_2};
+    use snarkvm::circuit::AleoV0;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_key_cache_round_trip() {
+        let directory = std::env::temp_dir().join(format!("key_cache_{}", random_program_id(10)));
+        let key_cache = KeyCache::open(&directory).unwrap();
+        let program = Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap();
+        let function_name = Identifier::<Testnet3>::from_str("hello").unwrap();
+
+        // Nothing is stored or loaded before the keys are synthesized
+        let mut process = Process::<Testnet3>::load().unwrap();
+        process.add_program(&program).unwrap();
+        assert_eq!(key_cache.load(&process, &program).unwrap(), 0);
+        assert_eq!(key_cache.store(&process, &program).unwrap(), 0);
+
+        // Synthesized keys are stored once
+        process.synthesize_key::<AleoV0, _>(program.id(), &function_name, &mut rand::thread_rng()).unwrap();
+        assert_eq!(key_cache.store(&process, &program).unwrap(), 1);
+        assert_eq!(key_cache.store(&process, &program).unwrap(), 0);
+
+        // A fresh process gets the same keys from the cache
+        let mut fresh_process = Process::<Testnet3>::load().unwrap();
+        fresh_process.add_program(&program).unwrap();
+        assert_eq!(key_cache.load(&fresh_process, &program).unwrap(), 1);
+        assert_eq!(
+            fresh_process.get_verifying_key(program.id(), function_name).unwrap(),
+            process.get_verifying_key(program.id(), function_name).unwrap()
+        );
+
+        // A changed program with the same ID does not reuse the keys
+        let changed = Program::<Testnet3>::from_str(HELLO_PROGRAM_2).unwrap();
+        assert_ne!(KeyCache::checksum(&changed).unwrap(), KeyCache::checksum(&program).unwrap());
+
+        // A key that cannot be read is a cache miss and is stored again
+        let program_directory = key_cache.program_directory(&program).unwrap();
+        let (prover_path, _) = KeyCache::key_paths(&program_directory, &function_name);
+        std::fs::write(&prover_path, b"corrupt").unwrap();
+        let mut corrupt_process = Process::<Testnet3>::load().unwrap();
+        corrupt_process.add_program(&program).unwrap();
+        assert_eq!(key_cache.load(&corrupt_process, &program).unwrap(), 0);
+        assert!(!prover_path.exists());
+        assert_eq!(key_cache.store(&process, &program).unwrap(), 1);
+        assert_eq!(key_cache.load(&corrupt_process, &program).unwrap(), 1);
+
+        // No temporary files are left behind
+        let files = std::fs::read_dir(&program_directory).unwrap().map(|entry| entry.unwrap().file_name());
+        assert!(files.into_iter().all(|name| !name.to_string_lossy().ends_with(".tmp")));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
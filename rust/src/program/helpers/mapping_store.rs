@@ -0,0 +1,285 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Entries of a single mapping, in the order they were inserted
+type MappingEntries<N> = Vec<(Plaintext<N>, Value<N>)>;
+
+/// In-memory copy of the mapping state of programs, used to simulate their finalize blocks
+///
+/// The store can be seeded by hand with [MappingStore::insert] or with snapshots of values fetched
+/// from the network with [MappingStore::fetch], and can be serialized to reuse a snapshot between
+/// simulations. Mappings that are not seeded are empty when a finalize block is simulated.
+///
+/// Basic Usage:
+/// let mut mappings = MappingStore::<Testnet3>::new();
+/// mappings.insert("token.aleo", "account", Plaintext::from_str("aleo1...")?, Value::from_str("100u64")?)?;
+/// mappings.fetch(&api_client, "credits.aleo", "account", [Plaintext::from_str("aleo1...")?])?;
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MappingStore<N: Network> {
+    mappings: IndexMap<ProgramID<N>, IndexMap<Identifier<N>, MappingEntries<N>>>,
+}
+
+impl<N: Network> Default for MappingStore<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Network> MappingStore<N> {
+    /// Create an empty mapping store
+    pub fn new() -> Self {
+        Self { mappings: IndexMap::new() }
+    }
+
+    /// Set the value of a key in a program mapping, replacing any value it already has
+    pub fn insert(
+        &mut self,
+        program_id: impl TryInto<ProgramID<N>>,
+        mapping_name: impl TryInto<Identifier<N>>,
+        key: Plaintext<N>,
+        value: Value<N>,
+    ) -> Result<()> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let mapping_name = mapping_name.try_into().map_err(|_| anyhow!("Invalid mapping name"))?;
+        self.set(program_id, mapping_name, key, value);
+        Ok(())
+    }
+
+    /// Get the value of a key in a program mapping, if it is set
+    pub fn get(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Option<&Value<N>> {
+        self.entries(program_id, mapping_name)?.iter().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value)
+    }
+
+    /// Get the entries set in a program mapping
+    pub fn entries(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+    ) -> Option<&[(Plaintext<N>, Value<N>)]> {
+        self.mappings.get(program_id)?.get(mapping_name).map(|entries| entries.as_slice())
+    }
+
+    /// Get the IDs of the programs and the names of the mappings that have entries set
+    pub fn mappings(&self) -> impl Iterator<Item = (&ProgramID<N>, &Identifier<N>)> {
+        self.mappings
+            .iter()
+            .flat_map(|(program_id, mappings)| mappings.keys().map(move |mapping_name| (program_id, mapping_name)))
+    }
+
+    /// Snapshot the current values of the given keys of a program mapping from the network. Keys
+    /// that are not set on the network are left unset in the store.
+    ///
+    /// Nodes answer requests for keys that are not set with a `null` value, which network clients
+    /// report as an [ApiError::NotFound] like a missing resource.
+    pub fn fetch(
+        &mut self,
+        api_client: &impl NetworkClient<N>,
+        program_id: impl TryInto<ProgramID<N>>,
+        mapping_name: impl TryInto<Identifier<N>>,
+        keys: impl IntoIterator<Item = Plaintext<N>>,
+    ) -> Result<()> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let mapping_name = mapping_name.try_into().map_err(|_| anyhow!("Invalid mapping name"))?;
+        for key in keys {
+            match api_client.get_mapping_value(&program_id, &mapping_name, &key) {
+                Ok(value) => self.set(program_id, mapping_name, key, value),
+                // The key is not set on the network
+                Err(error) if ApiError::is_not_found(&error) => continue,
+                Err(error) => bail!("Failed to fetch {program_id}/{mapping_name}[{key}] from the network: {error}"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the mapping writes of a simulated finalize block, so a following simulation sees them
+    pub fn apply(&mut self, writes: &[MappingWrite<N>]) {
+        writes
+            .iter()
+            .for_each(|write| self.set(write.program_id, write.mapping_name, write.key.clone(), write.value.clone()));
+    }
+
+    fn set(&mut self, program_id: ProgramID<N>, mapping_name: Identifier<N>, key: Plaintext<N>, value: Value<N>) {
+        let entries = self.mappings.entry(program_id).or_default().entry(mapping_name).or_default();
+        match entries.iter_mut().find(|(entry_key, _)| *entry_key == key) {
+            Some((_, entry_value)) => *entry_value = value,
+            None => entries.push((key, value)),
+        }
+    }
+}
+
+/// Write to a program mapping made by a simulated finalize block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MappingWrite<N: Network> {
+    /// ID of the program owning the mapping
+    pub program_id: ProgramID<N>,
+    /// Name of the mapping
+    pub mapping_name: Identifier<N>,
+    /// Key written to
+    pub key: Plaintext<N>,
+    /// Value of the key before the finalize block ran, if it was set
+    pub previous: Option<Value<N>>,
+    /// Value of the key after the finalize block ran
+    pub value: Value<N>,
+}
+
+/// Outcome of simulating the finalize blocks of a program execution
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FinalizeSimulation<N: Network> {
+    outputs: Vec<Value<N>>,
+    writes: Vec<MappingWrite<N>>,
+    failure: Option<String>,
+}
+
+impl<N: Network> FinalizeSimulation<N> {
+    pub(crate) fn new(outputs: Vec<Value<N>>, writes: Vec<MappingWrite<N>>, failure: Option<String>) -> Self {
+        Self { outputs, writes, failure }
+    }
+
+    /// Get the outputs of the executed function
+    pub fn outputs(&self) -> &[Value<N>] {
+        &self.outputs
+    }
+
+    /// Get the mapping writes made by the finalize blocks, one per written key. A failed
+    /// finalize block writes nothing, as the network reverts all of its writes.
+    pub fn writes(&self) -> &[MappingWrite<N>] {
+        &self.writes
+    }
+
+    /// Get the reason a finalize block failed, such as a failed assertion or an underflow
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    /// Check if the finalize blocks ran to completion, in which case the network would accept
+    /// the execution with the same mapping state
+    pub fn is_success(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve the response returned by `respond` for the path of each request to the returned url
+    fn serve(respond: fn(&str) -> (&'static str, &'static str)) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0u8; 1024];
+                let length = stream.read(&mut request).unwrap_or_default();
+                let request = String::from_utf8_lossy(&request[..length]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = respond(path);
+                let response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_mapping_store_fetch_leaves_unset_keys_unset() {
+        let url = serve(|path| match path.rsplit('/').next() {
+            Some("1field") => ("200 OK", "\"100u64\""),
+            Some("2field") => ("200 OK", "null"),
+            Some("3field") => ("404 Not Found", ""),
+            _ => ("500 Internal Server Error", "Something went wrong: Invalid mapping key"),
+        });
+        let api_client = AleoAPIClient::<Testnet3>::builder()
+            .peer(&url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let program_id = ProgramID::from_str("token.aleo").unwrap();
+        let account = Identifier::from_str("account").unwrap();
+
+        // Keys answered with null or a 404 are left unset
+        let mut mappings = MappingStore::<Testnet3>::new();
+        let keys = ["1field", "2field", "3field"].map(|key| Plaintext::from_str(key).unwrap());
+        mappings.fetch(&api_client, program_id, account, keys.clone()).unwrap();
+        assert_eq!(mappings.get(&program_id, &account, &keys[0]), Some(&Value::from_str("100u64").unwrap()));
+        assert_eq!(mappings.get(&program_id, &account, &keys[1]), None);
+        assert_eq!(mappings.get(&program_id, &account, &keys[2]), None);
+
+        // Other failures are reported
+        let error = mappings.fetch(&api_client, program_id, account, [Plaintext::from_str("4field").unwrap()]);
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn test_mapping_store_insert_and_apply() {
+        let mut mappings = MappingStore::<Testnet3>::new();
+        mappings
+            .insert("token.aleo", "account", Plaintext::from_str("1field").unwrap(), Value::from_str("100u64").unwrap())
+            .unwrap();
+        mappings
+            .insert("token.aleo", "account", Plaintext::from_str("2field").unwrap(), Value::from_str("5u64").unwrap())
+            .unwrap();
+        mappings
+            .insert("token.aleo", "account", Plaintext::from_str("1field").unwrap(), Value::from_str("90u64").unwrap())
+            .unwrap();
+        assert!(mappings
+            .insert(
+                "token.aleo",
+                "not a mapping",
+                Plaintext::from_str("1field").unwrap(),
+                Value::from_str("1u64").unwrap()
+            )
+            .is_err());
+
+        let program_id = ProgramID::from_str("token.aleo").unwrap();
+        let account = Identifier::from_str("account").unwrap();
+        let key = Plaintext::from_str("1field").unwrap();
+        assert_eq!(mappings.get(&program_id, &account, &key), Some(&Value::from_str("90u64").unwrap()));
+        assert_eq!(mappings.entries(&program_id, &account).unwrap().len(), 2);
+
+        let write = MappingWrite {
+            program_id,
+            mapping_name: account,
+            key: Plaintext::from_str("3field").unwrap(),
+            previous: None,
+            value: Value::from_str("10u64").unwrap(),
+        };
+        mappings.apply(std::slice::from_ref(&write));
+        assert_eq!(mappings.get(&program_id, &account, &write.key), Some(&write.value));
+        assert_eq!(mappings.mappings().count(), 1);
+
+        let serialized = serde_json::to_string(&mappings).unwrap();
+        assert_eq!(serde_json::from_str::<MappingStore<Testnet3>>(&serialized).unwrap(), mappings);
+    }
+}
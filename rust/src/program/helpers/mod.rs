@@ -16,15 +16,47 @@
 
 use super::*;
 
+pub mod authorization;
+pub use authorization::*;
+
+pub mod consolidation;
+pub use consolidation::*;
+
+pub mod key_cache;
+pub use key_cache::*;
+
+pub mod mapping_store;
+pub use mapping_store::*;
+
 pub mod offline;
 pub use offline::*;
 
 pub mod state;
 pub use state::*;
 
+pub mod query;
+pub use query::*;
+
 pub mod records;
 pub use records::*;
 
+pub mod reservation;
+pub use reservation::*;
+
+pub mod selection;
+pub use selection::*;
+
+#[cfg(feature = "offline")]
+pub mod state_bundle;
+#[cfg(feature = "offline")]
+pub use state_bundle::*;
+
+pub mod store;
+pub use store::*;
+
+pub mod transaction;
+pub use transaction::*;
+
 /// Transfer Type to Perform
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TransferType {
@@ -0,0 +1,252 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::{cmp::Ordering, fmt};
+
+/// Comparison made by a [FieldPredicate]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl Comparison {
+    /// Operators in the order they are searched for, so `>=` is not mistaken for `>`
+    const OPERATORS: [(&'static str, Self); 6] = [
+        ("==", Self::Equal),
+        ("!=", Self::NotEqual),
+        ("<=", Self::LessThanOrEqual),
+        (">=", Self::GreaterThanOrEqual),
+        ("<", Self::LessThan),
+        (">", Self::GreaterThan),
+    ];
+
+    fn is_ordering(&self) -> bool {
+        !matches!(self, Self::Equal | Self::NotEqual)
+    }
+
+    fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Self::Equal => ordering.is_eq(),
+            Self::NotEqual => ordering.is_ne(),
+            Self::LessThan => ordering.is_lt(),
+            Self::LessThanOrEqual => ordering.is_le(),
+            Self::GreaterThan => ordering.is_gt(),
+            Self::GreaterThanOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (operator, _) = Self::OPERATORS.iter().find(|(_, comparison)| comparison == self).unwrap();
+        write!(f, "{operator}")
+    }
+}
+
+/// Condition on a field of a record, such as `amount >= 100u64`
+///
+/// The field is given by its path in the record (nested fields are separated by dots) and is
+/// compared with a literal of the same type. Any literal can be compared for (in)equality, only
+/// integers can be ordered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldPredicate<N: Network> {
+    path: Vec<Identifier<N>>,
+    comparison: Comparison,
+    value: Literal<N>,
+}
+
+impl<N: Network> FieldPredicate<N> {
+    /// Create a predicate comparing the field at the given path with a literal
+    pub fn new(path: Vec<Identifier<N>>, comparison: Comparison, value: Literal<N>) -> Result<Self> {
+        ensure!(!path.is_empty(), "The path of a record field must not be empty");
+        ensure!(
+            !comparison.is_ordering() || integer_ordering(&value, &value).is_some(),
+            "Only integer fields can be compared with {comparison}, found {value}"
+        );
+        Ok(Self { path, comparison, value })
+    }
+
+    /// Check if the record has the field and its value satisfies the predicate
+    pub fn matches(&self, record: &Record<N, Plaintext<N>>) -> bool {
+        let Ok(Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) =
+            record.find(&self.path)
+        else {
+            return false;
+        };
+        let Plaintext::Literal(literal, _) = plaintext else { return false };
+        match self.comparison {
+            Comparison::Equal => literal == self.value,
+            Comparison::NotEqual => literal != self.value,
+            comparison => integer_ordering(&literal, &self.value).is_some_and(|ordering| comparison.holds(ordering)),
+        }
+    }
+}
+
+impl<N: Network> FromStr for FieldPredicate<N> {
+    type Err = Error;
+
+    /// Parse a predicate of the form `<path> <operator> <literal>`, for example `amount >= 100u64`
+    fn from_str(predicate: &str) -> Result<Self> {
+        let (position, operator, comparison) = Comparison::OPERATORS
+            .iter()
+            .find_map(|(operator, comparison)| Some((predicate.find(operator)?, *operator, *comparison)))
+            .ok_or_else(|| {
+                anyhow!("Invalid record field predicate {predicate}, it must have the form <field> <operator> <value>")
+            })?;
+        let path = predicate[..position]
+            .trim()
+            .split('.')
+            .map(Identifier::from_str)
+            .collect::<Result<Vec<_>>>()
+            .map_err(|_| anyhow!("Invalid record field in predicate {predicate}"))?;
+        let value = Literal::from_str(predicate[position + operator.len()..].trim())
+            .map_err(|_| anyhow!("Invalid value in predicate {predicate}, it must be a literal such as 100u64"))?;
+        Self::new(path, comparison, value)
+    }
+}
+
+impl<N: Network> fmt::Display for FieldPredicate<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = self.path.iter().map(|identifier| identifier.to_string()).collect::<Vec<_>>().join(".");
+        write!(f, "{path} {} {}", self.comparison, self.value)
+    }
+}
+
+/// Query for the records of a program owned by an account
+///
+/// Records are matched by the program that created them, their record name and any number of
+/// predicates on their fields.
+///
+/// Basic Usage:
+/// let query = RecordQuery::new(ProgramID::from_str("token.aleo")?, Identifier::from_str("token")?)
+///     .filter(FieldPredicate::from_str("amount >= 100u64")?);
+/// let tokens = record_finder.find_records(&query, &private_key)?;
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordQuery<N: Network> {
+    program_id: ProgramID<N>,
+    record_name: Identifier<N>,
+    predicates: Vec<FieldPredicate<N>>,
+}
+
+impl<N: Network> RecordQuery<N> {
+    /// Create a query matching every record with the given name created by the program
+    pub fn new(program_id: ProgramID<N>, record_name: Identifier<N>) -> Self {
+        Self { program_id, record_name, predicates: vec![] }
+    }
+
+    /// Only match records satisfying the predicate
+    pub fn filter(mut self, predicate: FieldPredicate<N>) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Get the program the queried records are created by
+    pub fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Get the name of the queried records
+    pub fn record_name(&self) -> &Identifier<N> {
+        &self.record_name
+    }
+
+    /// Get the predicates the queried records must satisfy
+    pub fn predicates(&self) -> &[FieldPredicate<N>] {
+        &self.predicates
+    }
+
+    /// Check if a decrypted record with the given commitment matches the query. The program and
+    /// record name are checked by recomputing the commitment, as records do not carry them.
+    pub fn matches(&self, commitment: &Field<N>, record: &Record<N, Plaintext<N>>) -> bool {
+        record.to_commitment(&self.program_id, &self.record_name).is_ok_and(|expected| &expected == commitment)
+            && self.predicates.iter().all(|predicate| predicate.matches(record))
+    }
+}
+
+/// Order two integer literals of the same type
+fn integer_ordering<N: Network>(first: &Literal<N>, second: &Literal<N>) -> Option<Ordering> {
+    match (first, second) {
+        (Literal::I8(first), Literal::I8(second)) => Some(first.cmp(second)),
+        (Literal::I16(first), Literal::I16(second)) => Some(first.cmp(second)),
+        (Literal::I32(first), Literal::I32(second)) => Some(first.cmp(second)),
+        (Literal::I64(first), Literal::I64(second)) => Some(first.cmp(second)),
+        (Literal::I128(first), Literal::I128(second)) => Some(first.cmp(second)),
+        (Literal::U8(first), Literal::U8(second)) => Some(first.cmp(second)),
+        (Literal::U16(first), Literal::U16(second)) => Some(first.cmp(second)),
+        (Literal::U32(first), Literal::U32(second)) => Some(first.cmp(second)),
+        (Literal::U64(first), Literal::U64(second)) => Some(first.cmp(second)),
+        (Literal::U128(first), Literal::U128(second)) => Some(first.cmp(second)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{amount_record, RECIPIENT_PRIVATE_KEY};
+    use snarkvm_console::network::Testnet3;
+
+    fn token_record(amount: u64) -> Record<Testnet3, Plaintext<Testnet3>> {
+        amount_record(&PrivateKey::from_str(RECIPIENT_PRIVATE_KEY).unwrap(), "amount", amount)
+    }
+
+    #[test]
+    fn test_field_predicates() {
+        let record = token_record(150);
+        let predicate = FieldPredicate::<Testnet3>::from_str("amount >= 100u64").unwrap();
+        assert_eq!(predicate.to_string(), "amount >= 100u64");
+        assert!(predicate.matches(&record));
+        assert!(!FieldPredicate::from_str("amount<100u64").unwrap().matches(&record));
+        assert!(FieldPredicate::from_str("amount == 150u64").unwrap().matches(&record));
+        assert!(FieldPredicate::from_str("amount != 150u32").unwrap().matches(&record));
+
+        // Fields that are missing or hold another type never match
+        assert!(!FieldPredicate::from_str("balance >= 100u64").unwrap().matches(&record));
+        assert!(!FieldPredicate::from_str("amount > 100u32").unwrap().matches(&record));
+
+        // Only integers can be ordered and the predicate must be complete
+        assert!(FieldPredicate::<Testnet3>::from_str("owner > 1field").is_err());
+        assert!(FieldPredicate::<Testnet3>::from_str("amount 100u64").is_err());
+        assert!(FieldPredicate::<Testnet3>::from_str(">= 100u64").is_err());
+    }
+
+    #[test]
+    fn test_record_query_matches_program_and_record_name() {
+        let program_id = ProgramID::<Testnet3>::from_str("token.aleo").unwrap();
+        let record_name = Identifier::from_str("token").unwrap();
+        let record = token_record(150);
+        let commitment = record.to_commitment(&program_id, &record_name).unwrap();
+
+        let query = RecordQuery::new(program_id, record_name);
+        assert!(query.matches(&commitment, &record));
+        let large_tokens = query.clone().filter(FieldPredicate::from_str("amount >= 100u64").unwrap());
+        assert!(large_tokens.matches(&commitment, &record));
+        assert!(!query.filter(FieldPredicate::from_str("amount >= 200u64").unwrap()).matches(&commitment, &record));
+
+        // The commitment of the record differs for another program or record name
+        let other_program = RecordQuery::new(ProgramID::from_str("other_token.aleo").unwrap(), record_name);
+        assert!(!other_program.matches(&commitment, &record));
+        let other_name = RecordQuery::new(program_id, Identifier::from_str("coin").unwrap());
+        assert!(!other_name.matches(&commitment, &record));
+    }
+}
@@ -16,15 +16,131 @@
 
 use super::*;
 
+use std::sync::Arc;
+
+/// Interval at which the network is polled for the transaction spending reserved records
+const RESERVATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Helper struct for finding records on chain during program development
+///
+/// Records are searched for through any [NetworkClient] implementation, which defaults to the
+/// [AleoAPIClient]. If the finder is given a [RecordStore], records are read from the store and
+/// only the blocks produced since the last search are scanned. The records paying for amounts are
+/// chosen by a [CoinSelector], which defaults to [CoinSelection::LargestFirst]. If the finder is
+/// given [RecordReservations], the records it chooses are reserved so clones of the finder used
+/// by concurrent transactions never choose the same record.
 #[derive(Clone)]
-pub struct RecordFinder<N: Network> {
-    api_client: AleoAPIClient<N>,
+pub struct RecordFinder<N: Network, C: NetworkClient<N> = AleoAPIClient<N>> {
+    api_client: C,
+    record_store: Option<RecordStore<N>>,
+    coin_selector: Arc<dyn CoinSelector<N>>,
+    reservations: Option<RecordReservations<N>>,
+    _network: PhantomData<N>,
 }
 
-impl<N: Network> RecordFinder<N> {
-    pub fn new(api_client: AleoAPIClient<N>) -> Self {
-        Self { api_client }
+impl<N: Network, C: NetworkClient<N>> RecordFinder<N, C> {
+    pub fn new(api_client: C) -> Self {
+        Self {
+            api_client,
+            record_store: None,
+            coin_selector: Arc::new(CoinSelection::default()),
+            reservations: None,
+            _network: PhantomData,
+        }
+    }
+
+    /// Search for records in a record store, syncing it with the network before each search
+    pub fn with_record_store(mut self, record_store: RecordStore<N>) -> Self {
+        self.record_store = Some(record_store);
+        self
+    }
+
+    /// Get the record store records are searched for in, if any
+    pub fn record_store(&self) -> Option<&RecordStore<N>> {
+        self.record_store.as_ref()
+    }
+
+    /// Choose the records paying for amounts with the given strategy
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector<N> + 'static) -> Self {
+        self.coin_selector = Arc::new(coin_selector);
+        self
+    }
+
+    /// Reserve the records chosen to pay for amounts until they are released or the time to live
+    /// elapses, so they are not chosen again in the meantime
+    pub fn with_reservations(mut self, ttl: Duration) -> Self {
+        self.reservations = Some(RecordReservations::new(ttl));
+        self
+    }
+
+    /// Get the reservations of the records chosen by the finder, if records are reserved
+    pub fn reservations(&self) -> Option<&RecordReservations<N>> {
+        self.reservations.as_ref()
+    }
+
+    /// Release records reserved by the finder so they can be chosen again, for instance when the
+    /// transaction spending them could not be built or broadcast
+    pub fn release_records(&self, records: &[Record<N, Plaintext<N>>]) -> Result<()> {
+        match &self.reservations {
+            Some(reservations) => reservations.release(records),
+            None => Ok(()),
+        }
+    }
+
+    /// Wait for the transaction spending reserved records to be confirmed or rejected and then
+    /// release the records. If the transaction has not settled once the reservations' time to live
+    /// elapses, the records are released all the same.
+    pub fn release_records_when_settled(
+        &self,
+        transaction_id: N::TransactionID,
+        records: &[Record<N, Plaintext<N>>],
+    ) -> Result<TransactionStatus<N>> {
+        let ttl = self.reservations.as_ref().map_or(Duration::ZERO, |reservations| reservations.ttl());
+        let status = self.api_client.wait_for_transaction(transaction_id, ttl, RESERVATION_POLL_INTERVAL);
+        self.release_records(records)?;
+        status
+    }
+}
+
+// Records are searched for with a [BlockScanner], which fetches blocks from several threads
+impl<N: Network, C: NetworkClient<N> + Sync> RecordFinder<N, C> {
+    /// Choose unspent records of the account to pay for each of the amounts with the finder's
+    /// [CoinSelector]. Depending on the strategy an amount may be covered by several records, which
+    /// must be joined before they can be spent. If the finder reserves records, reserved records
+    /// are not chosen and the chosen records are reserved.
+    #[allow(clippy::type_complexity)]
+    pub fn select_records(
+        &self,
+        amounts: &[u64],
+        private_key: &PrivateKey<N>,
+    ) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>> {
+        // Stop searching the chain once a record covering each amount is found if the strategy
+        // allows it, searching every record only if those records turn out to be reserved
+        if self.record_store.is_none() && !self.coin_selector.needs_all_candidates() {
+            let candidates = self.find_unspent_records_on_chain(Some(&amounts.to_vec()), None, private_key)?;
+            match self.select_from(candidates, amounts) {
+                Err(_) if self.reservations.is_some() => (),
+                selected => return selected,
+            }
+        }
+        let candidates = self.find_unspent_records_on_chain(None, None, private_key)?;
+        self.select_from(candidates, amounts)
+    }
+
+    /// Choose records from the candidates with the finder's [CoinSelector], reserving them if the
+    /// finder reserves records
+    #[allow(clippy::type_complexity)]
+    fn select_from(
+        &self,
+        candidates: Vec<Record<N, Plaintext<N>>>,
+        amounts: &[u64],
+    ) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>> {
+        match &self.reservations {
+            Some(reservations) => {
+                reservations.select_and_reserve(candidates, |available| self.coin_selector.select(available, amounts))
+            }
+            None => self.coin_selector.select(&candidates, amounts),
+        }
     }
 
     /// Resolve two records for a transfer amount and fee respectively
@@ -45,23 +161,39 @@ impl<N: Network> RecordFinder<N> {
     /// Resolve a record with a specific value. If successful it will return a record with a gate
     /// value equal to or greater than the specified amount.
     pub fn find_one_record(&self, private_key: &PrivateKey<N>, amount: u64) -> Result<Record<N, Plaintext<N>>> {
-        let amounts = vec![amount];
-        self.find_unspent_records_on_chain(Some(&amounts), None, private_key)?
-            .into_iter()
-            .find(|record| record.microcredits().unwrap_or(0) >= amount)
-            .ok_or_else(|| anyhow!("Insufficient funds"))
+        Ok(self.find_record_amounts(vec![amount], private_key)?.remove(0))
     }
 
     /// Attempt to resolve records with specific gate values specified as a vector of u64s. If the
     /// function is successful at resolving the records, it will return a vector of records with
     /// microcredits equal to or greater than the specified amounts. If it cannot resolve records
-    /// with the specified amounts, it will return an error.
+    /// with the specified amounts, it will return an error. The records are returned in the order
+    /// of the amounts they pay for.
     pub fn find_record_amounts(
         &self,
         amounts: Vec<u64>,
         private_key: &PrivateKey<N>,
     ) -> Result<Vec<Record<N, Plaintext<N>>>> {
-        self.find_unspent_records_on_chain(Some(&amounts), None, private_key)
+        let selected = self.select_records(&amounts, private_key)?;
+        if let Some((records, amount)) = selected.iter().zip(amounts.iter()).find(|(records, _)| records.len() != 1) {
+            let count = records.len();
+            self.release_records(&selected.concat())?;
+            bail!("No single record covers {amount} microcredits, {count} records must be joined to pay for it");
+        }
+        Ok(selected.into_iter().map(|mut records| records.remove(0)).collect())
+    }
+
+    /// Plan how to join the account's unspent records into a record holding at least the amount,
+    /// for when no single record covers it. `join_fee` is the fee in microcredits each join of the
+    /// plan is paid with.
+    pub fn plan_consolidation(
+        &self,
+        amount: u64,
+        join_fee: u64,
+        private_key: &PrivateKey<N>,
+    ) -> Result<ConsolidationPlan<N>> {
+        let records = self.find_unspent_records_on_chain(None, None, private_key)?;
+        ConsolidationPlan::new(amount, join_fee, &records)
     }
 
     pub fn find_unspent_records_on_chain(
@@ -70,8 +202,85 @@ impl<N: Network> RecordFinder<N> {
         max_microcredits: Option<u64>,
         private_key: &PrivateKey<N>,
     ) -> Result<Vec<Record<N, Plaintext<N>>>> {
+        if let Some(record_store) = &self.record_store {
+            record_store.sync(&self.api_client, private_key)?;
+            // Search the most recent records first, as the network search does
+            let records = record_store
+                .unspent_records(&Address::try_from(private_key)?)?
+                .into_iter()
+                .rev()
+                .map(|stored| stored.record)
+                .collect();
+            return limit_records(records, amounts, max_microcredits);
+        }
+
         let latest_height = self.api_client.latest_height()?;
         let records = self.api_client.get_unspent_records(private_key, 0..latest_height, max_microcredits, amounts)?;
         Ok(records.into_iter().map(|(_, record)| record).collect())
     }
+
+    /// Find the unspent records of the account matching a [RecordQuery], for instance the records
+    /// of a token program holding at least a certain amount. The records are returned in the order
+    /// they were created in.
+    pub fn find_records(&self, query: &RecordQuery<N>, private_key: &PrivateKey<N>) -> Result<Vec<FoundRecord<N>>> {
+        if let Some(record_store) = &self.record_store {
+            record_store.sync(&self.api_client, private_key)?;
+            return Ok(record_store
+                .unspent_records(&Address::try_from(private_key)?)?
+                .into_iter()
+                .filter(|stored| query.matches(&stored.commitment, &stored.record))
+                .map(|stored| FoundRecord {
+                    commitment: stored.commitment,
+                    serial_number: stored.serial_number,
+                    block_height: stored.block_height,
+                    record: stored.record,
+                })
+                .collect());
+        }
+
+        // Search every block up to the latest one, so records spent in any of them are left out
+        let end_height = self.api_client.latest_height()?.saturating_add(1);
+        let mut cursor = UnspentScanCursor::new(0);
+        BlockScanner::new(&self.api_client).scan_unspent(private_key, end_height, &mut cursor, |_| false)?;
+        Ok(cursor.records.into_iter().filter(|found| query.matches(&found.commitment, &found.record)).collect())
+    }
+}
+
+/// Select records from a list of unspent records in the same way the network search does. If
+/// amounts are specified, the largest records are returned if each covers one of the amounts. If a
+/// maximum number of microcredits is specified, records are taken until they add up to it.
+fn limit_records<N: Network>(
+    mut records: Vec<Record<N, Plaintext<N>>>,
+    amounts: Option<&Vec<u64>>,
+    max_microcredits: Option<u64>,
+) -> Result<Vec<Record<N, Plaintext<N>>>> {
+    if let Some(amounts) = amounts {
+        ensure!(!amounts.is_empty(), "If specific amounts are specified, there must be one amount specified");
+        let mut required_amounts = amounts.clone();
+        required_amounts.sort_by(|a, b| b.cmp(a));
+        records.sort_by_key(|record| std::cmp::Reverse(record.microcredits().unwrap_or(0)));
+        ensure!(
+            records.len() >= required_amounts.len()
+                && records
+                    .iter()
+                    .zip(required_amounts.iter())
+                    .all(|(record, amount)| record.microcredits().unwrap_or(0) >= *amount),
+            "Could not find enough records with the specified amounts, consider splitting records into smaller amounts"
+        );
+        records.truncate(required_amounts.len());
+        return Ok(records);
+    }
+
+    if let Some(max_microcredits) = max_microcredits {
+        let mut total = 0u64;
+        records = records
+            .into_iter()
+            .take_while(|record| {
+                let below_max = total < max_microcredits;
+                total = total.saturating_add(record.microcredits().unwrap_or(0));
+                below_max
+            })
+            .collect();
+    }
+    Ok(records)
 }
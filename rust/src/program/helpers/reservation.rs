@@ -0,0 +1,151 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Credits records chosen to pay for transactions that have not settled yet
+///
+/// A reserved record is not chosen again until it is released or its reservation expires, so
+/// concurrent transactions never spend the same record. Records are identified by their
+/// commitment. Clones of a [RecordReservations] share the same reservations.
+#[derive(Clone)]
+pub struct RecordReservations<N: Network> {
+    ttl: Duration,
+    reserved: Arc<Mutex<IndexMap<Field<N>, Instant>>>,
+}
+
+impl<N: Network> RecordReservations<N> {
+    /// Create a set of reservations that expire after the given time to live
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, reserved: Arc::new(Mutex::new(IndexMap::new())) }
+    }
+
+    /// Get the time after which reservations expire
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Check if a credits record is reserved
+    pub fn is_reserved(&self, record: &Record<N, Plaintext<N>>) -> Result<bool> {
+        let commitment = credits_commitment(record)?;
+        Ok(self.reserved()?.contains_key(&commitment))
+    }
+
+    /// Reserve credits records, failing without reserving any of them if one is already reserved
+    pub fn reserve(&self, records: &[Record<N, Plaintext<N>>]) -> Result<()> {
+        let commitments = records.iter().map(credits_commitment).collect::<Result<Vec<_>>>()?;
+        let mut reserved = self.reserved()?;
+        ensure!(
+            commitments.iter().all(|commitment| !reserved.contains_key(commitment)),
+            "A record is already reserved for another transaction"
+        );
+        let expiry = Instant::now() + self.ttl;
+        reserved.extend(commitments.into_iter().map(|commitment| (commitment, expiry)));
+        Ok(())
+    }
+
+    /// Release reserved credits records so they can be chosen again
+    pub fn release(&self, records: &[Record<N, Plaintext<N>>]) -> Result<()> {
+        let commitments = records.iter().map(credits_commitment).collect::<Result<Vec<_>>>()?;
+        let mut reserved = self.reserved()?;
+        for commitment in commitments {
+            reserved.remove(&commitment);
+        }
+        Ok(())
+    }
+
+    /// Select records with the given selection function from the candidates that are not reserved
+    /// and reserve the selected records. No other selection can happen in between, so concurrent
+    /// selections never choose the same record.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn select_and_reserve(
+        &self,
+        candidates: Vec<Record<N, Plaintext<N>>>,
+        select: impl FnOnce(&[Record<N, Plaintext<N>>]) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>>,
+    ) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>> {
+        let candidates = candidates
+            .into_iter()
+            .map(|record| Ok((credits_commitment(&record)?, record)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut reserved = self.reserved()?;
+        let available = candidates
+            .iter()
+            .filter(|(commitment, _)| !reserved.contains_key(commitment))
+            .map(|(_, record)| record.clone())
+            .collect::<Vec<_>>();
+        let selected = select(&available)?;
+        let expiry = Instant::now() + self.ttl;
+        for record in selected.iter().flatten() {
+            reserved.insert(credits_commitment(record)?, expiry);
+        }
+        Ok(selected)
+    }
+
+    /// Lock the reservations, dropping those that have expired
+    fn reserved(&self) -> Result<MutexGuard<'_, IndexMap<Field<N>, Instant>>> {
+        let mut reserved = self.reserved.lock().map_err(|_| anyhow!("The record reservations lock was poisoned"))?;
+        let now = Instant::now();
+        reserved.retain(|_, expiry| *expiry > now);
+        Ok(reserved)
+    }
+}
+
+/// Compute the commitment of a record of the credits.aleo program
+fn credits_commitment<N: Network>(record: &Record<N, Plaintext<N>>) -> Result<Field<N>> {
+    record.to_commitment(&ProgramID::from_str("credits.aleo")?, &Identifier::from_str("credits")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::credits_records;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_selected_records_are_not_selected_again() {
+        let reservations = RecordReservations::<Testnet3>::new(Duration::from_secs(60));
+        let candidates = credits_records(&[500, 1_000]);
+
+        // Concurrent selections of the largest record each get a different one
+        let first = reservations
+            .select_and_reserve(candidates.clone(), |available| CoinSelection::LargestFirst.select(available, &[100]))
+            .unwrap();
+        let second = reservations
+            .select_and_reserve(candidates.clone(), |available| CoinSelection::LargestFirst.select(available, &[100]))
+            .unwrap();
+        assert_eq!(first, vec![vec![candidates[1].clone()]]);
+        assert_eq!(second, vec![vec![candidates[0].clone()]]);
+        assert!(reservations.reserve(&candidates[..1]).is_err());
+
+        // Released records can be selected again
+        reservations.release(&first[0]).unwrap();
+        assert!(!reservations.is_reserved(&candidates[1]).unwrap());
+        assert!(reservations.is_reserved(&candidates[0]).unwrap());
+        reservations.reserve(&candidates[1..]).unwrap();
+    }
+
+    #[test]
+    fn test_reservations_expire() {
+        let reservations = RecordReservations::<Testnet3>::new(Duration::from_millis(20));
+        let candidates = credits_records(&[500]);
+        reservations.reserve(&candidates).unwrap();
+        assert!(reservations.is_reserved(&candidates[0]).unwrap());
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!reservations.is_reserved(&candidates[0]).unwrap());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Strategy for choosing which unspent records pay for a set of amounts
+///
+/// The built-in strategies are provided by [CoinSelection], custom strategies can be given to a
+/// [RecordFinder] by implementing this trait.
+#[allow(clippy::type_complexity)]
+pub trait CoinSelector<N: Network>: Send + Sync {
+    /// Choose records from the candidates to cover each of the amounts (in microcredits). The
+    /// records chosen for each amount are returned in the order the amounts were given and each
+    /// candidate is chosen at most once. An amount covered by several records can only be paid for
+    /// once those records have been joined into one.
+    fn select(
+        &self,
+        candidates: &[Record<N, Plaintext<N>>],
+        amounts: &[u64],
+    ) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>>;
+
+    /// Whether the strategy must see every unspent record of the account to choose from. If not,
+    /// the [RecordFinder] stops searching the chain as soon as it has found a record covering each
+    /// amount and chooses among those.
+    fn needs_all_candidates(&self) -> bool {
+        true
+    }
+}
+
+/// Built-in coin selection strategies
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoinSelection {
+    /// Pay each amount with one of the largest records, the largest amount with the largest record
+    #[default]
+    LargestFirst,
+    /// Pay each amount with the smallest record that covers it
+    SmallestSufficient,
+    /// Pay each amount with a single record if one covers it, or otherwise with as few records as
+    /// possible
+    FewestRecords,
+    /// Pay each amount with a record matching it exactly or leaving change of at least the dust
+    /// threshold (in microcredits), so no unspendably small records are created
+    AvoidDust { dust_threshold: u64 },
+}
+
+impl CoinSelection {
+    /// Dust threshold used by [CoinSelection::AvoidDust] when parsed from a string
+    pub const DEFAULT_DUST_THRESHOLD: u64 = 10_000;
+
+    /// Whether the strategy pays for every amount with a single record. Strategies that do not can
+    /// only be used to find records once the records they choose are joined, for instance with a
+    /// [ConsolidationPlan].
+    pub fn selects_single_records(&self) -> bool {
+        !matches!(self, Self::FewestRecords)
+    }
+}
+
+impl FromStr for CoinSelection {
+    type Err = Error;
+
+    fn from_str(strategy: &str) -> Result<Self> {
+        match strategy.replace('-', "_").as_str() {
+            "largest_first" => Ok(Self::LargestFirst),
+            "smallest_sufficient" => Ok(Self::SmallestSufficient),
+            "fewest_records" => Ok(Self::FewestRecords),
+            "avoid_dust" => Ok(Self::AvoidDust { dust_threshold: Self::DEFAULT_DUST_THRESHOLD }),
+            _ => bail!(
+                "Invalid coin selection strategy {strategy}, it must be one of the following: largest_first, smallest_sufficient, fewest_records, avoid_dust"
+            ),
+        }
+    }
+}
+
+impl<N: Network> CoinSelector<N> for CoinSelection {
+    fn select(
+        &self,
+        candidates: &[Record<N, Plaintext<N>>],
+        amounts: &[u64],
+    ) -> Result<Vec<Vec<Record<N, Plaintext<N>>>>> {
+        ensure!(!amounts.is_empty(), "At least one amount must be specified to select records for");
+
+        // Pair the candidates with their values, skipping records that do not hold credits
+        let mut available =
+            candidates.iter().filter_map(|record| Some((record.microcredits().ok()?, record))).collect::<Vec<_>>();
+        available.sort_by_key(|(microcredits, _)| std::cmp::Reverse(*microcredits));
+
+        // Choose records for the largest amounts first, so they are not left without a record
+        // large enough to cover them
+        let mut order = (0..amounts.len()).collect::<Vec<_>>();
+        order.sort_by_key(|index| std::cmp::Reverse(amounts[*index]));
+
+        let mut selected = vec![vec![]; amounts.len()];
+        for index in order {
+            let amount = amounts[index];
+            let chosen = match self {
+                Self::LargestFirst => available.first().filter(|(microcredits, _)| *microcredits >= amount).map(|_| 0),
+                Self::SmallestSufficient => smallest_sufficient(&available, amount),
+                Self::FewestRecords => match smallest_sufficient(&available, amount) {
+                    Some(position) => Some(position),
+                    None => {
+                        // Take the largest records until they add up to the amount
+                        let mut total = 0u64;
+                        let count = available
+                            .iter()
+                            .take_while(|(microcredits, _)| {
+                                let below_amount = total < amount;
+                                total = total.saturating_add(*microcredits);
+                                below_amount
+                            })
+                            .count();
+                        ensure!(
+                            total >= amount,
+                            "Insufficient funds, the account's unspent records do not add up to {amount} microcredits"
+                        );
+                        selected[index] = available.drain(..count).map(|(_, record)| record.clone()).collect();
+                        continue;
+                    }
+                },
+                Self::AvoidDust { dust_threshold } => available
+                    .iter()
+                    .rposition(|(microcredits, _)| *microcredits == amount)
+                    .or_else(|| {
+                        available.iter().rposition(|(microcredits, _)| {
+                            *microcredits >= amount && microcredits - amount >= *dust_threshold
+                        })
+                    })
+                    .or_else(|| smallest_sufficient(&available, amount)),
+            };
+            let position = chosen.ok_or_else(|| {
+                anyhow!(
+                    "Could not find enough records with the specified amounts, consider splitting records into smaller amounts"
+                )
+            })?;
+            selected[index] = vec![available.remove(position).1.clone()];
+        }
+        Ok(selected)
+    }
+
+    fn needs_all_candidates(&self) -> bool {
+        !matches!(self, Self::LargestFirst)
+    }
+}
+
+/// Find the position of the smallest record covering the amount in a list sorted largest first
+fn smallest_sufficient<N: Network>(available: &[(u64, &Record<N, Plaintext<N>>)], amount: u64) -> Option<usize> {
+    available.iter().rposition(|(microcredits, _)| *microcredits >= amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::credits_records;
+    use snarkvm_console::network::Testnet3;
+
+    fn selected_amounts(selector: CoinSelection, candidates: &[u64], amounts: &[u64]) -> Result<Vec<Vec<u64>>> {
+        let selected = selector.select(&credits_records(candidates), amounts)?;
+        Ok(selected
+            .iter()
+            .map(|records| records.iter().map(|record| record.microcredits().unwrap()).collect())
+            .collect())
+    }
+
+    #[test]
+    fn test_coin_selection_strategies() {
+        let candidates = [50, 500, 1_000, 20_000, 100_000];
+
+        let selected = selected_amounts(CoinSelection::LargestFirst, &candidates, &[100, 900]).unwrap();
+        assert_eq!(selected, vec![vec![20_000], vec![100_000]]);
+
+        let selected = selected_amounts(CoinSelection::SmallestSufficient, &candidates, &[100, 900]).unwrap();
+        assert_eq!(selected, vec![vec![500], vec![1_000]]);
+
+        let selected = selected_amounts(CoinSelection::FewestRecords, &candidates, &[120_000]).unwrap();
+        assert_eq!(selected, vec![vec![100_000, 20_000]]);
+        assert!(selected_amounts(CoinSelection::FewestRecords, &candidates, &[200_000]).is_err());
+
+        let avoid_dust = CoinSelection::AvoidDust { dust_threshold: 10_000 };
+        assert_eq!(selected_amounts(avoid_dust, &candidates, &[1_000]).unwrap(), vec![vec![1_000]]);
+        assert_eq!(selected_amounts(avoid_dust, &candidates, &[900]).unwrap(), vec![vec![20_000]]);
+        assert_eq!(selected_amounts(avoid_dust, &candidates, &[95_000]).unwrap(), vec![vec![100_000]]);
+    }
+
+    #[test]
+    fn test_single_record_strategies_fail_without_a_large_enough_record() {
+        let candidates = [40_000, 40_000, 40_000];
+        for selector in [CoinSelection::LargestFirst, CoinSelection::SmallestSufficient] {
+            assert!(selected_amounts(selector, &candidates, &[100_000]).is_err());
+            assert!(selected_amounts(selector, &candidates, &[1, 1, 1, 1]).is_err());
+        }
+        assert_eq!(CoinSelection::from_str("fewest-records").unwrap(), CoinSelection::FewestRecords);
+        assert!(!CoinSelection::FewestRecords.selects_single_records());
+        assert!(CoinSelection::SmallestSufficient.selects_single_records());
+
+        // Only the largest first strategy can choose among the first records covering the amounts
+        assert!(!CoinSelector::<Testnet3>::needs_all_candidates(&CoinSelection::LargestFirst));
+        assert!(CoinSelector::<Testnet3>::needs_all_candidates(&CoinSelection::SmallestSufficient));
+        assert!(CoinSelection::from_str("random").is_err());
+    }
+}
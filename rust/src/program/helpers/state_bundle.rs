@@ -0,0 +1,201 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use snarkvm_ledger_block::Fee;
+use snarkvm_synthesizer::Authorization;
+
+/// Number of times fetching a state bundle is attempted if a block is added to the network midway
+const STATE_BUNDLE_FETCH_ATTEMPTS: usize = 3;
+
+/// Snapshot of the network state needed to spend a set of records without network access
+///
+/// A bundle holds a global state root and the inclusion path of each record to spend against that
+/// root. It is fetched on a machine with network access, serialized to a file and loaded on an
+/// offline machine, where it takes the place of a [Query] of the network when building
+/// transactions. Transactions built with a bundle must be broadcast before the network stops
+/// accepting its state root.
+///
+/// Basic Usage:
+/// let commitment = fee_record.to_commitment(&ProgramID::from_str("credits.aleo")?, &Identifier::from_str("credits")?)?;
+/// let bundle = StateBundle::fetch(&api_client, &[commitment])?;
+/// std::fs::write("state.json", serde_json::to_string(&bundle)?)?;
+///
+/// let bundle: StateBundle<Testnet3> = serde_json::from_str(&std::fs::read_to_string("state.json")?)?;
+/// let transaction = ProgramManager::<Testnet3>::create_transfer_transaction_offline::<AleoV0>(
+///     &private_key, 100, 500_000, recipient, TransferType::Public, None, fee_record, &bundle)?;
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct StateBundle<N: Network> {
+    state_root: N::StateRoot,
+    state_paths: Vec<StatePath<N>>,
+}
+
+impl<N: Network> StateBundle<N> {
+    /// Create a bundle from a state root and inclusion paths against it
+    pub fn new(state_root: N::StateRoot, state_paths: Vec<StatePath<N>>) -> Result<Self> {
+        ensure!(
+            state_paths.iter().all(|state_path| state_path.global_state_root() == state_root),
+            "Every inclusion path of a state bundle must lead to its state root {state_root}"
+        );
+        Ok(Self { state_root, state_paths })
+    }
+
+    /// Fetch the latest state root and the inclusion paths of the given record commitments
+    pub fn fetch(api_client: &impl NetworkClient<N>, commitments: &[Field<N>]) -> Result<Self> {
+        for _ in 0..STATE_BUNDLE_FETCH_ATTEMPTS {
+            let state_root = api_client.latest_state_root()?;
+            let state_paths = commitments
+                .iter()
+                .map(|commitment| api_client.get_state_path_for_commitment(commitment))
+                .collect::<Result<Vec<_>>>()?;
+            // A block added while fetching changes the state root, in which case fetch again
+            if let Ok(bundle) = Self::new(state_root, state_paths) {
+                return Ok(bundle);
+            }
+        }
+        bail!("The state root kept changing while fetching the state bundle, please try again")
+    }
+
+    /// Get the state root the bundle was fetched at
+    pub fn state_root(&self) -> N::StateRoot {
+        self.state_root
+    }
+
+    /// Get the inclusion paths of the records the bundle allows spending
+    pub fn state_paths(&self) -> &[StatePath<N>] {
+        &self.state_paths
+    }
+
+    /// Check if the bundle holds the inclusion path of a record commitment
+    pub fn contains(&self, commitment: &Field<N>) -> bool {
+        self.state_path(commitment).is_ok()
+    }
+
+    /// Get the inclusion path of a record commitment
+    fn state_path(&self, commitment: &Field<N>) -> Result<&StatePath<N>> {
+        self.state_paths
+            .iter()
+            .find(|state_path| state_path.transition_leaf().id() == *commitment)
+            .ok_or_else(|| anyhow!("The state bundle does not hold an inclusion path for record {commitment}"))
+    }
+
+    /// Prove the execution of an authorization against the state of the bundle
+    pub(crate) fn prove_execution<A: Aleo<Network = N>>(
+        &self,
+        process: &Process<N>,
+        authorization: Authorization<N>,
+        locator: &Locator<N>,
+    ) -> Result<Execution<N>> {
+        let (_, mut trace) = process.execute::<A>(authorization)?;
+        trace.prepare(self.clone())?;
+        trace.prove_execution::<A, _>(&locator.to_string(), &mut rand::thread_rng())
+    }
+
+    /// Prove the fee of a deployment or execution against the state of the bundle
+    pub(crate) fn prove_fee<A: Aleo<Network = N>>(
+        &self,
+        process: &Process<N>,
+        private_key: &PrivateKey<N>,
+        fee_record: Record<N, Plaintext<N>>,
+        fee: u64,
+        deployment_or_execution_id: Field<N>,
+    ) -> Result<Fee<N>> {
+        let rng = &mut rand::thread_rng();
+        let (_, _, mut trace) =
+            process.execute_fee::<A, _>(private_key, fee_record, fee, deployment_or_execution_id, rng)?;
+        trace.prepare(self.clone())?;
+        trace.prove_fee::<A, _>(rng)
+    }
+}
+
+#[async_trait(?Send)]
+impl<N: Network> QueryTrait<N> for StateBundle<N> {
+    fn current_state_root(&self) -> Result<N::StateRoot> {
+        Ok(self.state_root)
+    }
+
+    async fn current_state_root_async(&self) -> Result<N::StateRoot> {
+        Ok(self.state_root)
+    }
+
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.state_path(commitment).cloned()
+    }
+
+    async fn get_state_path_for_commitment_async(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.state_path(commitment).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::setup_local_ledger, ProgramManager, TransferType};
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_transfer_built_offline_with_a_state_bundle() {
+        let (private_key, local_ledger, record_finder, _) = setup_local_ledger();
+        let (amount_record, fee_record) =
+            record_finder.find_amount_and_fee_records(100, 500_000, &private_key).unwrap();
+
+        // Fetch the inclusion paths of both records and carry the bundle over as json
+        let credits = (ProgramID::from_str("credits.aleo").unwrap(), Identifier::from_str("credits").unwrap());
+        let commitments =
+            [&amount_record, &fee_record].map(|record| record.to_commitment(&credits.0, &credits.1).unwrap());
+        let bundle = StateBundle::fetch(&local_ledger, &commitments).unwrap();
+        assert_eq!(bundle.state_root(), local_ledger.latest_state_root().unwrap());
+        assert!(commitments.iter().all(|commitment| bundle.contains(commitment)));
+        let bundle: StateBundle<Testnet3> = serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+
+        // Paths leading to another state root are refused
+        let other_root = <Testnet3 as Network>::StateRoot::default();
+        assert!(StateBundle::new(other_root, bundle.state_paths().to_vec()).is_err());
+
+        // A record without an inclusion path in the bundle cannot be spent
+        let recipient = Address::try_from(&PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let fee_only = StateBundle::new(bundle.state_root(), bundle.state_paths()[1..].to_vec()).unwrap();
+        assert!(ProgramManager::<Testnet3>::create_transfer_transaction_offline::<AleoV0>(
+            &private_key,
+            100,
+            500_000,
+            recipient,
+            TransferType::Private,
+            Some(amount_record.clone()),
+            fee_record.clone(),
+            &fee_only,
+        )
+        .is_err());
+
+        // The transaction built with the bundle is accepted by the network
+        let transaction = ProgramManager::<Testnet3>::create_transfer_transaction_offline::<AleoV0>(
+            &private_key,
+            100,
+            500_000,
+            recipient,
+            TransferType::Private,
+            Some(amount_record),
+            fee_record,
+            &bundle,
+        )
+        .unwrap();
+        local_ledger.add_transaction(transaction).unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A decrypted record owned by an account tracked in a [RecordStore]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct StoredRecord<N: Network> {
+    /// Commitment of the record
+    pub commitment: Field<N>,
+    /// Serial number revealed on chain when the record is spent
+    pub serial_number: Field<N>,
+    /// Address of the account owning the record
+    pub owner: Address<N>,
+    /// Height of the block the record was created in
+    pub block_height: u32,
+    /// The decrypted record
+    pub record: Record<N, Plaintext<N>>,
+    /// Whether the serial number of the record has been seen on chain
+    pub spent: bool,
+}
+
+/// Contents of a record store as they are written to disk
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct RecordStoreFile<N: Network> {
+    next_heights: BTreeMap<String, u32>,
+    records: Vec<StoredRecord<N>>,
+}
+
+/// In-memory state of a record store
+struct RecordStoreState<N: Network> {
+    /// Height of the next block to scan for each tracked account, keyed by address
+    next_heights: BTreeMap<String, u32>,
+    /// Records of the tracked accounts keyed by commitment
+    records: IndexMap<Field<N>, StoredRecord<N>>,
+    /// Commitments of the tracked records keyed by serial number
+    serial_numbers: IndexMap<Field<N>, Field<N>>,
+}
+
+impl<N: Network> RecordStoreState<N> {
+    fn new(next_heights: BTreeMap<String, u32>, records: Vec<StoredRecord<N>>) -> Self {
+        let serial_numbers = records.iter().map(|stored| (stored.serial_number, stored.commitment)).collect();
+        let records = records.into_iter().map(|stored| (stored.commitment, stored)).collect();
+        Self { next_heights, records, serial_numbers }
+    }
+
+    fn insert(&mut self, stored: StoredRecord<N>) {
+        self.serial_numbers.insert(stored.serial_number, stored.commitment);
+        self.records.insert(stored.commitment, stored);
+    }
+
+    fn mark_spent<'a>(&mut self, serial_numbers: impl IntoIterator<Item = &'a Field<N>>) -> usize {
+        let mut spent = 0;
+        for serial_number in serial_numbers {
+            let stored = self.serial_numbers.get(serial_number).and_then(|commitment| self.records.get_mut(commitment));
+            if let Some(stored) = stored.filter(|stored| !stored.spent) {
+                stored.spent = true;
+                spent += 1;
+            }
+        }
+        spent
+    }
+}
+
+/// Local store of the records owned by one or more accounts
+///
+/// The store remembers the height up to which the chain has been scanned for each account, so
+/// [RecordStore::sync] only fetches the blocks produced since the last sync. Records found are
+/// decrypted and stored alongside their serial numbers, and are marked as spent once their serial
+/// number appears in a later block. Accounts are tracked by address, so neither private keys nor
+/// view keys are written to disk.
+///
+/// A [RecordFinder] given a store searches it for records instead of rescanning the chain.
+///
+/// Basic Usage:
+/// let record_store = RecordStore::<Testnet3>::open("records.json")?;
+/// let record_finder = RecordFinder::new(AleoAPIClient::testnet3()).with_record_store(record_store);
+/// let fee_record = record_finder.find_one_record(&private_key, 500_000)?;
+#[derive(Clone)]
+pub struct RecordStore<N: Network> {
+    path: Option<PathBuf>,
+    state: Arc<Mutex<RecordStoreState<N>>>,
+    sync_lock: Arc<Mutex<()>>,
+    save_lock: Arc<Mutex<()>>,
+}
+
+impl<N: Network> Default for RecordStore<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Network> RecordStore<N> {
+    /// Create a record store that is only kept in memory
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            state: Arc::new(Mutex::new(RecordStoreState::new(BTreeMap::new(), vec![]))),
+            sync_lock: Arc::new(Mutex::new(())),
+            save_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Open the record store at the given path, creating a new one if the file does not exist.
+    /// The store is written back to the file each time it changes.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let state = if path.exists() {
+            let file: RecordStoreFile<N> = serde_json::from_reader(File::open(&path)?)
+                .map_err(|e| anyhow!("Failed to read the record store at {}: {e}", path.display()))?;
+            RecordStoreState::new(file.next_heights, file.records)
+        } else {
+            RecordStoreState::new(BTreeMap::new(), vec![])
+        };
+        Ok(Self {
+            path: Some(path),
+            state: Arc::new(Mutex::new(state)),
+            sync_lock: Arc::new(Mutex::new(())),
+            save_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// Get the path the store is persisted to, if any
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Get the height of the next block to scan for an account, which is 0 for untracked accounts
+    pub fn next_height(&self, address: &Address<N>) -> Result<u32> {
+        Ok(self.state()?.next_heights.get(&address.to_string()).copied().unwrap_or(0))
+    }
+
+    /// Get a stored record by its commitment
+    pub fn get_record(&self, commitment: &Field<N>) -> Result<Option<StoredRecord<N>>> {
+        Ok(self.state()?.records.get(commitment).cloned())
+    }
+
+    /// Get all records stored for an account in the order they were created
+    pub fn records(&self, address: &Address<N>) -> Result<Vec<StoredRecord<N>>> {
+        Ok(self.state()?.records.values().filter(|stored| stored.owner == *address).cloned().collect())
+    }
+
+    /// Get the records stored for an account that have not been spent, in the order they were created
+    pub fn unspent_records(&self, address: &Address<N>) -> Result<Vec<StoredRecord<N>>> {
+        Ok(self.records(address)?.into_iter().filter(|stored| !stored.spent).collect())
+    }
+
+    /// Add a record owned by the private key to the store
+    pub fn insert_record(
+        &self,
+        private_key: &PrivateKey<N>,
+        commitment: Field<N>,
+        record: Record<N, Plaintext<N>>,
+        block_height: u32,
+    ) -> Result<()> {
+        let stored = StoredRecord {
+            commitment,
+            serial_number: Record::<N, Plaintext<N>>::serial_number(*private_key, commitment)?,
+            owner: Address::try_from(private_key)?,
+            block_height,
+            record,
+            spent: false,
+        };
+        self.state()?.insert(stored);
+        self.save()
+    }
+
+    /// Mark the records with the given serial numbers as spent, returning how many were marked
+    pub fn mark_spent<'a>(&self, serial_numbers: impl IntoIterator<Item = &'a Field<N>>) -> Result<usize> {
+        let spent = self.state()?.mark_spent(serial_numbers);
+        if spent > 0 {
+            self.save()?;
+        }
+        Ok(spent)
+    }
+
+    /// Scan the blocks produced since the last sync of the account for new records owned by the
+    /// private key and for the serial numbers of spent records. Progress is saved after every
+    /// window of blocks, so an interrupted sync resumes where it stopped.
+    pub fn sync<C: NetworkClient<N>>(&self, api_client: &C, private_key: &PrivateKey<N>) -> Result<()> {
+        let _guard = self.sync_lock.lock().map_err(|_| anyhow!("The record store sync lock was poisoned"))?;
+
+        let view_key = ViewKey::try_from(private_key)?;
+        let address = view_key.to_address();
+        let address_x_coordinate = address.to_x_coordinate();
+
+        let end_height = api_client.latest_height()?.saturating_add(1);
+        let mut start_height = self.next_height(&address)?;
+        while start_height < end_height {
+            let window_end = start_height.saturating_add(50).min(end_height);
+            let blocks = api_client.get_blocks(start_height, window_end)?;
+
+            let mut state = self.state()?;
+            for block in blocks {
+                let block_height = block.height();
+                let serial_numbers = block.serial_numbers().copied().collect::<Vec<_>>();
+                for (commitment, record) in block.into_records() {
+                    if record.is_owner_with_address_x_coordinate(&view_key, &address_x_coordinate) {
+                        state.insert(StoredRecord {
+                            commitment,
+                            serial_number: Record::<N, Plaintext<N>>::serial_number(*private_key, commitment)?,
+                            owner: address,
+                            block_height,
+                            record: record.decrypt(&view_key)?,
+                            spent: false,
+                        });
+                    }
+                }
+                // Records can be spent in the block they are created in, so mark spent records last
+                state.mark_spent(&serial_numbers);
+            }
+            state.next_heights.insert(address.to_string(), window_end);
+            drop(state);
+
+            self.save()?;
+            start_height = window_end;
+        }
+        Ok(())
+    }
+
+    /// Lock the state of the store
+    fn state(&self) -> Result<MutexGuard<'_, RecordStoreState<N>>> {
+        self.state.lock().map_err(|_| anyhow!("The record store lock was poisoned"))
+    }
+
+    /// Write the store to its file, if it has one. Saves are serialized from the copy of the state
+    /// to the write, so an older copy never overwrites a newer one.
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let _guard = self.save_lock.lock().map_err(|_| anyhow!("The record store save lock was poisoned"))?;
+        let state = self.state()?;
+        let file = RecordStoreFile {
+            next_heights: state.next_heights.clone(),
+            records: state.records.values().cloned().collect(),
+        };
+        drop(state);
+
+        // Write to a temporary file first so an interrupted write never corrupts the store. The
+        // temporary file is unique to the writer, so processes sharing the store never write to
+        // the same file.
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let temp_path =
+            path.with_file_name(format!("{file_name}.{}-{:016x}.tmp", std::process::id(), rand::random::<u64>()));
+        File::create(&temp_path)
+            .and_then(|temp_file| Ok(serde_json::to_writer(temp_file, &file)?))
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                anyhow!("Failed to save the record store at {}: {e}", path.display())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{credits_record, random_program_id, setup_local_ledger, RECIPIENT_PRIVATE_KEY},
+        TransferType,
+    };
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_record_store_tracks_spent_records() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let store = RecordStore::<Testnet3>::new();
+
+        let (first, second) = (Field::from_u64(1), Field::from_u64(2));
+        store.insert_record(&private_key, first, credits_record(&private_key, 100), 1).unwrap();
+        store.insert_record(&private_key, second, credits_record(&private_key, 200), 2).unwrap();
+        assert_eq!(store.unspent_records(&address).unwrap().len(), 2);
+
+        // Only the record whose serial number was revealed is marked as spent
+        let serial_number = store.get_record(&first).unwrap().unwrap().serial_number;
+        assert_eq!(store.mark_spent([&serial_number, &Field::from_u64(3)]).unwrap(), 1);
+        assert_eq!(store.mark_spent([&serial_number]).unwrap(), 0);
+        let unspent = store.unspent_records(&address).unwrap();
+        assert_eq!(unspent.len(), 1);
+        assert_eq!(unspent[0].commitment, second);
+        assert_eq!(store.records(&address).unwrap().len(), 2);
+
+        // Records of other accounts are not returned
+        let other_private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        assert!(store.records(&Address::try_from(&other_private_key).unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_record_store_persists_to_disk() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let directory = std::env::temp_dir().join(format!("record_store_{}", random_program_id(10)));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("records.json");
+
+        let store = RecordStore::<Testnet3>::open(&path).unwrap();
+        assert_eq!(store.next_height(&address).unwrap(), 0);
+        store.insert_record(&private_key, Field::from_u64(1), credits_record(&private_key, 100), 5).unwrap();
+        store.state().unwrap().next_heights.insert(address.to_string(), 6);
+        store.save().unwrap();
+
+        let reopened = RecordStore::<Testnet3>::open(&path).unwrap();
+        assert_eq!(reopened.next_height(&address).unwrap(), 6);
+        assert_eq!(reopened.records(&address).unwrap(), store.records(&address).unwrap());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_record_store_syncs_new_blocks() {
+        let (private_key, local_ledger, _, program_manager) = setup_local_ledger();
+        let address = Address::try_from(&private_key).unwrap();
+        let store = RecordStore::<Testnet3>::new();
+
+        // The first sync scans every block up to the latest one
+        store.sync(&local_ledger, &private_key).unwrap();
+        let latest_height = local_ledger.latest_height().unwrap();
+        assert_eq!(store.next_height(&address).unwrap(), latest_height + 1);
+        let genesis_records = store.unspent_records(&address).unwrap();
+        assert!(genesis_records.len() >= 2);
+
+        // A sync without new blocks leaves the store as it is
+        store.sync(&local_ledger, &private_key).unwrap();
+        assert_eq!(store.next_height(&address).unwrap(), latest_height + 1);
+        assert_eq!(store.records(&address).unwrap(), genesis_records);
+
+        // Spend two of the records in a transfer, the next sync only scans the block it is in
+        let recipient = Address::try_from(&PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let (amount_record, fee_record) = (&genesis_records[0], &genesis_records[1]);
+        program_manager
+            .transfer(
+                1_000_000,
+                5_000_000,
+                recipient,
+                TransferType::Private,
+                None,
+                Some(amount_record.record.clone()),
+                fee_record.record.clone(),
+            )
+            .unwrap();
+        store.sync(&local_ledger, &private_key).unwrap();
+        assert_eq!(store.next_height(&address).unwrap(), latest_height + 2);
+
+        // The spent records are marked as spent and the change of the transfer and its fee is added
+        let records = store.records(&address).unwrap();
+        let spent = records.iter().filter(|stored| stored.spent).map(|stored| stored.commitment).collect::<Vec<_>>();
+        assert_eq!(spent, vec![amount_record.commitment, fee_record.commitment]);
+        assert_eq!(records.len(), genesis_records.len() + 2);
+        assert!(records[genesis_records.len()..].iter().all(|stored| stored.block_height == latest_height + 1));
+    }
+}
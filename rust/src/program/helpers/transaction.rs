@@ -0,0 +1,140 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Fee paid by a transaction in microcredits, split into what it pays for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    /// Cost of storing the deployment or execution, one microcredit per byte
+    pub storage_cost: u64,
+    /// Cost of the program name of a deployment, 0 for executions
+    pub namespace_cost: u64,
+    /// Cost of running the finalize blocks of an execution, 0 for deployments
+    pub finalize_cost: u64,
+    /// Fee paid on top of the minimum cost of the transaction
+    pub priority_fee: u64,
+    /// Total fee paid by the transaction
+    pub total_fee: u64,
+}
+
+impl FeeBreakdown {
+    /// Break down the fee paid by a transaction created with the given priority fee. Executions
+    /// that do not pay a fee, such as splits, cost nothing.
+    pub fn from_transaction<N: Network>(transaction: &Transaction<N>, priority_fee: u64) -> Result<Self> {
+        let total_fee = *transaction.fee()?;
+        match transaction {
+            Transaction::Deploy(_, _, deployment, _) => {
+                let (_, (storage_cost, namespace_cost)) = deployment_cost(deployment)?;
+                Ok(Self { storage_cost, namespace_cost, finalize_cost: 0, priority_fee, total_fee })
+            }
+            Transaction::Execute(_, execution, Some(_)) => {
+                // The finalize cost is what remains of the fee once storage and priority are paid for
+                let storage_cost = execution.size_in_bytes()?;
+                let finalize_cost = total_fee.saturating_sub(storage_cost).saturating_sub(priority_fee);
+                Ok(Self { storage_cost, namespace_cost: 0, finalize_cost, priority_fee, total_fee })
+            }
+            Transaction::Execute(_, _, None) => Ok(Self::default()),
+            Transaction::Fee(..) => Ok(Self { total_fee, ..Self::default() }),
+        }
+    }
+}
+
+/// A transaction built by the [ProgramManager] without broadcasting it, so it can be reviewed,
+/// stored or broadcast later
+///
+/// Basic Usage:
+/// let built = program_manager.build_transfer(amount, fee, recipient, TransferType::Private, None, Some(amount_record), fee_record)?;
+/// println!("Transaction {} pays a fee of {} microcredits", built.id(), built.fee().total_fee);
+/// program_manager.broadcast_transaction(built.into_transaction())?;
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltTransaction<N: Network> {
+    transaction: Transaction<N>,
+    fee: FeeBreakdown,
+}
+
+impl<N: Network> BuiltTransaction<N> {
+    /// Wrap a transaction created with the given priority fee, breaking down the fee it pays
+    pub fn new(transaction: Transaction<N>, priority_fee: u64) -> Result<Self> {
+        let fee = FeeBreakdown::from_transaction(&transaction, priority_fee)?;
+        Ok(Self { transaction, fee })
+    }
+
+    /// Get the ID of the transaction
+    pub fn id(&self) -> N::TransactionID {
+        self.transaction.id()
+    }
+
+    /// Get the transaction
+    pub fn transaction(&self) -> &Transaction<N> {
+        &self.transaction
+    }
+
+    /// Get the breakdown of the fee paid by the transaction
+    pub fn fee(&self) -> &FeeBreakdown {
+        &self.fee
+    }
+
+    /// Get the transaction, consuming the built transaction
+    pub fn into_transaction(self) -> Transaction<N> {
+        self.transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{random_program, test_utils::setup_local_ledger, TransferType};
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_fee_breakdown() {
+        let (private_key, _, record_finder, mut program_manager) = setup_local_ledger();
+        let priority_fee = 1_000;
+        let mut records = record_finder.find_record_amounts(vec![1, 1, 1], &private_key).unwrap();
+
+        // A deployment pays for its storage and program name on top of the priority fee
+        let program = random_program();
+        program_manager.add_program(&program).unwrap();
+        let deployment = program_manager.build_deployment(program.id(), priority_fee, records.remove(0), None).unwrap();
+        let fee = deployment.fee();
+        assert!(fee.storage_cost > 0 && fee.namespace_cost > 0);
+        assert_eq!(fee.finalize_cost, 0);
+        assert_eq!(fee.priority_fee, priority_fee);
+        assert_eq!(fee.storage_cost + fee.namespace_cost + fee.priority_fee, fee.total_fee);
+        assert_eq!(deployment.id(), deployment.transaction().id());
+
+        // A public transfer pays for its storage and finalize block on top of the priority fee
+        let recipient = Address::try_from(&PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let execution = program_manager
+            .build_transfer(100, priority_fee, recipient, TransferType::Public, None, None, records.remove(0))
+            .unwrap();
+        let fee = execution.fee();
+        assert!(fee.storage_cost > 0 && fee.finalize_cost > 0);
+        assert_eq!(fee.namespace_cost, 0);
+        assert_eq!(fee.priority_fee, priority_fee);
+        assert_eq!(fee.storage_cost + fee.finalize_cost + fee.priority_fee, fee.total_fee);
+        assert_eq!(FeeBreakdown::from_transaction(execution.transaction(), priority_fee).unwrap(), *fee);
+
+        // A split does not pay a fee
+        let split = program_manager.build_split(1, records.remove(0), None).unwrap();
+        assert_eq!(*split.fee(), FeeBreakdown::default());
+        let id = split.id();
+        assert_eq!(split.into_transaction().id(), id);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Join two credits records into a single record holding the credits of both, paying the
+    /// specified fee from the fee record. Specify 0 for no fee.
+    pub fn join_records(
+        &self,
+        first: Record<N, Plaintext<N>>,
+        second: Record<N, Plaintext<N>>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<String> {
+        let transaction = self.build_join(first, second, fee, fee_record, password)?;
+        self.broadcast_transaction(transaction.into_transaction())
+    }
+
+    /// Build a transaction joining two credits records without broadcasting it
+    pub fn build_join(
+        &self,
+        first: Record<N, Plaintext<N>>,
+        second: Record<N, Plaintext<N>>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<BuiltTransaction<N>> {
+        // Ensure the fee record has enough credits to pay the fee
+        ensure!(fee_record.microcredits()? >= fee, "The fee record must hold at least the fee");
+
+        // Specify the network state query
+        let query = Query::from(self.api_client()?.base_url());
+
+        // Retrieve the private key.
+        let private_key = self.get_private_key(password)?;
+
+        // Generate the execution transaction
+        let execution = {
+            let rng = &mut rand::thread_rng();
+
+            // Initialize a VM
+            let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+            let vm = VM::from(store)?;
+
+            // Create a new transaction.
+            let inputs = [Value::Record(first), Value::Record(second)];
+            vm.execute(
+                &private_key,
+                ("credits.aleo", "join"),
+                inputs.iter(),
+                Some((fee_record, fee)),
+                Some(query),
+                rng,
+            )?
+        };
+
+        BuiltTransaction::new(execution, fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::setup_local_ledger;
+
+    #[test]
+    fn test_join_records() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+
+        let fee = 500_000;
+        let mut records = record_finder.find_record_amounts(vec![1, 1, fee], &private_key).unwrap();
+        let (fee_record, second, first) = (records.remove(2), records.remove(1), records.remove(0));
+        let total = first.microcredits().unwrap() + second.microcredits().unwrap();
+
+        // A fee record that cannot cover the fee is rejected before any proving work
+        let error = program_manager
+            .build_join(first.clone(), second.clone(), fee_record.microcredits().unwrap() + 1, fee_record.clone(), None)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "The fee record must hold at least the fee");
+
+        // The joined records are spent and a record holding the credits of both is created
+        program_manager.join_records(first.clone(), second.clone(), fee, fee_record.clone(), None).unwrap();
+        let latest_height = local_ledger.latest_height().unwrap();
+        let unspent = local_ledger.get_unspent_records(&private_key, 0..latest_height + 1, None, None).unwrap();
+        assert!(unspent.iter().all(|(_, record)| record != &first && record != &second && record != &fee_record));
+        assert!(unspent.iter().any(|(_, record)| record.microcredits().unwrap() == total));
+    }
+}
@@ -18,6 +18,12 @@
 
 use super::*;
 
+pub mod authorize;
+pub use authorize::*;
+
+pub mod consolidate;
+pub use consolidate::*;
+
 pub mod deploy;
 pub use deploy::*;
 
@@ -27,37 +33,53 @@ pub use execute::*;
 pub mod helpers;
 pub use helpers::*;
 
+pub mod join;
+pub use join::*;
+
 pub mod network;
 pub use network::*;
 
 pub mod resolver;
 pub use resolver::*;
 
+pub mod simulate;
+pub use simulate::*;
+
+pub mod split;
+pub use split::*;
+
 pub mod transfer;
 pub use transfer::*;
 
+pub mod verify;
+pub use verify::*;
+
 /// Program management object for loading programs for building, execution, and deployment
 ///
 /// This object is meant to be a software abstraction that can be consumed by software like
 /// CLI tools, IDE plugins, Server-side stack components and other software that needs to
 /// interact with the Aleo network.
+///
+/// The program manager talks to the network through any [NetworkClient] implementation, which
+/// defaults to the [AleoAPIClient].
 #[derive(Clone)]
-pub struct ProgramManager<N: Network> {
+pub struct ProgramManager<N: Network, C: NetworkClient<N> = AleoAPIClient<N>> {
     pub(crate) programs: IndexMap<ProgramID<N>, Program<N>>,
     pub(crate) private_key: Option<PrivateKey<N>>,
     pub(crate) private_key_ciphertext: Option<Ciphertext<N>>,
     pub(crate) local_program_directory: Option<PathBuf>,
-    pub(crate) api_client: Option<AleoAPIClient<N>>,
+    pub(crate) api_client: Option<C>,
+    pub(crate) key_cache: Option<KeyCache>,
 }
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
     /// Create a new program manager by specifying custom options for the private key (or private
     /// key ciphertext) and resolver. Use this method if you want to create a custom resolver
     /// (i.e. one that searches a local or remote database) for program and record resolution.
     pub fn new(
         private_key: Option<PrivateKey<N>>,
         private_key_ciphertext: Option<Ciphertext<N>>,
-        api_client: Option<AleoAPIClient<N>>,
+        api_client: Option<C>,
         local_program_directory: Option<PathBuf>,
     ) -> Result<Self> {
         if private_key.is_some() && private_key_ciphertext.is_some() {
@@ -66,7 +88,19 @@ impl<N: Network> ProgramManager<N> {
             bail!("Must have either private key or private key ciphertext");
         }
         let programs = IndexMap::new();
-        Ok(Self { programs, private_key, private_key_ciphertext, local_program_directory, api_client })
+        Ok(Self { programs, private_key, private_key_ciphertext, local_program_directory, api_client, key_cache: None })
+    }
+
+    /// Reuse the proving and verifying keys of program functions across executions by keeping
+    /// them in a key cache
+    pub fn with_key_cache(mut self, key_cache: KeyCache) -> Self {
+        self.key_cache = Some(key_cache);
+        self
+    }
+
+    /// Get the key cache the keys of program functions are kept in, if any
+    pub fn key_cache(&self) -> Option<&KeyCache> {
+        self.key_cache.as_ref()
     }
 
     /// Manually add a program to the program manager from memory if it does not already exist
@@ -80,18 +114,28 @@ impl<N: Network> ProgramManager<N> {
 
     /// Initialize a SnarkVM instance with a program and its imports
     pub fn initialize_vm(
-        api_client: &AleoAPIClient<N>,
+        api_client: &C,
+        program: &Program<N>,
+        initialize_execution: bool,
+    ) -> Result<VM<N, ConsensusMemory<N>>> {
+        let imports = api_client.get_program_imports_from_source(program)?;
+        Self::initialize_offline_vm(program, imports.values(), initialize_execution)
+    }
+
+    /// Initialize a SnarkVM instance with a program and its imports without fetching anything from
+    /// the network. The imports must be given in the order they are to be added, dependencies first.
+    pub fn initialize_offline_vm<'a>(
         program: &Program<N>,
+        imports: impl IntoIterator<Item = &'a Program<N>>,
         initialize_execution: bool,
     ) -> Result<VM<N, ConsensusMemory<N>>> {
         // Create an ephemeral SnarkVM to store the programs
-        // Initialize an RNG and query object for the transaction
         let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
         let vm = VM::<N, ConsensusMemory<N>>::from(store)?;
 
-        // Resolve imports
+        // Add the imports
         let credits_id = ProgramID::<N>::from_str("credits.aleo")?;
-        api_client.get_program_imports_from_source(program)?.iter().try_for_each(|(_, import)| {
+        imports.into_iter().try_for_each(|import| {
             if import.id() != &credits_id {
                 vm.process().write().add_program(import)?
             }
@@ -100,7 +144,7 @@ impl<N: Network> ProgramManager<N> {
 
         // If the initialization is for an execution, add the program. Otherwise, don't add it as
         // it will be added during the deployment process
-        if initialize_execution {
+        if initialize_execution && !vm.contains_program(program.id()) {
             vm.process().write().add_program(program)?;
         }
         Ok(vm)
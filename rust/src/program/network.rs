@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
     /// Broadcast a transaction to the network
     pub fn broadcast_transaction(&self, transaction: Transaction<N>) -> Result<String> {
         let transaction_type = if let Transaction::Deploy(..) = &transaction { "Deployment" } else { "Execute" };
@@ -30,8 +30,32 @@ impl<N: Network> ProgramManager<N> {
         result
     }
 
+    /// Wait for a broadcast transaction to be confirmed or rejected on chain, polling the network
+    /// every `poll_interval` until the `timeout` elapses
+    pub fn wait_for_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<TransactionStatus<N>> {
+        println!("⏳ Waiting for transaction {transaction_id} to be included in a block..");
+        let status = self.api_client()?.wait_for_transaction(transaction_id, timeout, poll_interval)?;
+        match &status {
+            TransactionStatus::Confirmed { block_height, .. } => {
+                println!("✅ Transaction {transaction_id} confirmed in block {block_height}")
+            }
+            TransactionStatus::Rejected { block_height, .. } => {
+                println!("❌ Transaction {transaction_id} rejected in block {block_height}")
+            }
+            TransactionStatus::TimedOut => {
+                println!("❌ Transaction {transaction_id} was not found on chain after {}s", timeout.as_secs())
+            }
+        }
+        Ok(status)
+    }
+
     /// Get a reference to the configured API client
-    pub fn api_client(&self) -> Result<&AleoAPIClient<N>> {
+    pub fn api_client(&self) -> Result<&C> {
         self.api_client.as_ref().ok_or_else(|| anyhow!("No API client found"))
     }
 
@@ -39,15 +63,13 @@ impl<N: Network> ProgramManager<N> {
     /// if it is the same as the local version
     pub fn on_chain_program_state(&self, program: &Program<N>) -> Result<OnChainProgramState> {
         let program_id = program.id();
-        Ok(self
-            .api_client()?
-            .get_program(program_id)
-            .map(
-                |chain_program| {
-                    if chain_program.eq(program) { OnChainProgramState::Same } else { OnChainProgramState::Different }
-                },
-            )
-            .unwrap_or(OnChainProgramState::NotDeployed))
+        match self.api_client()?.get_program(program_id) {
+            Ok(chain_program) => {
+                Ok(if chain_program.eq(program) { OnChainProgramState::Same } else { OnChainProgramState::Different })
+            }
+            Err(error) if ApiError::is_not_found(&error) => Ok(OnChainProgramState::NotDeployed),
+            Err(error) => bail!("❌ Could not check the on-chain state of program {program_id}: {error}"),
+        }
     }
 
     /// Check the value of an on-chain mapping
@@ -57,15 +79,19 @@ impl<N: Network> ProgramManager<N> {
         mapping_name: impl TryInto<Identifier<N>>,
         key: impl TryInto<Plaintext<N>>,
     ) -> Result<Value<N>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let mapping_name = mapping_name.try_into().map_err(|_| anyhow!("Invalid mapping name"))?;
+        let key = key.try_into().map_err(|_| anyhow!("Invalid key"))?;
         let api_client = self.api_client()?;
-        let mapping_value = api_client.get_mapping_value(program_id, mapping_name, key)?;
+        let mapping_value = api_client.get_mapping_value(&program_id, &mapping_name, &key)?;
         Ok(mapping_value)
     }
 
     /// Check the mappings available in a program
     pub fn get_mappings(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<Vec<Identifier<N>>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
         let api_client = self.api_client()?;
-        let mappings = api_client.get_program_mappings(program_id)?;
+        let mappings = api_client.get_program_mappings(&program_id)?;
         Ok(mappings)
     }
 }
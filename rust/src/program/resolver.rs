@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
     /// Find a program by first looking on disk, and if not found, on the aleo network
     pub fn find_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
         self.find_program_on_disk(program_id).or_else(|_| self.find_program_on_chain(program_id))
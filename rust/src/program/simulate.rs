@@ -0,0 +1,226 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_ledger_store::{
+    helpers::{memory::FinalizeMemory, MapRead},
+    FinalizeStorage, FinalizeStore,
+};
+use snarkvm_synthesizer::program::{FinalizeGlobalState, FinalizeOperation, FinalizeStoreTrait};
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Simulate the finalize blocks of a program function against an in-memory mapping store,
+    /// without proving the execution or interacting with the Aleo network.
+    ///
+    /// The function is executed with the given inputs and the finalize blocks of its transitions
+    /// are run against the mappings of the program and its imports, seeded from the given store.
+    /// The simulation reports the writes the finalize blocks make to the mappings, or the reason
+    /// they fail, such as a failed assertion. The given store is left unchanged; the writes of a
+    /// successful simulation can be applied to it with [MappingStore::apply].
+    ///
+    /// The finalize blocks see the given block height and a zero block round. The imports of the
+    /// program must be given in the order they are to be added, dependencies first.
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate_finalize<A: Aleo<Network = N>>(
+        &self,
+        private_key: &PrivateKey<N>,
+        program: &Program<N>,
+        function: impl TryInto<Identifier<N>>,
+        imports: &[Program<N>],
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        mappings: &MappingStore<N>,
+        block_height: u32,
+    ) -> Result<FinalizeSimulation<N>> {
+        // Check that the function exists in the program
+        let function_name = function.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        let program_id = program.id();
+        ensure!(
+            program.contains_function(&function_name),
+            "Program {program_id:?} does not contain function {function_name:?}, aborting simulation"
+        );
+
+        // Execute the function without proving it
+        let rng = &mut rand::thread_rng();
+        let vm = Self::initialize_offline_vm(program, imports, true)?;
+        if let Some(key_cache) = self.key_cache() {
+            key_cache.load(&vm.process().read(), program)?;
+        }
+        let authorization = vm.authorize(private_key, program_id, function_name, inputs, rng)?;
+        let (response, trace) = vm.process().read().execute::<A>(authorization)?;
+        if let Some(key_cache) = self.key_cache() {
+            key_cache.store(&vm.process().read(), program)?;
+        }
+        let execution = Execution::from(trace.transitions().iter().cloned(), Default::default(), None)?;
+
+        // Initialize the mappings of every program the execution can reach and seed their entries
+        let storage = FinalizeMemory::<N>::open(None)?;
+        let store = FinalizeStore::from(storage.clone())?;
+        let process = vm.process();
+        let process = process.read();
+        let credits = process.get_program(ProgramID::<N>::from_str("credits.aleo")?)?;
+        let mut mapping_names = vec![];
+        for reachable in std::iter::once(credits).chain(imports).chain(std::iter::once(program)) {
+            for mapping_name in reachable.mappings().keys() {
+                if !mapping_names.contains(&(*reachable.id(), *mapping_name)) {
+                    store.initialize_mapping(reachable.id(), mapping_name)?;
+                    mapping_names.push((*reachable.id(), *mapping_name));
+                }
+            }
+        }
+        for (seeded_id, seeded_name) in mappings.mappings() {
+            ensure!(
+                mapping_names.contains(&(*seeded_id, *seeded_name)),
+                "Mapping {seeded_name} is not defined in {seeded_id} or in any program the simulation can reach"
+            );
+            for (key, value) in mappings.entries(seeded_id, seeded_name).unwrap_or_default() {
+                store.insert_key_value(seeded_id, seeded_name, key.clone(), value.clone())?;
+            }
+        }
+
+        // Run the finalize blocks, which leave the mappings untouched if any of them fails
+        let state = FinalizeGlobalState::new::<N>(0, block_height, 0, 0, N::BlockHash::default())?;
+        let operations = match process.finalize_execution(state, &store, &execution) {
+            Ok(operations) => operations,
+            Err(error) => {
+                return Ok(FinalizeSimulation::new(response.outputs().to_vec(), vec![], Some(error.to_string())));
+            }
+        };
+
+        // Resolve the written keys and their final values, keeping one write per key
+        let mut writes: Vec<MappingWrite<N>> = vec![];
+        for operation in operations {
+            let (mapping_id, key_id) = match operation {
+                FinalizeOperation::InsertKeyValue(mapping_id, key_id, _)
+                | FinalizeOperation::UpdateKeyValue(mapping_id, _, key_id, _) => (mapping_id, key_id),
+                _ => continue,
+            };
+            let (program_id, mapping_name) = mapping_names
+                .iter()
+                .find(|(program_id, mapping_name)| {
+                    matches!(storage.get_mapping_id_confirmed(program_id, mapping_name), Ok(Some(id)) if id == mapping_id)
+                })
+                .copied()
+                .ok_or_else(|| anyhow!("The simulation wrote to an unknown mapping {mapping_id}"))?;
+            let key = match storage.key_map().get_confirmed(&key_id)? {
+                Some(key) => key.into_owned(),
+                None => bail!("The simulation wrote to an unknown key in {program_id}/{mapping_name}"),
+            };
+            let value = storage
+                .get_value_from_key_id_confirmed(&key_id)?
+                .ok_or_else(|| anyhow!("The simulation removed {program_id}/{mapping_name}[{key}]"))?;
+            match writes
+                .iter_mut()
+                .find(|write| write.program_id == program_id && write.mapping_name == mapping_name && write.key == key)
+            {
+                Some(write) => write.value = value,
+                None => {
+                    let previous = mappings.get(&program_id, &mapping_name, &key).cloned();
+                    writes.push(MappingWrite { program_id, mapping_name, key, previous, value });
+                }
+            }
+        }
+        Ok(FinalizeSimulation::new(response.outputs().to_vec(), writes, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::RECIPIENT_PRIVATE_KEY, FINALIZE_TEST_PROGRAM};
+    use snarkvm::circuit::AleoV0;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_simulate_finalize() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program = Program::<Testnet3>::from_str(FINALIZE_TEST_PROGRAM).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let mut mappings = MappingStore::new();
+        mappings
+            .insert(
+                "finalize_test.aleo",
+                "monotonic_counter",
+                Plaintext::from_str("1u32").unwrap(),
+                Value::from_str("5u32").unwrap(),
+            )
+            .unwrap();
+
+        // A successful finalize block reports its writes against the seeded values
+        let simulation = program_manager
+            .simulate_finalize::<AleoV0>(
+                &private_key,
+                &program,
+                "increase_counter",
+                &[],
+                ["1u32", "3u32"].into_iter(),
+                &mappings,
+                1,
+            )
+            .unwrap();
+        assert!(simulation.is_success());
+        assert_eq!(simulation.writes().len(), 1);
+        assert_eq!(simulation.writes()[0].key, Plaintext::from_str("1u32").unwrap());
+        assert_eq!(simulation.writes()[0].previous, Some(Value::from_str("5u32").unwrap()));
+        assert_eq!(simulation.writes()[0].value, Value::from_str("8u32").unwrap());
+        mappings.apply(simulation.writes());
+
+        // An overflowing counter fails the finalize block and writes nothing
+        mappings
+            .insert(
+                "finalize_test.aleo",
+                "monotonic_counter",
+                Plaintext::from_str("1u32").unwrap(),
+                Value::from_str(&format!("{}u32", u32::MAX)).unwrap(),
+            )
+            .unwrap();
+        let simulation = program_manager
+            .simulate_finalize::<AleoV0>(
+                &private_key,
+                &program,
+                "increase_counter",
+                &[],
+                ["1u32", "3u32"].into_iter(),
+                &mappings,
+                1,
+            )
+            .unwrap();
+        assert!(!simulation.is_success());
+        assert!(simulation.failure().is_some());
+        assert!(simulation.writes().is_empty());
+
+        // Seeding a mapping the program does not define is an error
+        mappings
+            .insert(
+                "finalize_test.aleo",
+                "missing",
+                Plaintext::from_str("1u32").unwrap(),
+                Value::from_str("1u32").unwrap(),
+            )
+            .unwrap();
+        assert!(program_manager
+            .simulate_finalize::<AleoV0>(
+                &private_key,
+                &program,
+                "increase_counter",
+                &[],
+                ["1u32", "3u32"].into_iter(),
+                &mappings,
+                1,
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Split a credits record into a record holding the specified amount and a record holding the
+    /// rest of its credits. Splits do not pay a fee.
+    pub fn split_record(&self, amount: u64, record: Record<N, Plaintext<N>>, password: Option<&str>) -> Result<String> {
+        let transaction = self.build_split(amount, record, password)?;
+        self.broadcast_transaction(transaction.into_transaction())
+    }
+
+    /// Build a transaction splitting a credits record without broadcasting it
+    pub fn build_split(
+        &self,
+        amount: u64,
+        record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<BuiltTransaction<N>> {
+        // Ensure the record has enough credits to split the amount off
+        ensure!(amount > 0, "The amount to split must be greater than 0 microcredits");
+        ensure!(
+            record.microcredits()? > amount,
+            "Credits in the record to split must be greater than the amount specified"
+        );
+
+        // Specify the network state query
+        let query = Query::from(self.api_client()?.base_url());
+
+        // Retrieve the private key.
+        let private_key = self.get_private_key(password)?;
+
+        // Generate the execution transaction
+        let execution = {
+            let rng = &mut rand::thread_rng();
+
+            // Initialize a VM
+            let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+            let vm = VM::from(store)?;
+
+            // Create a new transaction without a fee
+            let inputs = [Value::Record(record), amount.to_value()?];
+            vm.execute(&private_key, ("credits.aleo", "split"), inputs.iter(), None, Some(query), rng)?
+        };
+
+        BuiltTransaction::new(execution, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::setup_local_ledger;
+
+    #[test]
+    fn test_split_record() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+
+        let record = record_finder.find_one_record(&private_key, 1).unwrap();
+        let microcredits = record.microcredits().unwrap();
+
+        // Amounts that would leave either record empty are rejected before any proving work
+        assert!(program_manager.build_split(0, record.clone(), None).is_err());
+        assert!(program_manager.build_split(microcredits, record.clone(), None).is_err());
+
+        // The split record is spent and two records dividing its credits are created
+        let amount = 1_000_000;
+        program_manager.split_record(amount, record.clone(), None).unwrap();
+        let latest_height = local_ledger.latest_height().unwrap();
+        let unspent = local_ledger.get_unspent_records(&private_key, 0..latest_height + 1, None, None).unwrap();
+        assert!(unspent.iter().all(|(_, found)| found != &record));
+        assert!(unspent.iter().any(|(_, found)| found.microcredits().unwrap() == amount));
+        assert!(unspent.iter().any(|(_, found)| found.microcredits().unwrap() == microcredits - amount));
+    }
+}
@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
     /// Executes a transfer to the specified recipient_address with the specified amount and fee.
     /// Specify 0 for no fee.
     #[allow(clippy::too_many_arguments)]
@@ -30,6 +30,24 @@ impl<N: Network> ProgramManager<N> {
         amount_record: Option<Record<N, Plaintext<N>>>,
         fee_record: Record<N, Plaintext<N>>,
     ) -> Result<String> {
+        let transaction =
+            self.build_transfer(amount, fee, recipient_address, transfer_type, password, amount_record, fee_record)?;
+        self.broadcast_transaction(transaction.into_transaction())
+    }
+
+    /// Build a transfer transaction to the specified recipient_address with the specified amount and
+    /// fee without broadcasting it. Specify 0 for no fee.
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_transfer(
+        &self,
+        amount: u64,
+        fee: u64,
+        recipient_address: Address<N>,
+        transfer_type: TransferType,
+        password: Option<&str>,
+        amount_record: Option<Record<N, Plaintext<N>>>,
+        fee_record: Record<N, Plaintext<N>>,
+    ) -> Result<BuiltTransaction<N>> {
         // Ensure records provided have enough credits to cover the transfer amount and fee
         if let Some(amount_record) = amount_record.as_ref() {
             ensure!(
@@ -54,46 +72,7 @@ impl<N: Network> ProgramManager<N> {
             let vm = VM::from(store)?;
 
             // Prepare the inputs for a transfer.
-            let (transfer_function, inputs) = match transfer_type {
-                TransferType::Public => {
-                    let inputs = vec![
-                        Value::from_str(&recipient_address.to_string())?,
-                        Value::from_str(&format!("{}u64", amount))?,
-                    ];
-                    ("transfer_public", inputs)
-                }
-                TransferType::Private => {
-                    if amount_record.is_none() {
-                        bail!("Amount record must be specified for private transfers");
-                    } else {
-                        let inputs = vec![
-                            Value::Record(amount_record.unwrap()),
-                            Value::from_str(&recipient_address.to_string())?,
-                            Value::from_str(&format!("{}u64", amount))?,
-                        ];
-                        ("transfer_private", inputs)
-                    }
-                }
-                TransferType::PublicToPrivate => {
-                    let inputs = vec![
-                        Value::from_str(&recipient_address.to_string())?,
-                        Value::from_str(&format!("{}u64", amount))?,
-                    ];
-                    ("transfer_public_to_private", inputs)
-                }
-                TransferType::PrivateToPublic => {
-                    if amount_record.is_none() {
-                        bail!("Amount record must be specified for private transfers");
-                    } else {
-                        let inputs = vec![
-                            Value::Record(amount_record.unwrap()),
-                            Value::from_str(&recipient_address.to_string())?,
-                            Value::from_str(&format!("{}u64", amount))?,
-                        ];
-                        ("transfer_private_to_public", inputs)
-                    }
-                }
-            };
+            let (transfer_function, inputs) = transfer_inputs(amount, recipient_address, transfer_type, amount_record)?;
 
             // Create a new transaction.
             vm.execute(
@@ -106,123 +85,115 @@ impl<N: Network> ProgramManager<N> {
             )?
         };
 
-        self.broadcast_transaction(execution)
+        BuiltTransaction::new(execution, fee)
+    }
+
+    /// Create a transfer transaction without network access, using a state bundle fetched
+    /// beforehand in place of a query of the network. The bundle must hold the inclusion paths of
+    /// the fee record and of the amount record of private transfers. Specify 0 for no fee.
+    #[cfg(feature = "offline")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_transfer_transaction_offline<A: Aleo<Network = N>>(
+        private_key: &PrivateKey<N>,
+        amount: u64,
+        fee: u64,
+        recipient_address: Address<N>,
+        transfer_type: TransferType,
+        amount_record: Option<Record<N, Plaintext<N>>>,
+        fee_record: Record<N, Plaintext<N>>,
+        state: &StateBundle<N>,
+    ) -> Result<Transaction<N>> {
+        let credits = Program::credits()?;
+        let (transfer_function, inputs) = transfer_inputs(amount, recipient_address, transfer_type, amount_record)?;
+        Self::create_execute_transaction_offline::<A>(
+            private_key,
+            fee,
+            inputs.into_iter(),
+            fee_record,
+            &credits,
+            &[],
+            transfer_function,
+            state,
+        )
     }
 }
 
+/// Get the credits.aleo function executed by a transfer and its inputs
+fn transfer_inputs<N: Network>(
+    amount: u64,
+    recipient_address: Address<N>,
+    transfer_type: TransferType,
+    amount_record: Option<Record<N, Plaintext<N>>>,
+) -> Result<(&'static str, Vec<Value<N>>)> {
+    let recipient = recipient_address.to_value()?;
+    let amount = amount.to_value()?;
+    let private_amount_record = || {
+        amount_record.map(Value::Record).ok_or_else(|| anyhow!("Amount record must be specified for private transfers"))
+    };
+    Ok(match transfer_type {
+        TransferType::Public => ("transfer_public", vec![recipient, amount]),
+        TransferType::Private => ("transfer_private", vec![private_amount_record()?, recipient, amount]),
+        TransferType::PublicToPrivate => ("transfer_public_to_private", vec![recipient, amount]),
+        TransferType::PrivateToPublic => {
+            ("transfer_private_to_public", vec![private_amount_record()?, recipient, amount])
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::BEACON_PRIVATE_KEY, AleoAPIClient, RecordFinder};
+    use crate::{ledger::LocalLedger, test_utils::setup_local_ledger, AleoAPIClient, RecordFinder};
     use snarkvm_console::network::Testnet3;
 
-    use std::{str::FromStr, thread};
+    use std::str::FromStr;
 
-    // Attempt to transfer the specified amount from the sender to the recipient.
-    fn try_transfer(
+    // Transfer the specified amount from the sender to the recipient on the local ledger
+    fn transfer(
+        local_ledger: &LocalLedger<Testnet3>,
         sender: &PrivateKey<Testnet3>,
         recipient: &Address<Testnet3>,
         amount: u64,
         visibility: TransferType,
     ) {
-        println!("Attempting to transfer of type: {visibility:?} of {amount} to {recipient:?}");
-        let api_client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
-        let program_manager =
-            ProgramManager::<Testnet3>::new(Some(*sender), None, Some(api_client.clone()), None).unwrap();
-        let record_finder = RecordFinder::new(api_client);
+        let program_manager = ProgramManager::<Testnet3, LocalLedger<Testnet3>>::new(
+            Some(*sender),
+            None,
+            Some(local_ledger.clone()),
+            None,
+        )
+        .unwrap();
+        let record_finder = RecordFinder::new(local_ledger.clone());
         let fee = 5_000_000;
-        for i in 0..10 {
-            let (amount_record, fee_record) = match &visibility {
-                TransferType::Public => {
-                    let fee_record = record_finder.find_one_record(sender, fee);
-                    if fee_record.is_err() {
-                        println!("Record not found: {} - retrying", fee_record.unwrap_err());
-                        thread::sleep(std::time::Duration::from_secs(3));
-                        if i == 9 {
-                            panic!("Transfer failed after 10 attempts");
-                        }
-                        continue;
-                    }
-                    (None, fee_record.unwrap())
-                }
-                TransferType::PublicToPrivate => {
-                    let fee_record = record_finder.find_one_record(sender, fee);
-                    if fee_record.is_err() {
-                        println!("Record not found: {} - retrying", fee_record.unwrap_err());
-                        thread::sleep(std::time::Duration::from_secs(3));
-                        if i == 9 {
-                            panic!("Transfer failed after 10 attempts");
-                        }
-                        continue;
-                    }
-                    (None, fee_record.unwrap())
-                }
-                _ => {
-                    let record = record_finder.find_amount_and_fee_records(amount, fee, sender);
-                    if record.is_err() {
-                        println!("Record not found: {} - retrying", record.unwrap_err());
-                        thread::sleep(std::time::Duration::from_secs(3));
-                        continue;
-                    }
-                    let (amount_record, fee_record) = record.unwrap();
-                    (Some(amount_record), fee_record)
-                }
-            };
-
-            let result = program_manager.transfer(amount, fee, *recipient, visibility, None, amount_record, fee_record);
-            if result.is_err() {
-                println!("Transfer error: {} - retrying", result.unwrap_err());
-                if i == 9 {
-                    panic!("Transfer failed after 10 attempts");
-                }
-            } else {
-                break;
+        let (amount_record, fee_record) = match visibility {
+            TransferType::Public | TransferType::PublicToPrivate => {
+                (None, record_finder.find_one_record(sender, fee).unwrap())
             }
-        }
+            _ => {
+                let (amount_record, fee_record) =
+                    record_finder.find_amount_and_fee_records(amount, fee, sender).unwrap();
+                (Some(amount_record), fee_record)
+            }
+        };
+        program_manager.transfer(amount, fee, *recipient, visibility, None, amount_record, fee_record).unwrap();
     }
 
-    // Check that the specified amount has been transferred from the sender to the recipient.
+    // Check that the recipient owns an unspent record holding the specified amount
     fn verify_transfer(
-        amount: u64,
         api_client: &AleoAPIClient<Testnet3>,
+        amount: u64,
         recipient_private_key: &PrivateKey<Testnet3>,
-        visibility: TransferType,
     ) {
-        for i in 0..10 {
-            println!("Attempting to verify transfer of visibility: {visibility:?} for amount: {amount}");
-            let height = api_client.latest_height().unwrap();
-            let records = api_client.get_unspent_records(recipient_private_key, 0..height, None, None).unwrap();
-            let mut is_verified = false;
-            if !records.is_empty() {
-                for record in records.iter() {
-                    let (_, record) = record;
-                    let record_amount = record.microcredits().unwrap();
-                    println!("Found amount: {record_amount} - expected: {amount}");
-                    if amount == record_amount {
-                        println!("✅ Transfer of {amount} verified for transfer type: {visibility:?}");
-                        is_verified = true;
-                        break;
-                    }
-                }
-                if is_verified {
-                    break;
-                }
-            }
-            thread::sleep(std::time::Duration::from_secs(3));
-            if i > 8 {
-                let error =
-                    format!("❌ Failed to verify transfer of visibility: {visibility:?} after 8 transfer errors");
-                panic!("{error}");
-            }
-        }
+        let height = api_client.latest_height().unwrap();
+        let records = api_client.get_unspent_records(recipient_private_key, 0..height + 1, None, None).unwrap();
+        assert!(records.iter().any(|(_, record)| record.microcredits().unwrap() == amount));
     }
 
     #[test]
-    #[ignore]
     fn test_transfer_roundtrip() {
         // Initialize necessary key material
         // Use the beacon private key to make the initial transfer
-        let beacon_private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+        let (beacon_private_key, local_ledger, _, _) = setup_local_ledger();
         let amount = 16_666_666;
         let amount_str = "16666666u64";
         let fee = 26_666_666;
@@ -243,49 +214,44 @@ mod tests {
             PrivateKey::<Testnet3>::from_str("APrivateKey1zkp3NchSbrypyf2UoJSGyag58biAFPvtd1WtpM5M9pqoifK").unwrap();
         let public_to_private_recipient_view_key = ViewKey::try_from(&public_to_private_recipient_private_key).unwrap();
         let public_to_private_recipient_address = Address::try_from(&public_to_private_recipient_view_key).unwrap();
-        let api_client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
+        let api_client = AleoAPIClient::<Testnet3>::new(local_ledger.base_url(), "testnet3").unwrap();
         let public_address_literal = Literal::<Testnet3>::from_str(&public_recipient_address.to_string()).unwrap();
         let private_to_public_address_literal =
             Literal::<Testnet3>::from_str(&private_to_public_recipient_address.to_string()).unwrap();
         let expected_value = Value::from(Plaintext::<Testnet3>::from_str(amount_str).unwrap());
         let zero_value = Value::from(Plaintext::<Testnet3>::from_str("0u64").unwrap());
 
-        println!("Private recipient private_key: {}", private_recipient_private_key);
-        println!("Private recipient address: {}", private_recipient_address);
-        println!("Private to public recipient private_key: {}", private_to_public_recipient_private_key);
-        println!("Private to public recipient address: {}", private_to_public_recipient_address);
-        println!("Public recipient private_key: {}", public_recipient_private_key);
-        println!("Public recipient address: {}", public_recipient_address);
-        println!("Public to private recipient private_key: {}", public_to_private_recipient_private_key);
-        println!("Public to private recipient address: {}", public_to_private_recipient_address);
-
-        // Transfer funds to the private recipient and confirm that the transaction is on chain
-        try_transfer(&beacon_private_key, &private_recipient_address, amount, TransferType::Private);
+        // Transfer funds to the private recipient
+        transfer(&local_ledger, &beacon_private_key, &private_recipient_address, amount, TransferType::Private);
 
         // Transfer funds to each of the other recipients to pay the fee with
-        try_transfer(&beacon_private_key, &private_recipient_address, fee, TransferType::Private);
-        try_transfer(&beacon_private_key, &private_to_public_recipient_address, fee, TransferType::Private);
-        try_transfer(&beacon_private_key, &public_recipient_address, fee, TransferType::Private);
-        try_transfer(&beacon_private_key, &public_to_private_recipient_address, fee, TransferType::Private);
-        thread::sleep(std::time::Duration::from_secs(20));
+        transfer(&local_ledger, &beacon_private_key, &private_recipient_address, fee, TransferType::Private);
+        transfer(&local_ledger, &beacon_private_key, &private_to_public_recipient_address, fee, TransferType::Private);
+        transfer(&local_ledger, &beacon_private_key, &public_recipient_address, fee, TransferType::Private);
+        transfer(&local_ledger, &beacon_private_key, &public_to_private_recipient_address, fee, TransferType::Private);
 
         // Verify private transfer
-        verify_transfer(amount, &api_client, &private_recipient_private_key, TransferType::Private);
+        verify_transfer(&api_client, amount, &private_recipient_private_key);
 
         // Transfer funds to the private_to_public recipient
-        try_transfer(
+        transfer(
+            &local_ledger,
             &private_recipient_private_key,
             &private_to_public_recipient_address,
             amount,
             TransferType::PrivateToPublic,
         );
-        thread::sleep(std::time::Duration::from_secs(25));
         let value =
             api_client.get_mapping_value("credits.aleo", "account", &private_to_public_address_literal).unwrap();
         assert!(value.eq(&expected_value));
 
-        try_transfer(&private_to_public_recipient_private_key, &public_recipient_address, amount, TransferType::Public);
-        thread::sleep(std::time::Duration::from_secs(25));
+        transfer(
+            &local_ledger,
+            &private_to_public_recipient_private_key,
+            &public_recipient_address,
+            amount,
+            TransferType::Public,
+        );
         let value = api_client.get_mapping_value("credits.aleo", "account", public_address_literal).unwrap();
         assert!(value.eq(&expected_value));
         let value =
@@ -293,13 +259,13 @@ mod tests {
         assert!(value.eq(&zero_value));
 
         // Transfer funds to the public_to_private recipient and ensure the funds made the entire journey
-        try_transfer(
+        transfer(
+            &local_ledger,
             &public_recipient_private_key,
             &public_to_private_recipient_address,
             amount,
             TransferType::PublicToPrivate,
         );
-        thread::sleep(std::time::Duration::from_secs(25));
-        verify_transfer(amount, &api_client, &public_to_private_recipient_private_key, TransferType::PublicToPrivate);
+        verify_transfer(&api_client, amount, &public_to_private_recipient_private_key);
     }
 }
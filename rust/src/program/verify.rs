@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+impl<N: Network, C: NetworkClient<N>> ProgramManager<N, C> {
+    /// Verify a transaction locally, for instance one built on another machine, before broadcasting
+    /// it. The transaction is checked against a VM loaded with the programs it references and their
+    /// imports, which are fetched from the network.
+    ///
+    /// The checks of the network's VM are run: the ID, proofs and fee of the transaction are
+    /// verified and no input, output or transition may appear twice in it. Whether its records are
+    /// unspent and its state root is on chain is left to the network.
+    pub fn verify_transaction<A: Aleo<Network = N>>(transaction: &Transaction<N>, api_client: &C) -> Result<()> {
+        let id = transaction.id();
+        ensure!(*id == transaction.to_root()?, "Transaction {id} has an incorrect ID");
+
+        // Ensure no transition, input, output or transition key is repeated
+        ensure_unique(transaction.transition_ids(), "transitions", id)?;
+        ensure_unique(transaction.input_ids(), "input IDs", id)?;
+        ensure_unique(transaction.serial_numbers(), "serial numbers", id)?;
+        ensure_unique(transaction.tags(), "tags", id)?;
+        ensure_unique(transaction.output_ids(), "output IDs", id)?;
+        ensure_unique(transaction.commitments(), "commitments", id)?;
+        ensure_unique(transaction.nonces(), "nonces", id)?;
+        ensure_unique(transaction.transition_public_keys(), "transition public keys", id)?;
+        ensure_unique(transaction.transition_commitments(), "transition commitments", id)?;
+
+        // Initialize a VM
+        let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+        let vm = VM::<N, ConsensusMemory<N>>::from(store)?;
+
+        match transaction {
+            Transaction::Deploy(id, owner, deployment, fee) => {
+                let deployment_id = deployment.to_deployment_id()?;
+                ensure!(owner.verify(deployment_id), "Invalid owner signature for deployment transaction {id}");
+
+                // The deployed program is added to the VM while verifying it, only its imports are loaded
+                let imports = api_client.get_program_imports_from_source(deployment.program())?;
+                Self::load_programs(&vm, imports.into_values())?;
+                let process = vm.process();
+                let process = process.read();
+                ensure!(*fee.amount()? < N::MAX_FEE, "The fee of transaction {id} exceeds the maximum fee");
+                process.verify_fee(fee, deployment_id)?;
+                process.verify_deployment::<A, _>(deployment, &mut rand::thread_rng())?;
+            }
+            Transaction::Execute(id, execution, fee) => {
+                let execution_id = execution.to_execution_id()?;
+
+                // Only a lone split or mint transition may be executed without a fee
+                let is_free = (transaction.is_split() || transaction.is_mint()) && execution.len() == 1;
+                ensure!(fee.is_some() || is_free, "Execution transaction {id} is missing a fee");
+
+                // Load every program executed by a transition along with its imports
+                for program_id in execution.transitions().map(|transition| transition.program_id()) {
+                    if vm.contains_program(program_id) {
+                        continue;
+                    }
+                    let program = api_client.get_program(program_id)?;
+                    let imports = api_client.get_program_imports_from_source(&program)?;
+                    Self::load_programs(&vm, imports.into_values().chain(std::iter::once(program)))?;
+                }
+                let process = vm.process();
+                let process = process.read();
+                if let Some(fee) = fee {
+                    ensure!(*fee.amount()? < N::MAX_FEE, "The fee of transaction {id} exceeds the maximum fee");
+                    process.verify_fee(fee, execution_id)?;
+                }
+                process.verify_execution(execution)?;
+            }
+            Transaction::Fee(id, _) => {
+                bail!("Transaction {id} only pays a fee, fee transactions are created by the network and cannot be broadcast")
+            }
+        }
+        Ok(())
+    }
+
+    /// Add programs to a VM, in the order given, unless they are already loaded
+    fn load_programs(vm: &VM<N, ConsensusMemory<N>>, programs: impl Iterator<Item = Program<N>>) -> Result<()> {
+        for program in programs {
+            if !vm.contains_program(program.id()) {
+                vm.process().write().add_program(&program)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Ensure none of the elements of a transaction appear twice
+fn ensure_unique<T: Eq + std::hash::Hash>(
+    elements: impl Iterator<Item = T>,
+    name: &str,
+    id: impl std::fmt::Display,
+) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for element in elements {
+        ensure!(seen.insert(element), "Transaction {id} has duplicate {name}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ledger::LocalLedger, test_utils::setup_local_ledger, TransferType};
+    use snarkvm::circuit::AleoV0;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_verify_transaction() {
+        let (private_key, local_ledger, record_finder, program_manager) = setup_local_ledger();
+        let verify = |transaction: &Transaction<Testnet3>| {
+            ProgramManager::<Testnet3, LocalLedger<Testnet3>>::verify_transaction::<AleoV0>(transaction, &local_ledger)
+        };
+
+        let (amount_record, fee_record) =
+            record_finder.find_amount_and_fee_records(100, 500_000, &private_key).unwrap();
+        let recipient = Address::try_from(&PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+
+        // A transfer paying a fee and a split without one are valid
+        let transfer = program_manager
+            .build_transfer(100, 500_000, recipient, TransferType::Public, None, None, fee_record)
+            .unwrap()
+            .into_transaction();
+        verify(&transfer).unwrap();
+        let split = program_manager.build_split(50, amount_record.clone(), None).unwrap().into_transaction();
+        verify(&split).unwrap();
+
+        // Only splits and mints may be executed without a fee
+        let transfer_execution = transfer.execution().unwrap().clone();
+        let unpaid_transfer = Transaction::from_execution(transfer_execution, None).unwrap();
+        assert!(verify(&unpaid_transfer).unwrap_err().to_string().contains("missing a fee"));
+
+        // A record may not be spent twice in the same transaction
+        let other_split = program_manager.build_split(50, amount_record, None).unwrap().into_transaction();
+        let transitions = [&split, &other_split]
+            .into_iter()
+            .flat_map(|transaction| transaction.execution().unwrap().transitions().cloned().collect::<Vec<_>>());
+        let split_execution = split.execution().unwrap();
+        let double_spend = Transaction::from_execution(
+            Execution::from(transitions, split_execution.global_state_root(), split_execution.proof().cloned()).unwrap(),
+            None,
+        )
+        .unwrap();
+        assert!(verify(&double_spend).unwrap_err().to_string().contains("duplicate serial numbers"));
+    }
+}
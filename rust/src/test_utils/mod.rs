@@ -14,17 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AleoAPIClient, ProgramManager, RecordFinder, TransferType};
+use crate::{ApiError, LocalLedger, NetworkClient, ProgramManager, RecordFinder};
 use snarkvm::file::Manifest;
 use snarkvm_console::{
-    account::{PrivateKey, ViewKey},
-    network::Testnet3,
-    program::{Plaintext, Record},
+    account::{Address, PrivateKey},
+    network::{Network, Testnet3},
+    prelude::Uniform,
+    program::{Identifier, Plaintext, ProgramID, Record, StatePath, Value},
+    types::{Field, Group, Scalar},
 };
+use snarkvm_ledger_block::{Block, Transaction};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
 use snarkvm::synthesizer::Program;
-use std::{fs, fs::File, io::Write, ops::Add, panic::catch_unwind, path::PathBuf, str::FromStr, thread::sleep};
+use std::{fs, fs::File, io::Write, ops::Add, panic::catch_unwind, path::PathBuf, str::FromStr};
 
 pub const RECIPIENT_PRIVATE_KEY: &str = "APrivateKey1zkp3dQx4WASWYQVWKkq14v3RoQDfY2kbLssUj7iifi1VUQ6";
 pub const RECIPIENT_ADDRESS: &str = "aleo184vuwr5u7u0ha5f5k44067dd2uaqewxx6pe5ltha5pv99wvhfqxqv339h4";
@@ -141,6 +145,30 @@ pub const RECORD_5_MICROCREDITS: &str = r"{
   _nonce: 3700202890700295811197086261814785945731964545546334348117582517467189701159group.public
 }";
 
+/// Create a record owned by the account with a private u64 entry of the given name and a random
+/// nonce, so every record created has its own serial number
+pub fn amount_record(
+    private_key: &PrivateKey<Testnet3>,
+    entry_name: &str,
+    amount: u64,
+) -> Record<Testnet3, Plaintext<Testnet3>> {
+    let address = Address::try_from(private_key).unwrap();
+    let nonce = Group::<Testnet3>::generator() * Scalar::rand(&mut rand::thread_rng());
+    Record::from_str(&format!("{{ owner: {address}.private, {entry_name}: {amount}u64.private, _nonce: {nonce}.public }}"))
+        .unwrap()
+}
+
+/// Create a credits record owned by the account holding the given microcredits
+pub fn credits_record(private_key: &PrivateKey<Testnet3>, microcredits: u64) -> Record<Testnet3, Plaintext<Testnet3>> {
+    amount_record(private_key, "microcredits", microcredits)
+}
+
+/// Create credits records owned by the recipient test account holding each of the amounts
+pub fn credits_records(amounts: &[u64]) -> Vec<Record<Testnet3, Plaintext<Testnet3>>> {
+    let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+    amounts.iter().map(|amount| credits_record(&private_key, *amount)).collect()
+}
+
 /// Get a random program id
 pub fn random_program_id(len: usize) -> String {
     use rand::Rng;
@@ -198,67 +226,108 @@ pub fn teardown_directory(directory: &PathBuf) {
     }
 }
 
-/// Make transfers to the test account and return the records
-///
-/// This function assumes that a local snarkos beacon node is running on the specified port
-pub fn transfer_to_test_account(
-    amount: u64,
-    num_transactions: usize,
-    recipient_private_key: PrivateKey<Testnet3>,
-    port: &str,
-) -> Result<Vec<Record<Testnet3, Plaintext<Testnet3>>>> {
-    let api_client = AleoAPIClient::<Testnet3>::local_testnet3(port);
-    let beacon_private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY)?;
-
-    let recipient_view_key = ViewKey::<Testnet3>::try_from(&recipient_private_key)?;
-    let recipient_address = recipient_view_key.to_address();
-
-    let record_finder = RecordFinder::<Testnet3>::new(api_client.clone());
-
-    let program_manager =
-        ProgramManager::<Testnet3>::new(Some(beacon_private_key), None, Some(api_client), None).unwrap();
-
-    let fee = 500_000;
-    let mut transfer_successes = 0;
-    let mut retries = 0;
-    loop {
-        let input_record = record_finder.find_amount_and_fee_records(amount, fee, &beacon_private_key);
-        if input_record.is_err() {
-            println!("No records found, retrying");
-            retries += 1;
-            sleep(std::time::Duration::from_secs(3));
-            continue;
-        }
-        let (input_record, fee_record) = input_record.unwrap();
-        let result = program_manager.transfer(
-            amount,
-            500_000,
-            recipient_address,
-            TransferType::Private,
-            None,
-            Some(input_record),
-            fee_record,
-        );
-        if result.is_ok() {
-            println!("Transfer succeeded");
-            transfer_successes += 1;
-        } else {
-            println!("Transfer failed with error {:?}, retrying", result);
-            retries += 1;
-        }
-        if transfer_successes > num_transactions {
-            println!("{} transfers succeeded exiting", transfer_successes);
-            break;
-        }
-        if retries > 15 {
-            println!("exceeded 15 retries, exiting with found records");
-            break;
-        }
-        sleep(std::time::Duration::from_secs(3));
+/// Beacon private key, local ledger, record finder and program manager created by [setup_local_ledger]
+pub type LocalLedgerSetup = (
+    PrivateKey<Testnet3>,
+    LocalLedger<Testnet3>,
+    RecordFinder<Testnet3, LocalLedger<Testnet3>>,
+    ProgramManager<Testnet3, LocalLedger<Testnet3>>,
+);
+
+/// Create a local ledger funding the beacon account, along with a record finder and a program
+/// manager for the beacon account using it
+pub fn setup_local_ledger() -> LocalLedgerSetup {
+    let private_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+    let local_ledger = LocalLedger::<Testnet3>::new(&private_key).unwrap();
+    let record_finder = RecordFinder::new(local_ledger.clone());
+    let program_manager = ProgramManager::<Testnet3, LocalLedger<Testnet3>>::new(
+        Some(private_key),
+        None,
+        Some(local_ledger.clone()),
+        None,
+    )
+    .unwrap();
+    (private_key, local_ledger, record_finder, program_manager)
+}
+
+/// Network client that only knows about a fixed set of programs
+#[derive(Clone)]
+pub struct MockClient {
+    pub programs: IndexMap<ProgramID<Testnet3>, Program<Testnet3>>,
+}
+
+impl NetworkClient<Testnet3> for MockClient {
+    fn base_url(&self) -> &str {
+        "http://localhost:3030"
+    }
+
+    fn latest_height(&self) -> Result<u32> {
+        Ok(0)
+    }
+
+    fn latest_hash(&self) -> Result<<Testnet3 as Network>::BlockHash> {
+        bail!("No blocks available")
     }
 
-    let client = program_manager.api_client()?;
-    let latest_height = client.latest_height()?;
-    let records = client.get_unspent_records(&recipient_private_key, 0..latest_height, None, None)?;
-    Ok(records.into_iter().map(|(_cm, record)| record).collect())
+    fn latest_block(&self) -> Result<Block<Testnet3>> {
+        bail!("No blocks available")
+    }
+
+    fn get_block(&self, _height: u32) -> Result<Block<Testnet3>> {
+        bail!("No blocks available")
+    }
+
+    fn get_height(&self, _block_hash: <Testnet3 as Network>::BlockHash) -> Result<u32> {
+        bail!("No blocks available")
+    }
+
+    fn get_blocks(&self, _start_height: u32, _end_height: u32) -> Result<Vec<Block<Testnet3>>> {
+        Ok(vec![])
+    }
+
+    fn get_transaction(&self, _transaction_id: <Testnet3 as Network>::TransactionID) -> Result<Transaction<Testnet3>> {
+        bail!("No transactions available")
+    }
+
+    fn get_program(&self, program_id: &ProgramID<Testnet3>) -> Result<Program<Testnet3>> {
+        let url = format!("{}/testnet3/program/{program_id}", self.base_url());
+        self.programs.get(program_id).cloned().ok_or_else(|| ApiError::NotFound { url }.into())
+    }
+
+    fn get_program_mappings(&self, program_id: &ProgramID<Testnet3>) -> Result<Vec<Identifier<Testnet3>>> {
+        Ok(self.get_program(program_id)?.mappings().keys().copied().collect())
+    }
+
+    fn get_mapping_value(
+        &self,
+        _program_id: &ProgramID<Testnet3>,
+        _mapping_name: &Identifier<Testnet3>,
+        _key: &Plaintext<Testnet3>,
+    ) -> Result<Value<Testnet3>> {
+        bail!("No mappings available")
+    }
+
+    fn find_block_hash(
+        &self,
+        transaction_id: <Testnet3 as Network>::TransactionID,
+    ) -> Result<<Testnet3 as Network>::BlockHash> {
+        let url = format!("{}/testnet3/find/blockHash/{transaction_id}", self.base_url());
+        Err(ApiError::NotFound { url }.into())
+    }
+
+    fn find_transition_id(&self, _input_or_output_id: Field<Testnet3>) -> Result<<Testnet3 as Network>::TransitionID> {
+        bail!("No transitions available")
+    }
+
+    fn latest_state_root(&self) -> Result<<Testnet3 as Network>::StateRoot> {
+        bail!("No blocks available")
+    }
+
+    fn get_state_path_for_commitment(&self, _commitment: &Field<Testnet3>) -> Result<StatePath<Testnet3>> {
+        bail!("No records available")
+    }
+
+    fn transaction_broadcast(&self, _transaction: Transaction<Testnet3>) -> Result<String> {
+        bail!("Broadcasting is not supported")
+    }
 }
@@ -0,0 +1,290 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::program::Owner;
+use thiserror::Error;
+
+/// Error found in one input of a program function
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("Input {index} should be a {expected}: {reason}")]
+pub struct InputError {
+    /// Position of the input in the function signature, starting from 0
+    pub index: usize,
+    /// Type of the input declared in the function signature
+    pub expected: String,
+    /// Reason the input does not match its declared type
+    pub reason: String,
+}
+
+/// Checker of program function inputs against the types declared in the function signature
+///
+/// Inputs are given to executions as strings and a wrong type otherwise only shows up as an error
+/// from snarkVM once the VM is set up and the function keys are synthesized. The validator parses
+/// each input and checks it against its declared type, including the members of structs and the
+/// entries, visibility and owner of records, so every mistake is reported up front.
+///
+/// Basic Usage:
+/// let validator = InputValidator::new(&program).with_imports(&imports).with_caller(address);
+/// validator.validate("transfer_private", &["token1.record", "aleo1...", "10u64"])?;
+#[derive(Clone, Debug)]
+pub struct InputValidator<'a, N: Network> {
+    program: &'a Program<N>,
+    imports: Vec<&'a Program<N>>,
+    caller: Option<Address<N>>,
+}
+
+impl<'a, N: Network> InputValidator<'a, N> {
+    /// Create a validator for the functions of a program
+    pub fn new(program: &'a Program<N>) -> Self {
+        Self { program, imports: vec![], caller: None }
+    }
+
+    /// Add the imports of the program, used to check the records of other programs given as inputs.
+    /// Records of programs that are not added are only checked to be well formed.
+    pub fn with_imports(mut self, imports: impl IntoIterator<Item = &'a Program<N>>) -> Self {
+        self.imports.extend(imports);
+        self
+    }
+
+    /// Set the address of the account executing the function, which must own every record input
+    pub fn with_caller(mut self, caller: Address<N>) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Get the address of the account executing the function, if it is set
+    pub fn caller(&self) -> Option<&Address<N>> {
+        self.caller.as_ref()
+    }
+
+    /// Check the inputs of a function, returning an error for each input that does not match its
+    /// declared type. The function must exist and be given the number of inputs it declares.
+    pub fn check(&self, function: impl TryInto<Identifier<N>>, inputs: &[impl AsRef<str>]) -> Result<Vec<InputError>> {
+        let function_name = function.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        let program_id = self.program.id();
+        let function = self
+            .program
+            .get_function(&function_name)
+            .map_err(|_| anyhow!("Program {program_id} does not contain function {function_name}"))?;
+        let input_types = function.input_types();
+        ensure!(
+            input_types.len() == inputs.len(),
+            "Function {function_name} of {program_id} expects {} inputs, but {} were given",
+            input_types.len(),
+            inputs.len()
+        );
+
+        Ok(input_types
+            .iter()
+            .zip(inputs)
+            .enumerate()
+            .filter_map(|(index, (value_type, input))| {
+                self.check_input(value_type, input.as_ref().trim()).err().map(|reason| InputError {
+                    index,
+                    expected: value_type.to_string(),
+                    reason,
+                })
+            })
+            .collect())
+    }
+
+    /// Check the inputs of a function, failing with every input that does not match its declared
+    /// type
+    pub fn validate(&self, function: impl TryInto<Identifier<N>>, inputs: &[impl AsRef<str>]) -> Result<()> {
+        let errors = self.check(function, inputs)?;
+        if !errors.is_empty() {
+            let errors = errors.iter().map(|error| error.to_string()).collect::<Vec<_>>();
+            bail!("Invalid inputs:\n{}", errors.join("\n"));
+        }
+        Ok(())
+    }
+
+    fn check_input(&self, value_type: &ValueType<N>, input: &str) -> Result<(), String> {
+        match value_type {
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) => {
+                match Value::<N>::from_str(input).map_err(|_| format!("'{input}' is not a valid value"))? {
+                    Value::Plaintext(plaintext) => Self::check_plaintext(self.program, plaintext_type, &plaintext),
+                    Value::Record(_) => Err("found a record".to_string()),
+                }
+            }
+            ValueType::Record(record_name) => self.check_record(self.program, record_name, input),
+            ValueType::ExternalRecord(locator) => {
+                match self.imports.iter().find(|import| import.id() == locator.program_id()) {
+                    Some(import) => self.check_record(import, locator.resource(), input),
+                    None => {
+                        let record = Record::<N, Plaintext<N>>::from_str(input)
+                            .map_err(|_| "the input is not a valid record plaintext".to_string())?;
+                        self.check_owner(&record)
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_record(&self, program: &Program<N>, record_name: &Identifier<N>, input: &str) -> Result<(), String> {
+        let record_type = program.get_record(record_name).map_err(|e| e.to_string())?;
+        let record = Record::<N, Plaintext<N>>::from_str(input)
+            .map_err(|_| "the input is not a valid record plaintext".to_string())?;
+
+        if record.owner().is_public() != record_type.owner().is_public() {
+            let visibility = if record_type.owner().is_public() { "public" } else { "private" };
+            return Err(format!("the owner of the record should be {visibility}"));
+        }
+        self.check_owner(&record)?;
+
+        let entries = record_type.entries();
+        if let Some(name) = entries.keys().find(|name| !record.data().contains_key(*name)) {
+            return Err(format!("the record is missing the entry '{name}'"));
+        }
+        if let Some(name) = record.data().keys().find(|name| !entries.contains_key(*name)) {
+            return Err(format!("the record has an unexpected entry '{name}'"));
+        }
+        for (name, entry_type) in entries {
+            let entry = &record.data()[name];
+            let (plaintext_type, plaintext) = match (entry_type, entry) {
+                (EntryType::Constant(plaintext_type), Entry::Constant(plaintext))
+                | (EntryType::Public(plaintext_type), Entry::Public(plaintext))
+                | (EntryType::Private(plaintext_type), Entry::Private(plaintext)) => (plaintext_type, plaintext),
+                _ => return Err(format!("the record entry '{name}' should be {entry_type}")),
+            };
+            Self::check_plaintext(program, plaintext_type, plaintext)
+                .map_err(|reason| format!("in the record entry '{name}', {reason}"))?;
+        }
+        Ok(())
+    }
+
+    fn check_owner(&self, record: &Record<N, Plaintext<N>>) -> Result<(), String> {
+        let owner = match record.owner() {
+            Owner::Public(address) => *address,
+            Owner::Private(Plaintext::Literal(Literal::Address(address), _)) => *address,
+            Owner::Private(_) => return Err("the owner of the record is not an address".to_string()),
+        };
+        match self.caller {
+            Some(caller) if caller != owner => Err(format!("the record is owned by {owner}, not by {caller}")),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_plaintext(
+        program: &Program<N>,
+        plaintext_type: &PlaintextType<N>,
+        plaintext: &Plaintext<N>,
+    ) -> Result<(), String> {
+        match (plaintext_type, plaintext) {
+            (PlaintextType::Literal(literal_type), Plaintext::Literal(literal, _)) => {
+                match literal.to_type() == *literal_type {
+                    true => Ok(()),
+                    false => Err(format!("found a {} literal", literal.to_type())),
+                }
+            }
+            (PlaintextType::Literal(_), Plaintext::Struct(..)) => Err("found a struct".to_string()),
+            (PlaintextType::Struct(struct_name), Plaintext::Literal(literal, _)) => {
+                Err(format!("expected a {struct_name} struct, found a {} literal", literal.to_type()))
+            }
+            (PlaintextType::Struct(struct_name), Plaintext::Struct(members, _)) => {
+                let struct_ = program.get_struct(struct_name).map_err(|e| e.to_string())?;
+                let member_types = struct_.members();
+                if let Some(name) = member_types.keys().find(|name| !members.contains_key(*name)) {
+                    return Err(format!("the {struct_name} struct is missing the member '{name}'"));
+                }
+                if let Some(name) = members.keys().find(|name| !member_types.contains_key(*name)) {
+                    return Err(format!("the {struct_name} struct has an unexpected member '{name}'"));
+                }
+                if !member_types.keys().eq(members.keys()) {
+                    return Err(format!(
+                        "the members of the {struct_name} struct should be given in the order {}",
+                        member_types.keys().map(|name| name.to_string()).collect::<Vec<_>>().join(", ")
+                    ));
+                }
+                for (name, member_type) in member_types {
+                    Self::check_plaintext(program, member_type, &members[name])
+                        .map_err(|reason| format!("in the member '{name}' of the {struct_name} struct, {reason}"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    const VALIDATION_PROGRAM: &str = "program validation_test.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function move_point:
+    input r0 as point.public;
+    input r1 as u32.private;
+    output r1 as u32.private;
+
+function spend:
+    input r0 as token.record;
+    input r1 as u64.public;
+    output r1 as u64.public;
+";
+
+    fn token(owner: &Address<Testnet3>, amount: &str) -> String {
+        format!("{{ owner: {owner}.private, amount: {amount}.private, _nonce: 0group.public }}")
+    }
+
+    #[test]
+    fn test_input_validation() {
+        let program = Program::<Testnet3>::from_str(VALIDATION_PROGRAM).unwrap();
+        let rng = &mut rand::thread_rng();
+        let caller = Address::try_from(PrivateKey::<Testnet3>::new(rng).unwrap()).unwrap();
+        let other = Address::try_from(PrivateKey::<Testnet3>::new(rng).unwrap()).unwrap();
+        let validator = InputValidator::new(&program).with_caller(caller);
+
+        // Well typed inputs pass
+        validator.validate("move_point", &["{ x: 1u32, y: 2u32 }", "3u32"]).unwrap();
+        validator.validate("spend", &[token(&caller, "10u64"), "5u64".to_string()]).unwrap();
+
+        // Every mistyped input is reported with its position
+        let errors = validator.check("move_point", &["{ x: 1u32, y: 2u64 }", "3u64"]).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].index, 0);
+        assert_eq!(errors[0].expected, "point.public");
+        assert_eq!(errors[1].index, 1);
+        assert_eq!(errors[1].reason, "found a u64 literal");
+        assert_eq!(validator.check("move_point", &["{ x: 1u32 }", "3u32"]).unwrap().len(), 1);
+        assert_eq!(validator.check("move_point", &["1u32", "{ x: 1u32, y: 2u32 }"]).unwrap().len(), 2);
+
+        // Records must match their declared entries and be owned by the caller
+        let errors = validator.check("spend", &[token(&caller, "10u32"), "5u64".to_string()]).unwrap();
+        assert!(errors[0].reason.contains("amount"));
+        let errors = validator.check("spend", &[token(&other, "10u64"), "5u64".to_string()]).unwrap();
+        assert!(errors[0].reason.contains("owned by"));
+        assert_eq!(validator.check("spend", &["10u64", "5u64"]).unwrap().len(), 1);
+        assert!(InputValidator::new(&program).validate("spend", &[token(&other, "10u64"), "5u64".to_string()]).is_ok());
+
+        // Unknown functions and a wrong number of inputs are errors of the whole call
+        assert!(validator.check("missing", &["1u32"]).is_err());
+        assert!(validator.check("move_point", &["3u32"]).is_err());
+    }
+}
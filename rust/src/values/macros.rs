@@ -0,0 +1,202 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+/// Derive the conversions between a Rust struct and an Aleo struct with the same members
+///
+/// The fields listed are the members of the Aleo struct in their declared order and must all be
+/// convertible to plaintexts. Structs holding network types such as addresses name their network
+/// parameter.
+///
+/// Basic Usage:
+/// struct Point { x: u32, y: u32 }
+/// aleo_struct!(Point { x, y });
+///
+/// struct Payment<N: Network> { to: Address<N>, amount: u64 }
+/// aleo_struct!(Payment<N> { to, amount });
+#[macro_export]
+macro_rules! aleo_struct {
+    ($name:ident<$network:ident> { $($member:ident),+ $(,)? }) => {
+        $crate::aleo_struct!(@impl [$network] [$name<$network>] { $($member),+ });
+    };
+    ($name:ident { $($member:ident),+ $(,)? }) => {
+        $crate::aleo_struct!(@impl [N] [$name] { $($member),+ });
+    };
+    (@impl [$network:ident] [$($type:tt)+] { $($member:ident),+ }) => {
+        impl<$network: $crate::Network> $crate::ToPlaintext<$network> for $($type)+ {
+            fn to_plaintext(&self) -> $crate::values::__private::Result<$crate::Plaintext<$network>> {
+                $crate::StructBuilder::new()$(.member(stringify!($member), &self.$member))+.build()
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::FromPlaintext<$network> for $($type)+ {
+            fn from_plaintext(plaintext: &$crate::Plaintext<$network>) -> $crate::values::__private::Result<Self> {
+                Ok(Self { $($member: $crate::struct_member(plaintext, stringify!($member))?),+ })
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::ToValue<$network> for $($type)+ {
+            fn to_value(&self) -> $crate::values::__private::Result<$crate::Value<$network>> {
+                Ok($crate::Value::Plaintext($crate::ToPlaintext::to_plaintext(self)?))
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::FromValue<$network> for $($type)+ {
+            fn from_value(value: &$crate::Value<$network>) -> $crate::values::__private::Result<Self> {
+                let plaintext = <$crate::Plaintext<$network> as $crate::FromValue<$network>>::from_value(value)?;
+                $crate::FromPlaintext::from_plaintext(&plaintext)
+            }
+        }
+    };
+}
+
+/// Derive the conversions between a Rust struct and an Aleo record with the same entries
+///
+/// The Rust struct holds the owner of the record in an `owner: Address<N>` field and its nonce in
+/// a `nonce: Group<N>` field. The entries listed are the entries of the Aleo record in their
+/// declared order with their visibility, one of `constant`, `public` or `private`. The owner is
+/// private unless the record declares it public with a leading `owner: public`.
+///
+/// Basic Usage:
+/// struct Token<N: Network> { owner: Address<N>, amount: u64, nonce: Group<N> }
+/// aleo_record!(Token<N> { amount: private });
+#[macro_export]
+macro_rules! aleo_record {
+    ($name:ident<$network:ident> { owner: $owner:ident $(, $entry:ident: $visibility:ident)* $(,)? }) => {
+        $crate::aleo_record!(@impl $owner [$network] [$name<$network>] { $($entry: $visibility),* });
+    };
+    ($name:ident<$network:ident> { $($entry:ident: $visibility:ident),* $(,)? }) => {
+        $crate::aleo_record!(@impl private [$network] [$name<$network>] { $($entry: $visibility),* });
+    };
+    (@owner private $builder:expr) => {
+        $builder
+    };
+    (@owner public $builder:expr) => {
+        $builder.public_owner()
+    };
+    (@impl $owner:ident [$network:ident] [$($type:tt)+] { $($entry:ident: $visibility:ident),* }) => {
+        impl<$network: $crate::Network> $crate::ToRecord<$network> for $($type)+ {
+            fn to_record(
+                &self,
+            ) -> $crate::values::__private::Result<$crate::Record<$network, $crate::Plaintext<$network>>> {
+                let builder = $crate::RecordBuilder::new(self.owner).nonce(self.nonce);
+                let builder = $crate::aleo_record!(@owner $owner builder);
+                $(let builder = builder.$visibility(stringify!($entry), &self.$entry);)*
+                builder.build()
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::FromRecord<$network> for $($type)+ {
+            fn from_record(
+                record: &$crate::Record<$network, $crate::Plaintext<$network>>,
+            ) -> $crate::values::__private::Result<Self> {
+                Ok(Self {
+                    owner: $crate::record_owner(record)?,
+                    nonce: *record.nonce(),
+                    $($entry: $crate::record_entry(record, stringify!($entry))?,)*
+                })
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::ToValue<$network> for $($type)+ {
+            fn to_value(&self) -> $crate::values::__private::Result<$crate::Value<$network>> {
+                Ok($crate::Value::Record($crate::ToRecord::to_record(self)?))
+            }
+        }
+
+        impl<$network: $crate::Network> $crate::FromValue<$network> for $($type)+ {
+            fn from_value(value: &$crate::Value<$network>) -> $crate::values::__private::Result<Self> {
+                let record =
+                    <$crate::Record<$network, $crate::Plaintext<$network>> as $crate::FromValue<$network>>::from_value(
+                        value,
+                    )?;
+                $crate::FromRecord::from_record(&record)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use snarkvm_console::{network::Testnet3, types::Group};
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    aleo_struct!(Point { x, y });
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Payment<N: Network> {
+        to: Address<N>,
+        at: Point,
+        amount: u64,
+    }
+
+    aleo_struct!(Payment<N> { to, at, amount });
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Token<N: Network> {
+        owner: Address<N>,
+        amount: u64,
+        memo: Point,
+        nonce: Group<N>,
+    }
+
+    aleo_record!(Token<N> { amount: private, memo: public });
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Badge<N: Network> {
+        owner: Address<N>,
+        level: u8,
+        nonce: Group<N>,
+    }
+
+    aleo_record!(Badge<N> { owner: public, level: constant });
+
+    #[test]
+    fn test_aleo_struct() {
+        let address = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let payment = Payment { to: address, at: Point { x: 1, y: 2 }, amount: 5 };
+        let value = payment.to_value().unwrap();
+        let expected = format!("{{ to: {address}, at: {{ x: 1u32, y: 2u32 }}, amount: 5u64 }}");
+        assert_eq!(value, Value::from_str(&expected).unwrap());
+        assert_eq!(Payment::from_value(&value).unwrap(), payment);
+
+        let point = Plaintext::<Testnet3>::from_str("{ x: 1u32, y: 2u64 }").unwrap();
+        assert!(Point::from_plaintext(&point).is_err());
+        assert!(Point::from_value(&Value::<Testnet3>::from_str("1u32").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_aleo_record() {
+        let owner = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let token = Token { owner, amount: 100, memo: Point { x: 1, y: 2 }, nonce: Group::generator() };
+        let record = token.to_record().unwrap();
+        assert!(!record.owner().is_public());
+        assert_eq!(Token::from_record(&record).unwrap(), token);
+        assert_eq!(Token::from_value(&token.to_value().unwrap()).unwrap(), token);
+        assert_eq!(record_entry::<_, u64>(&record, "amount").unwrap(), 100);
+
+        let badge = Badge { owner, level: 3, nonce: Group::generator() };
+        let record = badge.to_record().unwrap();
+        assert!(record.owner().is_public());
+        assert_eq!(Badge::from_record(&record).unwrap(), badge);
+        assert!(Badge::from_record(&token.to_record().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Conversions between Rust types and the values of Aleo programs
+//!
+//! Literals convert to and from their Rust primitives, Aleo structs and records are assembled with
+//! [StructBuilder] and [RecordBuilder], and the [aleo_struct](crate::aleo_struct) and
+//! [aleo_record](crate::aleo_record) macros derive the conversions for Rust structs mirroring the
+//! structs and records of a program.
+//!
+//! Program outputs are decoded with [decode_output] and mapping values fetched from the network with
+//! [FromValue], for example `u64::from_value(&api_client.get_mapping_value("credits.aleo", "account", address)?)?`.
+
+use super::*;
+
+mod macros;
+
+pub mod plaintext;
+pub use plaintext::*;
+
+pub mod record;
+pub use record::*;
+
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by the code generated by the value macros and the binding generator
+    pub use anyhow::Result;
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::types::{Boolean, Group, Scalar, StringType, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
+
+/// Conversion of a Rust value into an Aleo plaintext
+pub trait ToPlaintext<N: Network> {
+    /// Convert the value into a plaintext
+    fn to_plaintext(&self) -> Result<Plaintext<N>>;
+}
+
+/// Conversion of an Aleo plaintext into a Rust value
+pub trait FromPlaintext<N: Network>: Sized {
+    /// Convert a plaintext into the value, failing if the plaintext is of another type
+    fn from_plaintext(plaintext: &Plaintext<N>) -> Result<Self>;
+}
+
+/// Conversion of a Rust value into an Aleo value used as a program input
+pub trait ToValue<N: Network> {
+    /// Convert the value into an Aleo value
+    fn to_value(&self) -> Result<Value<N>>;
+}
+
+/// Conversion of an Aleo value, such as a program output or a mapping value, into a Rust value
+pub trait FromValue<N: Network>: Sized {
+    /// Convert an Aleo value into the value, failing if the Aleo value is of another type
+    fn from_value(value: &Value<N>) -> Result<Self>;
+}
+
+impl<N: Network, T: ToPlaintext<N> + ?Sized> ToPlaintext<N> for &T {
+    fn to_plaintext(&self) -> Result<Plaintext<N>> {
+        (**self).to_plaintext()
+    }
+}
+
+impl<N: Network, T: ToValue<N> + ?Sized> ToValue<N> for &T {
+    fn to_value(&self) -> Result<Value<N>> {
+        (**self).to_value()
+    }
+}
+
+impl<N: Network> ToPlaintext<N> for Plaintext<N> {
+    fn to_plaintext(&self) -> Result<Plaintext<N>> {
+        Ok(self.clone())
+    }
+}
+
+impl<N: Network> FromPlaintext<N> for Plaintext<N> {
+    fn from_plaintext(plaintext: &Plaintext<N>) -> Result<Self> {
+        Ok(plaintext.clone())
+    }
+}
+
+impl<N: Network> ToValue<N> for Plaintext<N> {
+    fn to_value(&self) -> Result<Value<N>> {
+        Ok(Value::Plaintext(self.clone()))
+    }
+}
+
+impl<N: Network> FromValue<N> for Plaintext<N> {
+    fn from_value(value: &Value<N>) -> Result<Self> {
+        plaintext_of(value).cloned()
+    }
+}
+
+impl<N: Network> ToValue<N> for Value<N> {
+    fn to_value(&self) -> Result<Value<N>> {
+        Ok(self.clone())
+    }
+}
+
+impl<N: Network> FromValue<N> for Value<N> {
+    fn from_value(value: &Value<N>) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+/// Implement the conversions of a Rust type stored in an Aleo literal
+macro_rules! impl_literal {
+    ($type:ty, $variant:ident, |$value:ident| $to_literal:expr, |$literal:ident| $from_literal:expr) => {
+        impl<N: Network> ToPlaintext<N> for $type {
+            fn to_plaintext(&self) -> Result<Plaintext<N>> {
+                let $value = self;
+                Ok(Plaintext::from(Literal::$variant($to_literal)))
+            }
+        }
+
+        impl<N: Network> FromPlaintext<N> for $type {
+            fn from_plaintext(plaintext: &Plaintext<N>) -> Result<Self> {
+                match plaintext {
+                    Plaintext::Literal(Literal::$variant($literal), _) => Ok($from_literal),
+                    Plaintext::Literal(literal, _) => {
+                        bail!("Expected a {} literal, found a {} literal", stringify!($variant), literal.to_type())
+                    }
+                    Plaintext::Struct(..) => bail!("Expected a {} literal, found a struct", stringify!($variant)),
+                }
+            }
+        }
+
+        impl<N: Network> ToValue<N> for $type {
+            fn to_value(&self) -> Result<Value<N>> {
+                Ok(Value::Plaintext(self.to_plaintext()?))
+            }
+        }
+
+        impl<N: Network> FromValue<N> for $type {
+            fn from_value(value: &Value<N>) -> Result<Self> {
+                Self::from_plaintext(plaintext_of(value)?)
+            }
+        }
+    };
+}
+
+impl_literal!(bool, Boolean, |value| Boolean::new(*value), |literal| **literal);
+impl_literal!(i8, I8, |value| I8::new(*value), |literal| **literal);
+impl_literal!(i16, I16, |value| I16::new(*value), |literal| **literal);
+impl_literal!(i32, I32, |value| I32::new(*value), |literal| **literal);
+impl_literal!(i64, I64, |value| I64::new(*value), |literal| **literal);
+impl_literal!(i128, I128, |value| I128::new(*value), |literal| **literal);
+impl_literal!(u8, U8, |value| U8::new(*value), |literal| **literal);
+impl_literal!(u16, U16, |value| U16::new(*value), |literal| **literal);
+impl_literal!(u32, U32, |value| U32::new(*value), |literal| **literal);
+impl_literal!(u64, U64, |value| U64::new(*value), |literal| **literal);
+impl_literal!(u128, U128, |value| U128::new(*value), |literal| **literal);
+impl_literal!(String, String, |value| StringType::new(value), |literal| String::from(&**literal));
+impl_literal!(Address<N>, Address, |value| *value, |literal| *literal);
+impl_literal!(Field<N>, Field, |value| *value, |literal| *literal);
+impl_literal!(Group<N>, Group, |value| *value, |literal| *literal);
+impl_literal!(Scalar<N>, Scalar, |value| *value, |literal| *literal);
+
+/// Get the plaintext of an Aleo value, failing if the value is a record
+pub(crate) fn plaintext_of<N: Network>(value: &Value<N>) -> Result<&Plaintext<N>> {
+    match value {
+        Value::Plaintext(plaintext) => Ok(plaintext),
+        Value::Record(_) => bail!("Expected a plaintext value, found a record"),
+    }
+}
+
+/// Decode a member of an Aleo struct into a Rust value
+pub fn struct_member<N: Network, T: FromPlaintext<N>>(plaintext: &Plaintext<N>, name: &str) -> Result<T> {
+    let members = match plaintext {
+        Plaintext::Struct(members, _) => members,
+        Plaintext::Literal(literal, _) => bail!("Expected a struct, found a {} literal", literal.to_type()),
+    };
+    let member = members
+        .get(&Identifier::from_str(name)?)
+        .ok_or_else(|| anyhow!("The struct does not contain the member '{name}'"))?;
+    T::from_plaintext(member).map_err(|e| anyhow!("Invalid struct member '{name}': {e}"))
+}
+
+/// Decode an output of a function execution into a Rust value
+pub fn decode_output<N: Network, T: FromValue<N>>(response: &Response<N>, index: usize) -> Result<T> {
+    decode_value(response.outputs(), index)
+}
+
+/// Decode one of a list of Aleo values, such as the outputs of an offline execution, into a Rust value
+pub fn decode_value<N: Network, T: FromValue<N>>(values: &[Value<N>], index: usize) -> Result<T> {
+    let value =
+        values.get(index).ok_or_else(|| anyhow!("There are {} values, value {index} does not exist", values.len()))?;
+    T::from_value(value)
+}
+
+/// Builder of Aleo structs from Rust values, with members given in the order the struct declares
+///
+/// Basic Usage:
+/// let point = StructBuilder::<Testnet3>::new().member("x", 1u32).member("y", 2u32).build()?;
+#[derive(Debug)]
+pub struct StructBuilder<N: Network> {
+    members: Result<IndexMap<Identifier<N>, Plaintext<N>>>,
+}
+
+impl<N: Network> Default for StructBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Network> StructBuilder<N> {
+    /// Create a builder of a struct without members
+    pub fn new() -> Self {
+        Self { members: Ok(IndexMap::new()) }
+    }
+
+    /// Add a member to the struct. Errors are reported when the struct is built.
+    pub fn member(mut self, name: &str, value: impl ToPlaintext<N>) -> Self {
+        self.members = self.members.and_then(|mut members| {
+            let identifier = Identifier::from_str(name)?;
+            ensure!(!members.contains_key(&identifier), "The struct member '{name}' is given twice");
+            let plaintext = value.to_plaintext().map_err(|e| anyhow!("Invalid struct member '{name}': {e}"))?;
+            members.insert(identifier, plaintext);
+            Ok(members)
+        });
+        self
+    }
+
+    /// Build the struct plaintext
+    pub fn build(self) -> Result<Plaintext<N>> {
+        let members = self.members?;
+        ensure!(!members.is_empty(), "A struct must have at least one member");
+        Ok(Plaintext::Struct(members, Default::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_literal_conversions() {
+        let value = ToValue::<Testnet3>::to_value(&10u64).unwrap();
+        assert_eq!(value, Value::from_str("10u64").unwrap());
+        assert_eq!(u64::from_value(&value).unwrap(), 10);
+        assert!(u32::from_value(&value).is_err());
+
+        let plaintext = ToPlaintext::<Testnet3>::to_plaintext(&-3i8).unwrap();
+        assert_eq!(plaintext, Plaintext::from_str("-3i8").unwrap());
+        assert!(bool::from_plaintext(&Plaintext::<Testnet3>::from_str("true").unwrap()).unwrap());
+        let string = Plaintext::<Testnet3>::from_str("\"hello\"").unwrap();
+        assert_eq!(String::from_plaintext(&string).unwrap(), "hello");
+        assert_eq!(ToPlaintext::<Testnet3>::to_plaintext(&"hello".to_string()).unwrap(), string);
+
+        let address = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        assert_eq!(address.to_value().unwrap(), Value::from_str(RECIPIENT_ADDRESS).unwrap());
+        assert_eq!(Address::from_value(&address.to_value().unwrap()).unwrap(), address);
+    }
+
+    #[test]
+    fn test_struct_builder() {
+        let inner = StructBuilder::<Testnet3>::new().member("x", 1u32).member("y", 2u32).build().unwrap();
+        let outer = StructBuilder::<Testnet3>::new().member("point", &inner).member("flag", false).build().unwrap();
+        assert_eq!(outer, Plaintext::from_str("{ point: { x: 1u32, y: 2u32 }, flag: false }").unwrap());
+        assert_eq!(struct_member::<_, u32>(&inner, "y").unwrap(), 2);
+        assert_eq!(struct_member::<_, Plaintext<Testnet3>>(&outer, "point").unwrap(), inner);
+        assert!(struct_member::<_, u64>(&inner, "y").is_err());
+        assert!(struct_member::<_, u32>(&inner, "z").is_err());
+
+        assert!(StructBuilder::<Testnet3>::new().member("x", 1u32).member("x", 2u32).build().is_err());
+        assert!(StructBuilder::<Testnet3>::new().member("not valid", 1u32).build().is_err());
+        assert!(StructBuilder::<Testnet3>::new().build().is_err());
+    }
+}
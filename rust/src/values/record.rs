@@ -0,0 +1,191 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::{prelude::Zero, program::Owner, types::Group};
+
+/// Conversion of a Rust value into an Aleo record
+pub trait ToRecord<N: Network> {
+    /// Convert the value into a record plaintext
+    fn to_record(&self) -> Result<Record<N, Plaintext<N>>>;
+}
+
+/// Conversion of an Aleo record into a Rust value
+pub trait FromRecord<N: Network>: Sized {
+    /// Convert a record plaintext into the value, failing if an entry of the value is missing or of
+    /// another type
+    fn from_record(record: &Record<N, Plaintext<N>>) -> Result<Self>;
+}
+
+impl<N: Network> ToRecord<N> for Record<N, Plaintext<N>> {
+    fn to_record(&self) -> Result<Record<N, Plaintext<N>>> {
+        Ok(self.clone())
+    }
+}
+
+impl<N: Network> FromRecord<N> for Record<N, Plaintext<N>> {
+    fn from_record(record: &Record<N, Plaintext<N>>) -> Result<Self> {
+        Ok(record.clone())
+    }
+}
+
+impl<N: Network> ToValue<N> for Record<N, Plaintext<N>> {
+    fn to_value(&self) -> Result<Value<N>> {
+        Ok(Value::Record(self.clone()))
+    }
+}
+
+impl<N: Network> FromValue<N> for Record<N, Plaintext<N>> {
+    fn from_value(value: &Value<N>) -> Result<Self> {
+        record_of(value).cloned()
+    }
+}
+
+/// Get the record of an Aleo value, failing if the value is a plaintext
+pub(crate) fn record_of<N: Network>(value: &Value<N>) -> Result<&Record<N, Plaintext<N>>> {
+    match value {
+        Value::Record(record) => Ok(record),
+        Value::Plaintext(_) => bail!("Expected a record, found a plaintext value"),
+    }
+}
+
+/// Get the address owning a record
+pub fn record_owner<N: Network>(record: &Record<N, Plaintext<N>>) -> Result<Address<N>> {
+    match record.owner() {
+        Owner::Public(address) => Ok(*address),
+        Owner::Private(plaintext) => Address::from_plaintext(plaintext),
+    }
+}
+
+/// Decode an entry of an Aleo record into a Rust value, whatever its visibility
+pub fn record_entry<N: Network, T: FromPlaintext<N>>(record: &Record<N, Plaintext<N>>, name: &str) -> Result<T> {
+    let entry = record
+        .data()
+        .get(&Identifier::from_str(name)?)
+        .ok_or_else(|| anyhow!("The record does not contain the entry '{name}'"))?;
+    let plaintext = match entry {
+        Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext) => plaintext,
+    };
+    T::from_plaintext(plaintext).map_err(|e| anyhow!("Invalid record entry '{name}': {e}"))
+}
+
+type RecordEntries<N> = IndexMap<Identifier<N>, Entry<N, Plaintext<N>>>;
+
+/// Builder of Aleo records from Rust values, with entries given in the order the record declares
+///
+/// The owner of the record is private unless [RecordBuilder::public_owner] is called, and the nonce
+/// is zero unless one is given. Records spent by executions must carry the nonce they were created
+/// with on chain.
+///
+/// Basic Usage:
+/// let token = RecordBuilder::new(owner).private("amount", 100u64).nonce(nonce).build()?;
+#[derive(Debug)]
+pub struct RecordBuilder<N: Network> {
+    owner: Address<N>,
+    public_owner: bool,
+    entries: Result<RecordEntries<N>>,
+    nonce: Group<N>,
+}
+
+impl<N: Network> RecordBuilder<N> {
+    /// Create a builder of a record owned by an address
+    pub fn new(owner: Address<N>) -> Self {
+        Self { owner, public_owner: false, entries: Ok(IndexMap::new()), nonce: Group::zero() }
+    }
+
+    /// Make the owner of the record public
+    pub fn public_owner(mut self) -> Self {
+        self.public_owner = true;
+        self
+    }
+
+    /// Set the nonce of the record
+    pub fn nonce(mut self, nonce: Group<N>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Add a constant entry to the record. Errors are reported when the record is built.
+    pub fn constant(self, name: &str, value: impl ToPlaintext<N>) -> Self {
+        self.entry(name, value, Entry::Constant)
+    }
+
+    /// Add a public entry to the record. Errors are reported when the record is built.
+    pub fn public(self, name: &str, value: impl ToPlaintext<N>) -> Self {
+        self.entry(name, value, Entry::Public)
+    }
+
+    /// Add a private entry to the record. Errors are reported when the record is built.
+    pub fn private(self, name: &str, value: impl ToPlaintext<N>) -> Self {
+        self.entry(name, value, Entry::Private)
+    }
+
+    /// Build the record plaintext
+    pub fn build(self) -> Result<Record<N, Plaintext<N>>> {
+        let owner = match self.public_owner {
+            true => Owner::Public(self.owner),
+            false => Owner::Private(self.owner.to_plaintext()?),
+        };
+        Record::<N, Plaintext<N>>::from_plaintext(owner, self.entries?, self.nonce)
+    }
+
+    fn entry(
+        mut self,
+        name: &str,
+        value: impl ToPlaintext<N>,
+        entry: fn(Plaintext<N>) -> Entry<N, Plaintext<N>>,
+    ) -> Self {
+        self.entries = self.entries.and_then(|mut entries| {
+            let identifier = Identifier::from_str(name)?;
+            ensure!(!entries.contains_key(&identifier), "The record entry '{name}' is given twice");
+            let plaintext = value.to_plaintext().map_err(|e| anyhow!("Invalid record entry '{name}': {e}"))?;
+            entries.insert(identifier, entry(plaintext));
+            Ok(entries)
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_record_builder() {
+        let owner = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let point = StructBuilder::new().member("x", 1u32).member("y", 2u32).build().unwrap();
+        let record = RecordBuilder::new(owner).private("amount", 100u64).public("point", &point).build().unwrap();
+        let expected = format!(
+            "{{ owner: {owner}.private, amount: 100u64.private, point: {{ x: 1u32.public, y: 2u32.public }}, _nonce: 0group.public }}"
+        );
+        assert_eq!(record, Record::from_str(&expected).unwrap());
+        assert_eq!(record_owner(&record).unwrap(), owner);
+        assert_eq!(record_entry::<_, u64>(&record, "amount").unwrap(), 100);
+        assert_eq!(record_entry::<_, Plaintext<Testnet3>>(&record, "point").unwrap(), point);
+        assert!(record_entry::<_, u64>(&record, "missing").is_err());
+
+        let value = record.to_value().unwrap();
+        assert_eq!(Record::from_value(&value).unwrap(), record);
+        assert!(u64::from_value(&value).is_err());
+
+        let public = RecordBuilder::new(owner).public_owner().constant("id", 1u8).build().unwrap();
+        assert!(public.owner().is_public());
+        assert_eq!(record_owner(&public).unwrap(), owner);
+        assert!(RecordBuilder::new(owner).private("amount", 1u64).private("amount", 2u64).build().is_err());
+    }
+}
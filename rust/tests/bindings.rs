@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compile the bindings checked in for the test program, so a change to the code generator that
+//! produces invalid Rust fails the build. After changing the generator, regenerate them with
+//! [BindingGenerator::write] below the license header.
+
+#![cfg(feature = "full")]
+
+include!("bindings/bindings_test.rs");
+
+use aleo_rust::{BindingGenerator, FromValue, Program, ToValue, Value};
+use snarkvm_console::network::Testnet3;
+
+use std::str::FromStr;
+
+const LICENSE_HEADER: &str = include_str!("../../.resources/license_header");
+
+#[test]
+fn test_checked_in_bindings_are_current() {
+    let program = Program::<Testnet3>::from_str(include_str!("bindings/bindings_test.aleo")).unwrap();
+    let bindings = BindingGenerator::new(program.clone()).generate().unwrap();
+    let checked_in = include_str!("bindings/bindings_test.rs").strip_prefix(LICENSE_HEADER).unwrap();
+    assert_eq!(bindings, checked_in.trim_start());
+    assert_eq!(<bindings_test::BindingsTestProgram<Testnet3>>::program().unwrap(), program);
+    assert_eq!(bindings_test::PROGRAM_ID, program.id().to_string());
+}
+
+#[test]
+fn test_checked_in_bindings_convert_values() {
+    let point = bindings_test::Point { x: 1, y: 2 };
+    let value: Value<Testnet3> = point.to_value().unwrap();
+    assert_eq!(value, Value::from_str("{ x: 1u32, y: 2u32 }").unwrap());
+    assert_eq!(bindings_test::Point::from_value(&value).unwrap(), point);
+}
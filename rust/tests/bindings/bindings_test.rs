@@ -0,0 +1,161 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+// Rust bindings for bindings_test.aleo, generated by aleo-rust. Do not edit by hand.
+
+/// Typed bindings for bindings_test.aleo
+pub mod bindings_test {
+    #![allow(dead_code, clippy::too_many_arguments, clippy::type_complexity)]
+
+    /// The ID of the program
+    pub const PROGRAM_ID: &str = "bindings_test.aleo";
+
+    /// The source of the program the bindings were generated from
+    pub const PROGRAM: &str = r#"program bindings_test.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+struct payment:
+    to as address;
+    at as point;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+    origin as point.public;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast 0u32 0u32 into r2 as point;
+    cast r0 r1 r2 into r3 as token.record;
+    output r3 as token.record;
+
+function pay:
+    input r0 as token.record;
+    input r1 as payment.public;
+    output r1 as payment.public;
+    output r0.amount as u64.private;
+"#;
+
+    /// The `point` struct of bindings_test.aleo
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    aleo_rust::aleo_struct!(Point { x, y });
+
+    /// The `payment` struct of bindings_test.aleo
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Payment<N: aleo_rust::Network> {
+        pub to: aleo_rust::Address<N>,
+        pub at: Point,
+    }
+
+    aleo_rust::aleo_struct!(Payment<N> { to, at });
+
+    /// The `token` record of bindings_test.aleo
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Token<N: aleo_rust::Network> {
+        pub owner: aleo_rust::Address<N>,
+        pub amount: u64,
+        pub origin: Point,
+        pub nonce: aleo_rust::Group<N>,
+    }
+
+    aleo_rust::aleo_record!(Token<N> { amount: private, origin: public });
+
+    /// Typed calls to the functions of bindings_test.aleo
+    pub struct BindingsTestProgram<'a, N: aleo_rust::Network, C: aleo_rust::NetworkClient<N> = aleo_rust::AleoAPIClient<N>> {
+        manager: &'a mut aleo_rust::ProgramManager<N, C>,
+    }
+
+    impl<'a, N: aleo_rust::Network, C: aleo_rust::NetworkClient<N>> BindingsTestProgram<'a, N, C> {
+        /// Call the functions of the program with a program manager
+        pub fn new(manager: &'a mut aleo_rust::ProgramManager<N, C>) -> Self {
+            Self { manager }
+        }
+
+        /// Get the program the bindings were generated from
+        pub fn program() -> aleo_rust::values::__private::Result<aleo_rust::Program<N>> {
+            <aleo_rust::Program<N> as core::str::FromStr>::from_str(PROGRAM)
+        }
+
+        /// Execute the `mint` function on the network and get the ID of its transaction
+        pub fn mint(
+            &mut self,
+            r0: aleo_rust::Address<N>,
+            r1: u64,
+            priority_fee: u64,
+            fee_record: aleo_rust::Record<N, aleo_rust::Plaintext<N>>,
+            password: Option<&str>,
+        ) -> aleo_rust::values::__private::Result<String> {
+            let inputs: Vec<aleo_rust::Value<N>> = vec![aleo_rust::ToValue::<N>::to_value(&r0)?, aleo_rust::ToValue::<N>::to_value(&r1)?];
+            self.manager.execute_program(PROGRAM_ID, "mint", inputs.into_iter(), priority_fee, fee_record, password)
+        }
+
+        /// Execute the `mint` function offline and decode its outputs
+        pub fn mint_offline<A: aleo_rust::Aleo<Network = N>>(
+            &self,
+            private_key: &aleo_rust::PrivateKey<N>,
+            r0: aleo_rust::Address<N>,
+            r1: u64,
+            imports: &[aleo_rust::Program<N>],
+            url: &str,
+        ) -> aleo_rust::values::__private::Result<(aleo_rust::program::OfflineExecution<N>, Token<N>)> {
+            let inputs: Vec<aleo_rust::Value<N>> = vec![aleo_rust::ToValue::<N>::to_value(&r0)?, aleo_rust::ToValue::<N>::to_value(&r1)?];
+            let program = Self::program()?;
+            let execution = self.manager.execute_program_offline::<A>(private_key, &program, "mint", imports, inputs.into_iter(), true, url)?;
+            let outputs = execution.outputs().unwrap_or_default();
+            let outputs = aleo_rust::values::decode_value(&outputs, 0)?;
+            Ok((execution, outputs))
+        }
+
+        /// Execute the `pay` function on the network and get the ID of its transaction
+        pub fn pay(
+            &mut self,
+            r0: Token<N>,
+            r1: Payment<N>,
+            priority_fee: u64,
+            fee_record: aleo_rust::Record<N, aleo_rust::Plaintext<N>>,
+            password: Option<&str>,
+        ) -> aleo_rust::values::__private::Result<String> {
+            let inputs: Vec<aleo_rust::Value<N>> = vec![aleo_rust::ToValue::<N>::to_value(&r0)?, aleo_rust::ToValue::<N>::to_value(&r1)?];
+            self.manager.execute_program(PROGRAM_ID, "pay", inputs.into_iter(), priority_fee, fee_record, password)
+        }
+
+        /// Execute the `pay` function offline and decode its outputs
+        pub fn pay_offline<A: aleo_rust::Aleo<Network = N>>(
+            &self,
+            private_key: &aleo_rust::PrivateKey<N>,
+            r0: Token<N>,
+            r1: Payment<N>,
+            imports: &[aleo_rust::Program<N>],
+            url: &str,
+        ) -> aleo_rust::values::__private::Result<(aleo_rust::program::OfflineExecution<N>, (Payment<N>, u64))> {
+            let inputs: Vec<aleo_rust::Value<N>> = vec![aleo_rust::ToValue::<N>::to_value(&r0)?, aleo_rust::ToValue::<N>::to_value(&r1)?];
+            let program = Self::program()?;
+            let execution = self.manager.execute_program_offline::<A>(private_key, &program, "pay", imports, inputs.into_iter(), true, url)?;
+            let outputs = execution.outputs().unwrap_or_default();
+            let outputs = (aleo_rust::values::decode_value(&outputs, 0)?, aleo_rust::values::decode_value(&outputs, 1)?);
+            Ok((execution, outputs))
+        }
+    }
+}
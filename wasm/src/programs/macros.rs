@@ -43,6 +43,18 @@ macro_rules! execute_program {
         let function_name = IdentifierNative::from_str(&$function_id_string)
             .map_err(|_| "The function name provided was invalid".to_string())?;
 
+        log("Checking the inputs against the function signature");
+        {
+            let private_key: &$crate::types::PrivateKeyNative = &$private_key;
+            let caller = $crate::types::AddressNative::try_from(private_key).map_err(|e| e.to_string())?;
+            let imports = program.imports().keys().filter_map(|import_id| $process.get_program(*import_id).ok());
+            aleo_rust::InputValidator::new(&program)
+                .with_imports(imports)
+                .with_caller(caller)
+                .validate(function_name, &inputs_native)
+                .map_err(|e| e.to_string())?;
+        }
+
         let program_id = program.id().to_string();
 
         if program_id != "credits.aleo" {